@@ -1,12 +1,30 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use std::error::Error;
+use std::io::{stdin, stdout};
 use std::path::PathBuf;
+use std::process::exit;
+use std::time::Duration;
 
-use clap::Parser;
+use clap::{CommandFactory, Parser, Subcommand};
+use clap::error::ErrorKind;
+use colored::Colorize;
 
-use cirquil::headless::{HeadlessArgs, run_player_headless};
+use cirquil::core::simulation::components::external::allow_external_commands;
+use cirquil::headless::{HeadlessArgs, parse_timeout, run_player_headless};
+use cirquil::core::analysis::equivalence::EquivalenceOptions;
+use cirquil::headless::check::{CheckArgs, run_check};
+use cirquil::headless::equivalence::{EquivalenceArgs, run_equivalence};
+use cirquil::headless::import::{ImportArgs, run_import};
+use cirquil::headless::interactive::{InteractiveArgs, run_interactive};
+use cirquil::headless::render::{RenderArgs, run_render};
+use cirquil::headless::remote::{RemoteArgs, run_remote};
+use cirquil::headless::synthesize::{run_synthesize, SynthesizeArgs};
+use cirquil::headless::vectors::{run_vectors, VectorArgs};
+use cirquil::headless::verilog::{ExportArgs, run_export};
 use cirquil::player::run_player_gui;
+use cirquil::remote::server::RemoteServer;
+use cirquil::serde::summary::StopReason;
 
 /// Cirquil circuit simulator
 #[derive(Parser, Debug)]
@@ -16,12 +34,16 @@ struct CirquilArgs {
     #[arg(long)]
     headless: bool,
 
+    /// Read commands from stdin instead of running a fixed number of cycles
+    #[arg(long, requires = "headless")]
+    interactive: bool,
+
     /// Circuit to load
     #[arg(long, required_if_eq("headless", "true"))]
     circuit: Option<PathBuf>,
 
-    /// Workbench to load
-    #[arg(long, required_if_eq("headless", "true"))]
+    /// Workbench to load, required in headless mode unless interactive or remote
+    #[arg(long)]
     workbench: Option<PathBuf>,
 
     /// Replay file to save
@@ -32,24 +54,279 @@ struct CirquilArgs {
     #[arg(long, requires = "headless")]
     trace: Option<PathBuf>,
 
-    /// How many cycles to simulate
-    #[arg(long, requires = "headless", required_if_eq("headless", "true"))]
+    /// VCD waveform file to save
+    #[arg(long, requires = "headless")]
+    vcd: Option<PathBuf>,
+
+    /// Stimulus file (JSON or CSV) with input values to set before cycles
+    #[arg(long, requires = "headless")]
+    stimulus: Option<PathBuf>,
+
+    /// Stop once a condition over probe names holds, e.g. "done == 1"
+    #[arg(long, requires = "headless")]
+    until: Option<String>,
+
+    /// Stop after this many seconds of wall-clock time
+    #[arg(long, requires = "headless", value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+
+    /// Accept remote control clients on this loopback address, e.g. 127.0.0.1:7070
+    #[arg(long, conflicts_with = "interactive")]
+    remote: Option<String>,
+
+    /// JSON summary file to save
+    #[arg(long, requires = "headless")]
+    summary: Option<PathBuf>,
+
+    /// How many cycles to simulate, required in headless mode unless interactive or remote
+    #[arg(long, requires = "headless")]
     cycles: Option<usize>,
+
+    /// Run the commands of external components, which can execute any program
+    #[arg(long, global = true)]
+    allow_external: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run design rule checks and exit with non-zero code on errors
+    Check {
+        /// Circuit to check
+        #[arg(long)]
+        circuit: PathBuf,
+    },
+    /// Run test vectors on the top circuit and exit with non-zero code on failures
+    Test {
+        /// Circuit to test
+        #[arg(long)]
+        circuit: PathBuf,
+
+        /// Test vectors in Logisim text format or CSV
+        #[arg(long)]
+        vectors: PathBuf,
+    },
+    /// Compare outputs of two circuits and exit with non-zero code if they differ
+    Equivalence {
+        /// Reference circuit
+        #[arg(long)]
+        first: PathBuf,
+
+        /// Circuit compared against the reference
+        #[arg(long)]
+        second: PathBuf,
+
+        /// Largest number of input bits checked exhaustively
+        #[arg(long, default_value_t = EquivalenceOptions::default().max_exhaustive_bits)]
+        max_exhaustive_bits: u32,
+
+        /// How many random input vectors to try for larger circuits
+        #[arg(long, default_value_t = EquivalenceOptions::default().random_vectors)]
+        vectors: u64,
+
+        /// Seed of the random input vectors
+        #[arg(long, default_value_t = EquivalenceOptions::default().seed)]
+        seed: u64,
+    },
+    /// Export a project as structural Verilog
+    Export {
+        /// Circuit to export
+        #[arg(long)]
+        circuit: PathBuf,
+
+        /// Verilog file to save
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Import a Yosys JSON netlist as a project
+    Import {
+        /// Netlist written by Yosys `write_json`
+        #[arg(long)]
+        netlist: PathBuf,
+
+        /// Project file to save
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Draw a circuit as an SVG image
+    Render {
+        /// Circuit to draw
+        #[arg(long)]
+        circuit: PathBuf,
+
+        /// SVG file to save
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Workbench whose probes are drawn
+        #[arg(long)]
+        workbench: Option<PathBuf>,
+
+        /// Circuit instance to draw, like main/adder with subcircuits named by label or UUID, the top circuit by default
+        #[arg(long)]
+        instance: Option<String>,
+
+        /// How many cycles to simulate before drawing
+        #[arg(long, default_value_t = 0)]
+        cycles: usize,
+
+        /// Color wires and pins by their values
+        #[arg(long)]
+        values: bool,
+    },
+    /// Build a circuit from a truth table or boolean expressions
+    Synthesize {
+        /// Behavioural definition to synthesize
+        #[arg(long)]
+        definition: PathBuf,
+
+        /// Circuit file to save
+        #[arg(long)]
+        output: PathBuf,
+
+        /// Name of the generated circuit
+        #[arg(long, default_value = "main")]
+        name: String,
+    },
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
     let args = CirquilArgs::parse();
+    allow_external_commands(args.allow_external);
 
-    if args.headless {
-        run_player_headless(HeadlessArgs {
+    if let Some(command) = args.command {
+        match command {
+            Command::Check { circuit } => {
+                match run_check(CheckArgs { circuit_path: circuit }) {
+                    Ok(0) => {}
+                    Ok(_) => exit(1),
+                    Err(error) => {
+                        eprintln!("{}: {error}", "ERROR".red());
+                        exit(1);
+                    }
+                }
+            }
+            Command::Test { circuit, vectors } => {
+                match run_vectors(VectorArgs { circuit_path: circuit, vectors_path: vectors }) {
+                    Ok(0) => {}
+                    Ok(_) => exit(1),
+                    Err(error) => {
+                        eprintln!("{}: {error}", "ERROR".red());
+                        exit(1);
+                    }
+                }
+            }
+            Command::Equivalence { first, second, max_exhaustive_bits, vectors, seed } => {
+                let args = EquivalenceArgs {
+                    first_path: first,
+                    second_path: second,
+                    options: EquivalenceOptions { max_exhaustive_bits, random_vectors: vectors, seed },
+                };
+
+                match run_equivalence(args) {
+                    Ok(true) => {}
+                    Ok(false) => exit(1),
+                    Err(error) => {
+                        eprintln!("{}: {error}", "ERROR".red());
+                        exit(1);
+                    }
+                }
+            }
+            Command::Export { circuit, output } => {
+                if let Err(error) = run_export(ExportArgs { circuit_path: circuit, output_path: output }) {
+                    eprintln!("{}: {error}", "ERROR".red());
+                    exit(1);
+                }
+            }
+            Command::Import { netlist, output } => {
+                if let Err(error) = run_import(ImportArgs { netlist_path: netlist, output_path: output }) {
+                    eprintln!("{}: {error}", "ERROR".red());
+                    exit(1);
+                }
+            }
+            Command::Render { circuit, out, workbench, instance, cycles, values } => {
+                let args = RenderArgs { circuit_path: circuit, output_path: out, workbench_path: workbench, instance, cycles, values };
+
+                if let Err(error) = run_render(args) {
+                    eprintln!("{}: {error}", "ERROR".red());
+                    exit(1);
+                }
+            }
+            Command::Synthesize { definition, output, name } => {
+                let args = SynthesizeArgs { definition_path: definition, output_path: output, name };
+
+                if let Err(error) = run_synthesize(args) {
+                    eprintln!("{}: {error}", "ERROR".red());
+                    exit(1);
+                }
+            }
+        }
+
+        return Ok(());
+    }
+
+    if let (true, Some(address)) = (args.headless, &args.remote) {
+        let args = RemoteArgs { circuit_path: args.circuit.unwrap(), workbench_path: args.workbench, address: address.clone() };
+
+        if let Err(error) = run_remote(args) {
+            eprintln!("{}: {error}", "ERROR".red());
+            exit(1);
+        }
+    } else if args.headless && args.interactive {
+        let args = InteractiveArgs { circuit_path: args.circuit.unwrap(), workbench_path: args.workbench };
+
+        if let Err(error) = run_interactive(args, stdin().lock(), stdout()) {
+            eprintln!("{}: {error}", "ERROR".red());
+            exit(1);
+        }
+    } else if args.headless {
+        if args.workbench.is_none() || args.cycles.is_none() {
+            CirquilArgs::command()
+                .error(ErrorKind::MissingRequiredArgument, "--workbench and --cycles are required in headless mode")
+                .exit();
+        }
+
+        let result = run_player_headless(HeadlessArgs {
             circuit_path: args.circuit.unwrap(),
             workbench_path: args.workbench.unwrap(),
             cycles: args.cycles.unwrap(),
             trace_path: args.trace,
+            vcd_path: args.vcd,
             replay_path: args.replay,
-        })?;
+            stimulus_path: args.stimulus,
+            until: args.until,
+            timeout: args.timeout,
+            summary_path: args.summary,
+        });
+
+        // Errors exit with 1, runs which did not finish as expected with their own codes
+        match result {
+            Ok(summary) => match summary.stop_reason {
+                StopReason::Cycles | StopReason::Condition => {}
+                StopReason::ConditionNotMet => exit(2),
+                StopReason::Timeout => exit(3),
+                StopReason::AssertionFailed => exit(4),
+            },
+            Err(error) => {
+                eprintln!("{}: {error}", "ERROR".red());
+                exit(1);
+            }
+        }
     } else {
-        run_player_gui(args.circuit, args.workbench)?;
+        let remote = match &args.remote {
+            Some(address) => match RemoteServer::bind(address) {
+                Ok(server) => Some(server),
+                Err(error) => {
+                    eprintln!("{}: {error}", "ERROR".red());
+                    exit(1);
+                }
+            },
+            None => None,
+        };
+
+        run_player_gui(args.circuit, args.workbench, remote)?;
     }
 
     Ok(())
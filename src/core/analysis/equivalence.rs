@@ -0,0 +1,185 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::core::analysis::Harness;
+use crate::core::expression::width_mask;
+use crate::core::simulation::circuit::Circuit;
+use crate::core::simulation::components::behavioural::definition::Port;
+use crate::core::simulation::value::Value;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EquivalenceError {
+    /// Port of one circuit has no port with the same label in the other.
+    MissingPort { name: String },
+    WidthMismatch { name: String, first: u8, second: u8 },
+    NoOutputs,
+}
+
+impl Display for EquivalenceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EquivalenceError::MissingPort { name } => {
+                write!(f, "Port {} does not exist in both circuits", name)
+            }
+            EquivalenceError::WidthMismatch { name, first, second } => {
+                write!(f, "Port {} is {} bits wide in the first circuit and {} bits in the second", name, first, second)
+            }
+            EquivalenceError::NoOutputs => {
+                write!(f, "Circuits have no output pins")
+            }
+        }
+    }
+}
+
+impl Error for EquivalenceError {}
+
+#[derive(Debug, Clone)]
+pub struct EquivalenceOptions {
+    /// Input spaces up to this many bits are checked exhaustively.
+    pub max_exhaustive_bits: u32,
+    /// Number of random input vectors tried for larger input spaces.
+    pub random_vectors: u64,
+    pub seed: u64,
+}
+
+impl Default for EquivalenceOptions {
+    fn default() -> Self {
+        EquivalenceOptions {
+            max_exhaustive_bits: 16,
+            random_vectors: 100_000,
+            seed: 0x2545f4914f6cdd1d,
+        }
+    }
+}
+
+/// Input vector on which the circuits differ, `outputs` lists only the
+/// outputs which do not match as the first and the second circuit value.
+#[derive(Debug, Clone)]
+pub struct Counterexample {
+    pub inputs: Vec<(Port, u32)>,
+    pub outputs: Vec<(Port, Value, Value)>,
+}
+
+impl Display for Counterexample {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let inputs: Vec<String> = self.inputs.iter()
+            .map(|(port, value)| format!("{}={}", port.name, Value::create(*value, port.bit_width).to_bit_string(port.bit_width)))
+            .collect();
+        write!(f, "inputs {}", inputs.join(" "))?;
+
+        for (port, first, second) in self.outputs.iter() {
+            write!(f, ", {}: {} != {}", port.name, first.to_bit_string(port.bit_width), second.to_bit_string(port.bit_width))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Equivalence {
+    /// No difference found in `vectors` input vectors, which prove the
+    /// equivalence when `exhaustive` is set.
+    Equivalent { vectors: u64, exhaustive: bool },
+    Different(Counterexample),
+}
+
+/// Xorshift generator, good enough to pick test vectors and reproducible
+/// from the seed.
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// Compares outputs of two combinational circuits, ports are matched by
+/// label. Inputs are taken in the order of the first circuit.
+pub fn check_equivalence(first: &Circuit, second: &Circuit, options: &EquivalenceOptions) -> Result<Equivalence, EquivalenceError> {
+    let first = Harness::new(first);
+    let mut second = Harness::new(second);
+
+    // Reorder ports of the second circuit to follow the first one
+    for (first_ports, second_ports) in [(&first.inputs, &mut second.inputs), (&first.outputs, &mut second.outputs)] {
+        if let Some((_, port)) = second_ports.iter().find(|(_, port)| !first_ports.iter().any(|(_, other)| other.name == port.name)) {
+            return Err(EquivalenceError::MissingPort { name: port.name.clone() });
+        }
+
+        let mut ordered = Vec::new();
+        for (_, port) in first_ports.iter() {
+            let Some(matching) = second_ports.iter().find(|(_, other)| other.name == port.name) else {
+                return Err(EquivalenceError::MissingPort { name: port.name.clone() });
+            };
+
+            if matching.1.bit_width != port.bit_width {
+                return Err(EquivalenceError::WidthMismatch {
+                    name: port.name.clone(),
+                    first: port.bit_width,
+                    second: matching.1.bit_width,
+                });
+            }
+
+            ordered.push(matching.clone());
+        }
+
+        *second_ports = ordered;
+    }
+
+    if first.outputs.is_empty() {
+        return Err(EquivalenceError::NoOutputs);
+    }
+
+    let compare = |inputs: Vec<u32>| -> Option<Counterexample> {
+        let outputs: Vec<(Port, Value, Value)> = first.apply(&inputs).into_iter()
+            .zip(second.apply(&inputs))
+            .zip(first.outputs.iter())
+            .filter(|((a, b), (_, port))| a.to_bit_string(port.bit_width) != b.to_bit_string(port.bit_width))
+            .map(|((a, b), (_, port))| (port.clone(), a, b))
+            .collect();
+
+        if outputs.is_empty() {
+            return None;
+        }
+
+        let inputs = first.inputs.iter().map(|(_, port)| port.clone()).zip(inputs).collect();
+        Some(Counterexample { inputs, outputs })
+    };
+
+    let bits = first.get_input_bits();
+
+    if bits <= options.max_exhaustive_bits.min(63) {
+        let vectors = 1u64 << bits;
+
+        for vector in 0..vectors {
+            let mut shift = bits;
+            let inputs = first.inputs.iter()
+                .map(|(_, port)| {
+                    shift -= port.bit_width as u32;
+                    ((vector >> shift) & width_mask(port.bit_width)) as u32
+                })
+                .collect();
+
+            if let Some(counterexample) = compare(inputs) {
+                return Ok(Equivalence::Different(counterexample));
+            }
+        }
+
+        return Ok(Equivalence::Equivalent { vectors, exhaustive: true });
+    }
+
+    let mut random = Random(options.seed.max(1));
+    for _ in 0..options.random_vectors {
+        let inputs = first.inputs.iter()
+            .map(|(_, port)| (random.next() & width_mask(port.bit_width)) as u32)
+            .collect();
+
+        if let Some(counterexample) = compare(inputs) {
+            return Ok(Equivalence::Different(counterexample));
+        }
+    }
+
+    Ok(Equivalence::Equivalent { vectors: options.random_vectors, exhaustive: false })
+}
@@ -0,0 +1,133 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::core::expression::{BinaryOp, Expr};
+
+/// Product term, variables whose bits are set in `mask` are eliminated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Implicant {
+    pub bits: u64,
+    pub mask: u64,
+}
+
+impl Implicant {
+    pub fn covers(&self, minterm: u64) -> bool {
+        minterm & !self.mask == self.bits
+    }
+
+    fn combine(&self, other: &Implicant) -> Option<Implicant> {
+        let difference = self.bits ^ other.bits;
+
+        if self.mask == other.mask && difference.count_ones() == 1 {
+            Some(Implicant { bits: self.bits & !difference, mask: self.mask | difference })
+        } else {
+            None
+        }
+    }
+}
+
+/// Finds all prime implicants with the Quine–McCluskey method.
+pub fn prime_implicants(minterms: &[u64], dont_cares: &[u64]) -> Vec<Implicant> {
+    let mut primes: BTreeSet<Implicant> = BTreeSet::new();
+    let mut current: BTreeSet<Implicant> = minterms.iter().chain(dont_cares.iter())
+        .map(|&bits| Implicant { bits, mask: 0 })
+        .collect();
+
+    while !current.is_empty() {
+        // Only terms with the same mask and one more set bit can combine
+        let mut groups: BTreeMap<(u64, u32), Vec<Implicant>> = BTreeMap::new();
+        for implicant in current.iter() {
+            groups.entry((implicant.mask, implicant.bits.count_ones())).or_default().push(*implicant);
+        }
+
+        let mut next: BTreeSet<Implicant> = BTreeSet::new();
+        let mut combined: BTreeSet<Implicant> = BTreeSet::new();
+
+        for ((mask, ones), group) in groups.iter() {
+            let Some(neighbours) = groups.get(&(*mask, ones + 1)) else { continue; };
+
+            for a in group.iter() {
+                for b in neighbours.iter() {
+                    if let Some(implicant) = a.combine(b) {
+                        next.insert(implicant);
+                        combined.insert(*a);
+                        combined.insert(*b);
+                    }
+                }
+            }
+        }
+
+        primes.extend(current.difference(&combined));
+        current = next;
+    }
+
+    primes.into_iter().collect()
+}
+
+/// Picks prime implicants covering every minterm: essential ones first,
+/// then greedily the one covering the most minterms left.
+pub fn minimize(minterms: &[u64], dont_cares: &[u64]) -> Vec<Implicant> {
+    let primes = prime_implicants(minterms, dont_cares);
+    let mut remaining: BTreeSet<u64> = minterms.iter().copied().collect();
+    let mut chosen: BTreeSet<Implicant> = BTreeSet::new();
+
+    for minterm in minterms.iter() {
+        let mut covering = primes.iter().filter(|prime| prime.covers(*minterm));
+
+        if let (Some(prime), None) = (covering.next(), covering.next()) {
+            chosen.insert(*prime);
+        }
+    }
+
+    remaining.retain(|minterm| !chosen.iter().any(|prime| prime.covers(*minterm)));
+
+    while !remaining.is_empty() {
+        let best = primes.iter()
+            .filter(|prime| !chosen.contains(prime))
+            .max_by_key(|prime| {
+                let covered = remaining.iter().filter(|minterm| prime.covers(**minterm)).count();
+                (covered, prime.mask.count_ones(), std::cmp::Reverse(**prime))
+            })
+            .copied()
+            .unwrap();
+
+        remaining.retain(|minterm| !best.covers(*minterm));
+        chosen.insert(best);
+    }
+
+    chosen.into_iter().collect()
+}
+
+/// Builds a sum of products, variable `k` of `variables` is bit `n - 1 - k`
+/// of the implicants, so the first variable is the most significant.
+pub fn to_sum_of_products(implicants: &[Implicant], variables: &[Expr]) -> Expr {
+    let count = variables.len();
+    let all = if count >= 64 { u64::MAX } else { (1 << count) - 1 };
+
+    if implicants.is_empty() {
+        return Expr::Constant(0);
+    }
+
+    if implicants.iter().any(|implicant| implicant.mask & all == all) {
+        return Expr::Constant(1);
+    }
+
+    implicants.iter()
+        .map(|implicant| {
+            variables.iter().enumerate()
+                .filter_map(|(k, variable)| {
+                    let bit = 1 << (count - 1 - k);
+
+                    if implicant.mask & bit != 0 {
+                        None
+                    } else if implicant.bits & bit != 0 {
+                        Some(variable.clone())
+                    } else {
+                        Some(Expr::complement(variable.clone()))
+                    }
+                })
+                .reduce(|product, literal| Expr::binary(BinaryOp::And, product, literal))
+                .unwrap()
+        })
+        .reduce(|sum, product| Expr::binary(BinaryOp::Or, sum, product))
+        .unwrap()
+}
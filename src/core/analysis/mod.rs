@@ -0,0 +1,275 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::core::analysis::minimize::{minimize, to_sum_of_products};
+use crate::core::expression::Expr;
+use crate::core::simulation::circuit::Circuit;
+use crate::core::simulation::component::{Component, ComponentIdx, ComponentModel};
+use crate::core::simulation::components::behavioural::definition::{Definition, Port};
+use crate::core::simulation::value::Value;
+
+pub mod equivalence;
+pub mod minimize;
+pub mod vectors;
+
+/// Largest number of input bits analysed by default, the truth table has
+/// two to the power of this many rows.
+pub const DEFAULT_MAX_INPUT_BITS: u32 = 12;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnalysisError {
+    TooManyInputs { bits: u32, limit: u32 },
+    NoOutputs,
+}
+
+impl Display for AnalysisError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnalysisError::TooManyInputs { bits, limit } => {
+                write!(f, "Circuit has {} input bits, at most {} can be analysed", bits, limit)
+            }
+            AnalysisError::NoOutputs => {
+                write!(f, "Circuit has no output pins")
+            }
+        }
+    }
+}
+
+impl Error for AnalysisError {}
+
+/// Outputs of a circuit for every combination of its input pins.
+#[derive(Debug, Clone)]
+pub struct TruthTable {
+    pub inputs: Vec<Port>,
+    pub outputs: Vec<Port>,
+    /// Output values, row `n` has the inputs of `n` written in binary with
+    /// the first input in the most significant bits.
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl TruthTable {
+    pub fn get_input_bits(&self) -> u32 {
+        self.inputs.iter().map(|port| port.bit_width as u32).sum()
+    }
+
+    pub fn get_row_inputs(&self, row: usize) -> Vec<u32> {
+        let mut shift = self.get_input_bits();
+
+        self.inputs.iter()
+            .map(|port| {
+                shift -= port.bit_width as u32;
+                ((row as u64 >> shift) & ((1 << port.bit_width) - 1)) as u32
+            })
+            .collect()
+    }
+
+    /// One expression per input bit, most significant first.
+    fn get_variables(&self) -> Vec<Expr> {
+        self.inputs.iter()
+            .flat_map(|port| {
+                (0..port.bit_width).rev().map(move |bit| match port.bit_width {
+                    1 => Expr::variable(&port.name),
+                    _ => Expr::Slice { name: port.name.clone(), high: bit, low: bit },
+                })
+            })
+            .collect()
+    }
+
+    /// Minimal sum of products for every output bit. Undefined outputs are
+    /// treated as don't-cares.
+    pub fn get_expressions(&self) -> Vec<(String, Expr)> {
+        let variables = self.get_variables();
+        let mut expressions = Vec::new();
+
+        for (idx, port) in self.outputs.iter().enumerate() {
+            for bit in (0..port.bit_width).rev() {
+                let mut minterms = Vec::new();
+                let mut dont_cares = Vec::new();
+
+                for (row, outputs) in self.rows.iter().enumerate() {
+                    let value = outputs[idx];
+
+                    if (value.get_raw_mask() >> bit) & 1 != 0 {
+                        dont_cares.push(row as u64);
+                    } else if (value.get_raw_value() >> bit) & 1 != 0 {
+                        minterms.push(row as u64);
+                    }
+                }
+
+                let name = match port.bit_width {
+                    1 => port.name.clone(),
+                    _ => format!("{}[{}]", port.name, bit),
+                };
+
+                expressions.push((name, to_sum_of_products(&minimize(&minterms, &dont_cares), &variables)));
+            }
+        }
+
+        expressions
+    }
+
+    fn get_header(&self) -> Vec<String> {
+        self.inputs.iter().chain(self.outputs.iter())
+            .map(|port| port.name.clone())
+            .collect()
+    }
+
+    fn get_cells(&self, row: usize) -> Vec<String> {
+        let inputs = self.get_row_inputs(row).into_iter().zip(self.inputs.iter())
+            .map(|(value, port)| Value::create(value, port.bit_width).to_bit_string(port.bit_width));
+        let outputs = self.rows[row].iter().zip(self.outputs.iter())
+            .map(|(value, port)| value.to_bit_string(port.bit_width));
+
+        inputs.chain(outputs).collect()
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut writer = csv::Writer::from_writer(vec![]);
+
+        writer.write_record(self.get_header()).unwrap();
+        for row in 0..self.rows.len() {
+            writer.write_record(self.get_cells(row)).unwrap();
+        }
+
+        String::from_utf8(writer.into_inner().unwrap()).unwrap()
+    }
+
+    /// Table followed by the minimized expressions.
+    pub fn to_markdown(&self) -> String {
+        let header = self.get_header();
+        let mut markdown = format!("| {} |\n|{}\n", header.join(" | "), "---|".repeat(header.len()));
+
+        for row in 0..self.rows.len() {
+            markdown.push_str(&format!("| {} |\n", self.get_cells(row).join(" | ")));
+        }
+
+        markdown.push('\n');
+        for (name, expression) in self.get_expressions() {
+            markdown.push_str(&format!("- `{} = {}`\n", name, expression));
+        }
+
+        markdown
+    }
+}
+
+fn get_port(component: &Component) -> Port {
+    Port {
+        name: component.get_properties().get_string("label").unwrap_or_default(),
+        bit_width: component.get_pins()[0].bit_width,
+    }
+}
+
+/// Copy of a circuit driven through its input pins.
+struct Harness {
+    circuit: Circuit,
+    inputs: Vec<(ComponentIdx, Port)>,
+    outputs: Vec<(ComponentIdx, Port)>,
+}
+
+impl Harness {
+    fn new(circuit: &Circuit) -> Self {
+        let circuit = circuit.deep_clone();
+
+        let find_ports = |matches: fn(&ComponentModel) -> bool| -> Vec<(ComponentIdx, Port)> {
+            circuit.components.iter().enumerate()
+                .filter(|(_, component)| matches(&component.model))
+                .map(|(idx, component)| (idx, get_port(component)))
+                .collect()
+        };
+
+        let inputs = find_ports(|model| matches!(model, ComponentModel::InputPin(_)));
+        let outputs = find_ports(|model| matches!(model, ComponentModel::OutputPin(_)));
+
+        circuit.reset();
+        circuit.propagate_all();
+
+        Harness { circuit, inputs, outputs }
+    }
+
+    fn get_input_bits(&self) -> u32 {
+        self.inputs.iter().map(|(_, port)| port.bit_width as u32).sum()
+    }
+
+    /// Sets the input pins in order and returns the values of the output pins.
+    fn apply(&self, values: &[u32]) -> Vec<Value> {
+        let values: Vec<Value> = self.inputs.iter().zip(values)
+            .map(|((_, port), value)| Value::create(*value, port.bit_width))
+            .collect();
+
+        self.apply_values(&values)
+    }
+
+    fn apply_values(&self, values: &[Value]) -> Vec<Value> {
+        let mut changed = Vec::new();
+
+        for ((idx, _), value) in self.inputs.iter().zip(values) {
+            let component = &self.circuit.components[*idx];
+            if let ComponentModel::InputPin(pin) = &component.model {
+                pin.value.set(*value);
+            }
+            changed.push(component);
+        }
+
+        self.circuit.propagate(changed);
+
+        self.outputs.iter()
+            .map(|(idx, _)| match &self.circuit.components[*idx].model {
+                ComponentModel::OutputPin(pin) => pin.value.get(),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+}
+
+/// Simulates every combination of input pin values on a copy of `circuit`.
+///
+/// The circuit is expected to be combinational, stateful parts keep the
+/// state left by the previous row.
+pub fn analyze_circuit(circuit: &Circuit, max_input_bits: u32) -> Result<TruthTable, AnalysisError> {
+    let harness = Harness::new(circuit);
+
+    if harness.outputs.is_empty() {
+        return Err(AnalysisError::NoOutputs);
+    }
+
+    let bits = harness.get_input_bits();
+    if bits > max_input_bits {
+        return Err(AnalysisError::TooManyInputs { bits, limit: max_input_bits });
+    }
+
+    let table = TruthTable {
+        inputs: harness.inputs.iter().map(|(_, port)| port.clone()).collect(),
+        outputs: harness.outputs.iter().map(|(_, port)| port.clone()).collect(),
+        rows: vec![],
+    };
+
+    let rows = (0..1usize << bits)
+        .map(|row| harness.apply(&table.get_row_inputs(row)))
+        .collect();
+
+    Ok(TruthTable { rows, ..table })
+}
+
+/// Evaluates a behavioural definition for every combination of its inputs.
+pub fn tabulate_definition(definition: &Definition, max_input_bits: u32) -> Result<TruthTable, AnalysisError> {
+    let table = TruthTable {
+        inputs: definition.get_inputs().to_vec(),
+        outputs: definition.get_outputs().to_vec(),
+        rows: vec![],
+    };
+
+    if table.outputs.is_empty() {
+        return Err(AnalysisError::NoOutputs);
+    }
+
+    let bits = table.get_input_bits();
+    if bits > max_input_bits {
+        return Err(AnalysisError::TooManyInputs { bits, limit: max_input_bits });
+    }
+
+    let rows = (0..1usize << bits)
+        .map(|row| definition.evaluate(&table.get_row_inputs(row)))
+        .collect();
+
+    Ok(TruthTable { rows, ..table })
+}
@@ -0,0 +1,88 @@
+use crate::core::analysis::Harness;
+use crate::core::simulation::circuit::Circuit;
+use crate::core::simulation::value::Value;
+use crate::serde::vectors::{TestVectors, VectorError};
+
+/// Output which did not match the expected value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorFailure {
+    pub name: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorResult {
+    pub line: usize,
+    pub failures: Vec<VectorFailure>,
+}
+
+impl VectorResult {
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+enum Column {
+    Input(usize),
+    Output(usize),
+}
+
+/// Applies test vectors to the input pins of a copy of `circuit` in order and
+/// compares its output pins. Inputs missing from the vectors stay unknown,
+/// state is kept between rows.
+pub fn run_test_vectors(circuit: &Circuit, vectors: &TestVectors) -> Result<Vec<VectorResult>, VectorError> {
+    let harness = Harness::new(circuit);
+    let header_error = |message: String| VectorError { line: 0, message };
+
+    let columns = vectors.columns.iter()
+        .map(|column| {
+            let input = harness.inputs.iter().position(|(_, port)| port.name == column.name);
+            let output = harness.outputs.iter().position(|(_, port)| port.name == column.name);
+
+            let (column_kind, port) = match (input, output) {
+                (Some(idx), _) => (Column::Input(idx), &harness.inputs[idx].1),
+                (None, Some(idx)) => (Column::Output(idx), &harness.outputs[idx].1),
+                (None, None) => return Err(header_error(format!("circuit has no pin labelled {}", column.name))),
+            };
+
+            match column.bit_width {
+                Some(bit_width) if bit_width != port.bit_width => Err(header_error(format!(
+                    "pin {} is {} bits wide, not {}", column.name, port.bit_width, bit_width,
+                ))),
+                _ => Ok(column_kind),
+            }
+        })
+        .collect::<Result<Vec<Column>, VectorError>>()?;
+
+    let mut inputs: Vec<Value> = vec![Value::default(); harness.inputs.len()];
+
+    let results = vectors.rows.iter()
+        .map(|row| {
+            for (column, value) in columns.iter().zip(row.values.iter()) {
+                if let Column::Input(idx) = column {
+                    inputs[*idx] = value.to_value(harness.inputs[*idx].1.bit_width);
+                }
+            }
+
+            let outputs = harness.apply_values(&inputs);
+
+            let failures = columns.iter().zip(row.values.iter()).zip(vectors.columns.iter())
+                .filter_map(|((column, expected), vector_column)| {
+                    let Column::Output(idx) = column else { return None; };
+                    let bit_width = harness.outputs[*idx].1.bit_width;
+
+                    (!expected.matches(outputs[*idx], bit_width)).then(|| VectorFailure {
+                        name: vector_column.name.clone(),
+                        expected: expected.to_bit_string(bit_width),
+                        actual: outputs[*idx].to_bit_string(bit_width),
+                    })
+                })
+                .collect();
+
+            VectorResult { line: row.line, failures }
+        })
+        .collect();
+
+    Ok(results)
+}
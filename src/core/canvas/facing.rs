@@ -0,0 +1,71 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::canvas::location::Location;
+
+/// Rotation of a component on the canvas. Components are defined facing east,
+/// other facings turn them clockwise in quarter turns around their location.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Facing {
+    North,
+    #[default]
+    East,
+    South,
+    West,
+}
+
+impl Facing {
+    /// Returns the number of clockwise quarter turns from east.
+    pub fn get_turns(&self) -> u8 {
+        match self {
+            Facing::East => 0,
+            Facing::South => 1,
+            Facing::West => 2,
+            Facing::North => 3,
+        }
+    }
+
+    pub fn from_turns(turns: u8) -> Self {
+        match turns % 4 {
+            0 => Facing::East,
+            1 => Facing::South,
+            2 => Facing::West,
+            _ => Facing::North,
+        }
+    }
+
+    pub fn rotated_clockwise(&self) -> Self {
+        Self::from_turns(self.get_turns() + 1)
+    }
+
+    /// Returns the rotation between `base` and this facing, which is how a
+    /// component drawn facing `base` has to be turned to face this way.
+    pub fn relative_to(&self, base: Facing) -> Self {
+        Self::from_turns(self.get_turns() + 4 - base.get_turns())
+    }
+
+    /// Rotates an offset given for an east-facing component.
+    pub fn rotate(&self, location: Location) -> Location {
+        match self {
+            Facing::East => location,
+            Facing::South => Location::new(-location.y, location.x),
+            Facing::West => Location::new(-location.x, -location.y),
+            Facing::North => Location::new(location.y, -location.x),
+        }
+    }
+}
+
+impl FromStr for Facing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "north" => Ok(Facing::North),
+            "east" => Ok(Facing::East),
+            "south" => Ok(Facing::South),
+            "west" => Ok(Facing::West),
+            _ => Err(format!("Unknown facing \"{}\"", s)),
+        }
+    }
+}
@@ -2,3 +2,4 @@ pub mod location;
 pub mod circuit;
 pub mod wire;
 pub mod component;
+pub mod facing;
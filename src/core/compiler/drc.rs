@@ -0,0 +1,266 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+use uuid::Uuid;
+
+use crate::core::canvas::circuit::CanvasCircuit;
+use crate::core::canvas::location::Location;
+use crate::core::compiler::project::InstantiatedCircuits;
+use crate::core::simulation::circuit::Circuit;
+use crate::core::simulation::component::ComponentModel;
+use crate::core::simulation::pin::{Direction, PinIdx};
+use crate::core::simulation::property::PropertyError;
+use crate::core::simulation::wire::WireIdx;
+use crate::serde::project::{ProjectFile, SavedCircuit};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiagnosticKind {
+    UnconnectedInput { component: Uuid, pin: PinIdx },
+    UndrivenWire { wire: WireIdx },
+    MultipleDrivers { wire: WireIdx, drivers: Vec<(Uuid, PinIdx)> },
+    DanglingWireEnd,
+    LonelyTunnel { component: Uuid, label: String },
+    UnmatchedPort { label: String, direction: Direction },
+    BitWidthMismatch { wire: WireIdx, widths: Vec<u8> },
+    InvalidProperty { component: Uuid, error: PropertyError },
+}
+
+/// Design rule violation located in [`CanvasCircuit`] coordinates.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub kind: DiagnosticKind,
+    pub circuit: String,
+    pub location: Location,
+}
+
+impl Diagnostic {
+    fn warning(kind: DiagnosticKind, circuit: &str, location: Location) -> Self {
+        Diagnostic { severity: Severity::Warning, kind, circuit: circuit.to_string(), location }
+    }
+
+    fn error(kind: DiagnosticKind, circuit: &str, location: Location) -> Self {
+        Diagnostic { severity: Severity::Error, kind, circuit: circuit.to_string(), location }
+    }
+
+    pub fn message(&self) -> String {
+        match &self.kind {
+            DiagnosticKind::UnconnectedInput { component, pin } => {
+                format!("input pin {} of component {} is not connected", pin, component)
+            }
+            DiagnosticKind::UndrivenWire { wire } => {
+                format!("wire {} has no driver", wire)
+            }
+            DiagnosticKind::MultipleDrivers { wire, drivers } => {
+                let drivers: Vec<String> = drivers.iter()
+                    .map(|(component, pin)| format!("{} pin {}", component, pin))
+                    .collect();
+
+                format!("wire {} has {} drivers: {}", wire, drivers.len(), drivers.join(", "))
+            }
+            DiagnosticKind::DanglingWireEnd => {
+                "wire end is not connected to anything".to_string()
+            }
+            DiagnosticKind::LonelyTunnel { label, .. } => {
+                format!("tunnel label \"{}\" is used only once", label)
+            }
+            DiagnosticKind::UnmatchedPort { label, direction } => {
+                format!("{:?} port \"{}\" has no matching pin inside the circuit", direction, label)
+            }
+            DiagnosticKind::BitWidthMismatch { wire, widths } => {
+                format!("wire {} connects pins of different bit widths {:?}", wire, widths)
+            }
+            DiagnosticKind::InvalidProperty { component, error } => {
+                format!("component {}: {}", component, error)
+            }
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            format!("{:?} in {} at ({}, {}): {}",
+                    self.severity, self.circuit, self.location.x, self.location.y, self.message())
+                .as_str()
+        )
+    }
+}
+
+/// Runs design rule checks over every circuit of a compiled project.
+///
+/// Connectivity checks need an instance of the circuit, so circuits which are not
+/// reachable from the top circuit are only checked for tunnel and port labels.
+pub fn check_project(project: &ProjectFile, circuits: &InstantiatedCircuits) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+
+    for (canvas_idx, canvas) in circuits.canvas_circuits.iter().enumerate() {
+        if let Some(saved) = project.circuits.get(&canvas.name) {
+            check_labels(&canvas.name, saved, &mut diagnostics);
+            check_properties(&canvas.name, saved, &mut diagnostics);
+        }
+
+        let instance = circuits.instantiated_circuits.iter()
+            .find(|(_, idx)| *idx == canvas_idx);
+
+        if let Some((circuit, _)) = instance {
+            check_connectivity(circuit, canvas, &mut diagnostics);
+        }
+    }
+
+    diagnostics.sort_by_key(|d| std::cmp::Reverse(d.severity));
+    diagnostics
+}
+
+fn check_labels(name: &str, saved: &SavedCircuit, diagnostics: &mut Vec<Diagnostic>) {
+    let mut tunnels: HashMap<&str, Vec<(Uuid, Location)>> = HashMap::new();
+    let mut pin_labels: HashSet<(String, Direction)> = HashSet::new();
+
+    for saved_component in saved.components.iter() {
+        let component = &saved_component.component;
+        let label = component.get_properties().get("label")
+            .and_then(|p| p.as_string())
+            .map(|p| p.get());
+
+        match (&component.model, label) {
+            (ComponentModel::Tunnel(tunnel), _) => {
+                tunnels.entry(tunnel.name.as_str())
+                    .or_default()
+                    .push((component.uuid, saved_component.location));
+            }
+            (ComponentModel::InputPin(_), Some(label)) => {
+                pin_labels.insert((label, Direction::Input));
+            }
+            (ComponentModel::OutputPin(_), Some(label)) => {
+                pin_labels.insert((label, Direction::Output));
+            }
+            _ => {}
+        }
+    }
+
+    let mut tunnels: Vec<(&str, Vec<(Uuid, Location)>)> = tunnels.into_iter().collect();
+    tunnels.sort_by_key(|(label, _)| *label);
+
+    for (label, occurrences) in tunnels.iter() {
+        if let [(component, location)] = occurrences.as_slice() {
+            diagnostics.push(Diagnostic::warning(
+                DiagnosticKind::LonelyTunnel { component: *component, label: label.to_string() },
+                name, *location,
+            ));
+        }
+    }
+
+    for port in saved.pins.iter() {
+        if !pin_labels.contains(&(port.label.clone(), port.direction)) {
+            diagnostics.push(Diagnostic::error(
+                DiagnosticKind::UnmatchedPort { label: port.label.clone(), direction: port.direction },
+                name, port.location,
+            ));
+        }
+    }
+}
+
+fn check_properties(name: &str, saved: &SavedCircuit, diagnostics: &mut Vec<Diagnostic>) {
+    for saved_component in saved.components.iter() {
+        let component = &saved_component.component;
+
+        for error in component.get_properties().validate(&component.model.get_schema()) {
+            diagnostics.push(Diagnostic::error(
+                DiagnosticKind::InvalidProperty { component: component.uuid, error },
+                name, saved_component.location,
+            ));
+        }
+    }
+}
+
+fn check_connectivity(circuit: &Circuit, canvas: &CanvasCircuit, diagnostics: &mut Vec<Diagnostic>) {
+    let name = canvas.name.as_str();
+    let mut pin_locations: HashSet<Location> = HashSet::new();
+
+    for canvas_component in canvas.components.iter() {
+        let component = circuit.get_component(canvas_component.component);
+
+        for (pin_idx, pin) in component.get_pins().iter().enumerate() {
+            let location = canvas_component.loc + pin.location;
+            pin_locations.insert(location);
+
+            if pin.direction == Direction::Input && pin.wire.get().is_none() {
+                diagnostics.push(Diagnostic::warning(
+                    DiagnosticKind::UnconnectedInput { component: component.uuid, pin: pin_idx },
+                    name, location,
+                ));
+            }
+        }
+    }
+
+    let mut ends: HashMap<Location, usize> = HashMap::new();
+    for canvas_wire in canvas.wires.iter() {
+        for (start, end) in canvas_wire.segments.iter() {
+            *ends.entry(*start).or_default() += 1;
+            *ends.entry(*end).or_default() += 1;
+        }
+    }
+
+    let mut dangling: Vec<Location> = ends.into_iter()
+        .filter(|(location, count)| *count == 1 && !pin_locations.contains(location))
+        .map(|(location, _)| location)
+        .collect();
+    dangling.sort_by_key(|location| (location.x, location.y));
+
+    for location in dangling {
+        diagnostics.push(Diagnostic::warning(DiagnosticKind::DanglingWireEnd, name, location));
+    }
+
+    for (wire_idx, wire) in circuit.wires.iter().enumerate() {
+        let pin_location = |(component_idx, pin_idx): (usize, PinIdx)| -> Location {
+            let loc = canvas.components.iter()
+                .find(|c| c.component == component_idx)
+                .map(|c| c.loc)
+                .unwrap_or(Location::new(0, 0));
+
+            loc + circuit.get_component(component_idx).get_pins()[pin_idx].location
+        };
+
+        let location = canvas.wires.iter()
+            .find(|w| w.wire == wire_idx)
+            .and_then(|w| w.segments.first())
+            .map(|(start, _)| *start)
+            .or_else(|| wire.connected_components.first().map(|c| pin_location(*c)))
+            .unwrap_or(Location::new(0, 0));
+
+        let drivers: Vec<(Uuid, PinIdx)> = wire.connected_components.iter()
+            .filter(|(component_idx, pin_idx)| {
+                circuit.get_component(*component_idx).get_pins()[*pin_idx].direction == Direction::Output
+            })
+            .map(|(component_idx, pin_idx)| (circuit.get_component(*component_idx).uuid, *pin_idx))
+            .collect();
+
+        let tri_state_drivers = wire.connected_components.iter()
+            .filter(|(component_idx, pin_idx)| {
+                circuit.get_component(*component_idx).get_pins()[*pin_idx].direction == Direction::Inout
+            })
+            .count();
+
+        if drivers.is_empty() && tri_state_drivers == 0 {
+            diagnostics.push(Diagnostic::warning(DiagnosticKind::UndrivenWire { wire: wire_idx }, name, location));
+        } else if drivers.len() > 1 {
+            diagnostics.push(Diagnostic::error(DiagnosticKind::MultipleDrivers { wire: wire_idx, drivers }, name, location));
+        }
+
+        let mut widths: Vec<u8> = wire.connected_components.iter()
+            .map(|(component_idx, pin_idx)| circuit.get_component(*component_idx).get_pins()[*pin_idx].bit_width)
+            .collect();
+        widths.sort_unstable();
+        widths.dedup();
+
+        if widths.len() > 1 {
+            diagnostics.push(Diagnostic::error(DiagnosticKind::BitWidthMismatch { wire: wire_idx, widths }, name, location));
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use uuid::Uuid;
+
+use crate::core::canvas::location::Location;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompileError {
+    MissingProperty {
+        circuit: String,
+        component: Uuid,
+        location: Location,
+        property: String,
+    },
+    UnknownSubcircuit {
+        circuit: String,
+        component: Uuid,
+        location: Location,
+        name: String,
+    },
+    UuidClash {
+        circuit: String,
+        component: Uuid,
+        location: Location,
+    },
+    MissingTopCircuit {
+        name: String,
+    },
+    RecursiveSubcircuit {
+        circuit: String,
+        component: Uuid,
+        location: Location,
+        chain: Vec<String>,
+    },
+}
+
+impl Display for CompileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompileError::MissingProperty { circuit, component, location, property } => {
+                write!(f, "Circuit {}: component {} at ({}, {}) has no \"{}\" property",
+                       circuit, component, location.x, location.y, property)
+            }
+            CompileError::UnknownSubcircuit { circuit, component, location, name } => {
+                write!(f, "Circuit {}: subcircuit {} at ({}, {}) refers to unknown circuit \"{}\"",
+                       circuit, component, location.x, location.y, name)
+            }
+            CompileError::UuidClash { circuit, component, location } => {
+                write!(f, "Circuit {}: UUID {} of component at ({}, {}) is used more than once",
+                       circuit, component, location.x, location.y)
+            }
+            CompileError::MissingTopCircuit { name } => {
+                write!(f, "Top circuit \"{}\" does not exist", name)
+            }
+            CompileError::RecursiveSubcircuit { circuit, component, location, chain } => {
+                write!(f, "Circuit {}: subcircuit {} at ({}, {}) instantiates itself: {}",
+                       circuit, component, location.x, location.y, chain.join(" → "))
+            }
+        }
+    }
+}
+
+impl Error for CompileError {}
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use uuid::Uuid;
+
+use crate::core::canvas::circuit::CanvasCircuit;
+use crate::core::compiler::circuit::compile_circuit;
+use crate::core::compiler::error::CompileError;
+use crate::core::compiler::project::{instantiate_project, InstantiatedCircuits, traverse_uuids};
+use crate::core::simulation::circuit::{Circuit, CircuitIdx};
+use crate::core::simulation::component::ComponentModel;
+use crate::core::simulation::components::subcircuit::Subcircuit;
+use crate::serde::project::{ProjectFile, SavedCircuit};
+
+/// Keeps compiled circuits between edits so that changing one circuit only
+/// recomputes its own nets and rebuilds its own instances.
+///
+/// Instances of the edited circuit are rebuilt from the new template, while
+/// their ancestors are cloned with the subcircuit pointers replaced. Wires and
+/// stateful components are carried over by component UUID. When the edit adds
+/// or removes subcircuits, the simulation tree is instantiated again and the
+/// state is carried over by instance path.
+#[derive(Debug)]
+pub struct IncrementalCompiler {
+    project: ProjectFile,
+    templates: Vec<Circuit>,
+    name_to_idx: HashMap<String, CircuitIdx>,
+    top_circuit: CircuitIdx,
+    circuits: InstantiatedCircuits,
+}
+
+impl IncrementalCompiler {
+    pub fn new(project: ProjectFile) -> Result<Self, CompileError> {
+        let mut templates: Vec<Circuit> = Vec::new();
+        let mut canvas_circuits: Vec<CanvasCircuit> = Vec::new();
+        let mut name_to_idx: HashMap<String, CircuitIdx> = HashMap::new();
+
+        for (name, saved) in project.circuits.iter() {
+            let (compiled, canvas) = compile_circuit(name.clone(), saved.clone())?;
+            name_to_idx.insert(name.clone(), templates.len());
+            templates.push(compiled);
+            canvas_circuits.push(canvas);
+        }
+
+        let (top_circuit, circuits) = instantiate_project(
+            project.top_circuit.as_str(), &name_to_idx, &templates, canvas_circuits,
+        )?;
+
+        circuits.reset();
+
+        Ok(IncrementalCompiler { project, templates, name_to_idx, top_circuit, circuits })
+    }
+
+    pub fn get_project(&self) -> &ProjectFile {
+        &self.project
+    }
+
+    pub fn get_circuits(&self) -> &InstantiatedCircuits {
+        &self.circuits
+    }
+
+    pub fn get_top_circuit(&self) -> CircuitIdx {
+        self.top_circuit
+    }
+
+    /// Replaces circuit `name` with an edited version, adding it if it is new.
+    ///
+    /// On error the compiler keeps the previous project and simulation state.
+    pub fn update_circuit(&mut self, name: &str, saved: SavedCircuit) -> Result<(), CompileError> {
+        let (template, canvas) = compile_circuit(name.to_string(), saved.clone())?;
+        let by_uuid = traverse_uuids(&template, &canvas)?;
+
+        let Some(&idx) = self.name_to_idx.get(name) else {
+            let mut templates = self.templates.clone();
+            let mut canvas_circuits = self.circuits.canvas_circuits.clone();
+            let mut name_to_idx = self.name_to_idx.clone();
+
+            name_to_idx.insert(name.to_string(), templates.len());
+            templates.push(template);
+            canvas_circuits.push(canvas);
+
+            self.reinstantiate(templates, canvas_circuits, name_to_idx)?;
+            self.project.circuits.insert(name.to_string(), saved);
+
+            return Ok(());
+        };
+
+        if get_subcircuits(&template) != get_subcircuits(&self.templates[idx]) {
+            let mut templates = self.templates.clone();
+            let mut canvas_circuits = self.circuits.canvas_circuits.clone();
+
+            templates[idx] = template;
+            canvas_circuits[idx] = canvas;
+
+            self.reinstantiate(templates, canvas_circuits, self.name_to_idx.clone())?;
+            self.project.circuits.insert(name.to_string(), saved);
+
+            return Ok(());
+        }
+
+        self.rebuild_instances(idx, &template);
+
+        self.templates[idx] = template;
+        self.circuits.canvas_circuits[idx] = canvas;
+        self.circuits.by_uuid[idx] = by_uuid;
+        self.project.circuits.insert(name.to_string(), saved);
+
+        Ok(())
+    }
+
+    /// Rebuilds every instance of circuit type `idx` in place, then replaces
+    /// ancestors which point to a rebuilt instance. Children always precede
+    /// their parents in `instantiated_circuits`, so one pass is enough.
+    fn rebuild_instances(&mut self, idx: CircuitIdx, template: &Circuit) {
+        let mut rebuilt: HashMap<CircuitIdx, Rc<Circuit>> = HashMap::new();
+
+        for (instance, (old, circuit_idx)) in self.circuits.instantiated_circuits.iter().enumerate() {
+            let new = if *circuit_idx == idx {
+                let children: HashMap<Uuid, &ComponentModel> = old.components.iter()
+                    .filter(|c| matches!(c.model, ComponentModel::Subcircuit(_)))
+                    .map(|c| (c.uuid, &c.model))
+                    .collect();
+
+                let mut new = template.clone();
+                for component in new.components.iter_mut() {
+                    if let Some(&model) = children.get(&component.uuid) {
+                        component.model = model.clone();
+                    }
+                }
+
+                transfer_state(&new, old);
+                new
+            } else if old.components.iter().any(|c| get_instance(&c.model).is_some_and(|i| rebuilt.contains_key(&i))) {
+                old.as_ref().clone()
+            } else {
+                continue;
+            };
+
+            let mut new = new;
+            for component in new.components.iter_mut() {
+                if let Some(child) = get_instance(&component.model) {
+                    if let Some(circuit) = rebuilt.get(&child) {
+                        component.model = ComponentModel::Subcircuit(
+                            Subcircuit::Instantiated(circuit.clone(), child)
+                        );
+                    }
+                }
+            }
+
+            rebuilt.insert(instance, Rc::new(new));
+        }
+
+        let mut rebuilt: Vec<(CircuitIdx, Rc<Circuit>)> = rebuilt.into_iter().collect();
+        rebuilt.sort_by_key(|(instance, _)| *instance);
+
+        for (instance, circuit) in rebuilt.into_iter() {
+            for (comp_idx, component) in circuit.components.iter().enumerate() {
+                if let Some(child) = get_instance(&component.model) {
+                    self.circuits.parents[child] = Some((instance, comp_idx));
+                }
+            }
+
+            circuit.propagate_all();
+            self.circuits.instantiated_circuits[instance].0 = circuit;
+        }
+    }
+
+    fn reinstantiate(&mut self,
+                     templates: Vec<Circuit>,
+                     canvas_circuits: Vec<CanvasCircuit>,
+                     name_to_idx: HashMap<String, CircuitIdx>)
+                     -> Result<(), CompileError> {
+        let (top_circuit, circuits) = instantiate_project(
+            self.project.top_circuit.as_str(), &name_to_idx, &templates, canvas_circuits,
+        )?;
+
+        let old_instances: HashMap<(Vec<Uuid>, &str), CircuitIdx> = (0..self.circuits.instantiated_circuits.len())
+            .map(|instance| {
+                ((self.circuits.get_instance_path(instance), self.circuits.get_circuit_name(instance)), instance)
+            })
+            .collect();
+
+        for (instance, (circuit, _)) in circuits.instantiated_circuits.iter().enumerate() {
+            let key = (circuits.get_instance_path(instance), circuits.get_circuit_name(instance));
+
+            if let Some(&old) = old_instances.get(&key) {
+                transfer_state(circuit, &self.circuits.instantiated_circuits[old].0);
+            }
+
+            circuit.propagate_all();
+        }
+
+        self.templates = templates;
+        self.name_to_idx = name_to_idx;
+        self.top_circuit = top_circuit;
+        self.circuits = circuits;
+
+        Ok(())
+    }
+}
+
+/// Copies component state matched by UUID and wire values matched by any
+/// pin which was connected to the same wire before.
+fn transfer_state(new: &Circuit, old: &Circuit) {
+    let old_components: HashMap<Uuid, usize> = old.components.iter().enumerate()
+        .map(|(comp_idx, component)| (component.uuid, comp_idx))
+        .collect();
+
+    for component in new.components.iter() {
+        if let Some(&old_idx) = old_components.get(&component.uuid) {
+            component.copy_state(old.get_component(old_idx));
+        }
+    }
+
+    for wire in new.wires.iter() {
+        let old_wire = wire.connected_components.iter()
+            .find_map(|(comp_idx, pin_idx)| {
+                let old_idx = *old_components.get(&new.get_component(*comp_idx).uuid)?;
+                old.get_component(old_idx).get_pins().get(*pin_idx)?.wire.get()
+            });
+
+        if let Some(old_wire) = old_wire {
+            wire.value.set(old.get_wire(old_wire).value.get());
+        }
+    }
+}
+
+fn get_instance(model: &ComponentModel) -> Option<CircuitIdx> {
+    match model {
+        ComponentModel::Subcircuit(Subcircuit::Instantiated(_, idx)) => Some(*idx),
+        _ => None,
+    }
+}
+
+fn get_subcircuits(circuit: &Circuit) -> Vec<(Uuid, String)> {
+    let mut subcircuits: Vec<(Uuid, String)> = circuit.components.iter()
+        .filter_map(|component| match &component.model {
+            ComponentModel::Subcircuit(Subcircuit::NotInstantiated(name)) => Some((component.uuid, name.clone())),
+            _ => None,
+        })
+        .collect();
+
+    subcircuits.sort();
+    subcircuits
+}
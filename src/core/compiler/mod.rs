@@ -1,3 +1,6 @@
-mod dfs;
 pub mod circuit;
 pub mod project;
+pub mod drc;
+pub mod error;
+pub mod incremental;
+pub mod net;
@@ -0,0 +1,195 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::core::canvas::location::Location;
+use crate::core::simulation::component::{ComponentIdx, ComponentModel};
+use crate::core::simulation::pin::PinIdx;
+use crate::serde::project::{SavedComponent, SavedWire};
+
+/// Electrically connected group of wire segments and component pins.
+#[derive(Debug, Clone, Default)]
+pub struct Net {
+    pub segments: Vec<(Location, Location)>,
+    pub nodes: Vec<Location>,
+    pub pins: Vec<(ComponentIdx, PinIdx)>,
+}
+
+struct DisjointSet {
+    parent: Vec<usize>,
+    ids: HashMap<Location, usize>,
+}
+
+impl DisjointSet {
+    fn new() -> Self {
+        DisjointSet { parent: Vec::new(), ids: HashMap::new() }
+    }
+
+    fn id(&mut self, location: Location) -> usize {
+        if let Some(&id) = self.ids.get(&location) {
+            return id;
+        }
+
+        let id = self.parent.len();
+        self.parent.push(id);
+        self.ids.insert(location, id);
+
+        id
+    }
+
+    fn find(&mut self, location: Location) -> usize {
+        let mut current = self.id(location);
+
+        while self.parent[current] != current {
+            self.parent[current] = self.parent[self.parent[current]];
+            current = self.parent[current];
+        }
+
+        current
+    }
+
+    fn union(&mut self, a: Location, b: Location) {
+        let a = self.find(a);
+        let b = self.find(b);
+
+        self.parent[a] = b;
+    }
+}
+
+/// Splits wires into nets following Logisim rules.
+///
+/// Wires are joined where an end of one wire lies anywhere on another one,
+/// and pins are joined to every wire passing through their location.
+/// Two wires crossing in their middles are not connected. Tunnels with the
+/// same name join their nets. A junction dot is placed wherever three or more
+/// segment ends meet.
+///
+/// Nets are ordered by their first wire and then by their first pin, so a
+/// circuit always compiles to the same wire indices. Pins which touch nothing
+/// but each other also form a net without segments.
+pub fn extract_nets(wires: &[SavedWire], components: &[SavedComponent]) -> Vec<Net> {
+    let pins: Vec<(ComponentIdx, PinIdx, Location)> = components.iter().enumerate()
+        .flat_map(|(comp_idx, saved)| {
+            saved.component.get_pins().iter().enumerate()
+                .map(move |(pin_idx, pin)| (comp_idx, pin_idx, saved.location + pin.location))
+        })
+        .collect();
+
+    let mut columns: HashMap<i16, Vec<i16>> = HashMap::new();
+    let mut rows: HashMap<i16, Vec<i16>> = HashMap::new();
+    let points = wires.iter()
+        .flat_map(|wire| [wire.start, wire.end])
+        .chain(pins.iter().map(|(_, _, location)| *location));
+
+    for point in points {
+        columns.entry(point.x).or_default().push(point.y);
+        rows.entry(point.y).or_default().push(point.x);
+    }
+
+    columns.values_mut().chain(rows.values_mut()).for_each(|coordinates| {
+        coordinates.sort_unstable();
+        coordinates.dedup();
+    });
+
+    let mut segments: Vec<(Location, Location)> = Vec::new();
+    let mut known_segments: HashSet<(Location, Location)> = HashSet::new();
+
+    for wire in wires.iter() {
+        for segment in split_wire(wire, &columns, &rows) {
+            if known_segments.insert(segment) {
+                segments.push(segment);
+            }
+        }
+    }
+
+    let mut set = DisjointSet::new();
+
+    for (start, end) in segments.iter() {
+        set.union(*start, *end);
+    }
+
+    let mut tunnels: HashMap<&str, Location> = HashMap::new();
+    for (comp_idx, _, location) in pins.iter() {
+        if let ComponentModel::Tunnel(tunnel) = &components[*comp_idx].component.model {
+            match tunnels.get(tunnel.name.as_str()) {
+                Some(other) => set.union(*other, *location),
+                None => {
+                    tunnels.insert(tunnel.name.as_str(), *location);
+                }
+            }
+        }
+    }
+
+    let mut net_of_root: HashMap<usize, usize> = HashMap::new();
+    let mut nets: Vec<Net> = Vec::new();
+
+    for (start, end) in segments.iter() {
+        let root = set.find(*start);
+        let net = *net_of_root.entry(root).or_insert_with(|| {
+            nets.push(Net::default());
+            nets.len() - 1
+        });
+
+        nets[net].segments.push((*start, *end));
+    }
+
+    for (comp_idx, pin_idx, location) in pins.iter() {
+        let root = set.find(*location);
+        let net = *net_of_root.entry(root).or_insert_with(|| {
+            nets.push(Net::default());
+            nets.len() - 1
+        });
+
+        nets[net].pins.push((*comp_idx, *pin_idx));
+    }
+
+    for net in nets.iter_mut() {
+        let mut ends: HashMap<Location, usize> = HashMap::new();
+        for (start, end) in net.segments.iter() {
+            *ends.entry(*start).or_default() += 1;
+            *ends.entry(*end).or_default() += 1;
+        }
+
+        net.nodes = ends.into_iter()
+            .filter(|(_, count)| *count >= 3)
+            .map(|(location, _)| location)
+            .collect();
+        net.nodes.sort_by_key(|location| (location.x, location.y));
+    }
+
+    nets.retain(|net| !net.segments.is_empty() || net.pins.len() > 1);
+    nets
+}
+
+/// Cuts a wire at every wire end and pin lying on it. Segments always go
+/// from the smaller coordinate to the larger one.
+fn split_wire(wire: &SavedWire,
+              columns: &HashMap<i16, Vec<i16>>,
+              rows: &HashMap<i16, Vec<i16>>)
+              -> Vec<(Location, Location)> {
+    let (start, end) = if wire.start.x > wire.end.x || wire.start.y > wire.end.y {
+        (wire.end, wire.start)
+    } else {
+        (wire.start, wire.end)
+    };
+
+    if start == end {
+        return vec![];
+    }
+
+    let cuts: Vec<Location> = if start.x == end.x {
+        columns[&start.x].iter()
+            .filter(|&&y| start.y <= y && y <= end.y)
+            .map(|&y| Location::new(start.x, y))
+            .collect()
+    } else if start.y == end.y {
+        rows[&start.y].iter()
+            .filter(|&&x| start.x <= x && x <= end.x)
+            .map(|&x| Location::new(x, start.y))
+            .collect()
+    } else {
+        vec![start, end]
+    };
+
+    cuts.windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .collect()
+}
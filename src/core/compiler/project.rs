@@ -8,8 +8,10 @@ use uuid::Uuid;
 
 use crate::core::canvas::circuit::CanvasCircuit;
 use crate::core::compiler::circuit::compile_circuit;
+use crate::core::compiler::error::CompileError;
 use crate::core::simulation::circuit::{Circuit, CircuitIdx};
 use crate::core::simulation::component::{ComponentIdx, ComponentModel};
+use crate::core::simulation::contention::ContentionReport;
 use crate::core::simulation::components::subcircuit::Subcircuit;
 use crate::serde::project::ProjectFile;
 
@@ -45,29 +47,155 @@ impl InstantiatedCircuits {
 
         self.canvas_circuits.get(*canvas_idx).unwrap().name.as_str()
     }
+
+    /// Returns UUIDs of subcircuit components leading from the top circuit to `idx`.
+    pub fn get_instance_path(&self, idx: CircuitIdx) -> Vec<Uuid> {
+        let mut path: Vec<Uuid> = Vec::new();
+        let mut current = idx;
+
+        while let Some((parent, comp)) = self.parents.get(current).copied().flatten() {
+            path.push(self.instantiated_circuits[parent].0.components[comp].uuid);
+            current = parent;
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// Returns circuit names from the top circuit to `idx`.
+    pub fn get_instance_names(&self, idx: CircuitIdx) -> Vec<&str> {
+        let mut names: Vec<&str> = vec![self.get_circuit_name(idx)];
+        let mut current = idx;
+
+        while let Some((parent, _)) = self.parents.get(current).copied().flatten() {
+            names.push(self.get_circuit_name(parent));
+            current = parent;
+        }
+
+        names.reverse();
+        names
+    }
+
+    /// Returns circuit names from the top circuit to `idx` joined with `/`.
+    pub fn get_instance_name(&self, idx: CircuitIdx) -> String {
+        self.get_instance_names(idx).join("/")
+    }
+
+    /// Returns the top circuit name followed by the subcircuit components
+    /// leading to `idx`. A component is named by its label, or by its UUID
+    /// when it has no label or shares it with another subcircuit of its
+    /// parent, so every instance has its own path.
+    pub fn get_instance_labels(&self, idx: CircuitIdx) -> Vec<String> {
+        let mut labels: Vec<String> = Vec::new();
+        let mut current = idx;
+
+        while let Some((parent, comp)) = self.parents.get(current).copied().flatten() {
+            let components = &self.instantiated_circuits[parent].0.components;
+            let label = |component: ComponentIdx| components[component].get_properties().get_string("label")
+                .filter(|label| !label.is_empty());
+
+            let shared = (0..components.len())
+                .filter(|&other| matches!(components[other].model, ComponentModel::Subcircuit(_)))
+                .filter(|&other| label(other).is_some() && label(other) == label(comp))
+                .count() > 1;
+
+            labels.push(match label(comp) {
+                Some(label) if !shared => label,
+                _ => components[comp].uuid.to_string(),
+            });
+            current = parent;
+        }
+
+        labels.push(self.get_circuit_name(current).to_string());
+        labels.reverse();
+        labels
+    }
+
+    /// Finds the instance whose [`get_instance_labels`](Self::get_instance_labels)
+    /// joined with `/` equal `path`.
+    pub fn find_instance(&self, path: &str) -> Option<CircuitIdx> {
+        (0..self.instantiated_circuits.len()).find(|&idx| self.get_instance_labels(idx).join("/") == path)
+    }
+
+    pub fn get_contentions(&self) -> Vec<ContentionReport> {
+        self.instantiated_circuits.iter().enumerate()
+            .flat_map(|(idx, (circuit, _))| {
+                circuit.contentions.borrow().get_contentions().iter()
+                    .map(|contention| ContentionReport {
+                        circuit: idx,
+                        instance_path: self.get_instance_path(idx),
+                        contention: contention.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    pub fn clear_contentions(&self) {
+        self.instantiated_circuits.iter().for_each(
+            |(circuit, _)| circuit.contentions.borrow_mut().clear()
+        );
+    }
+
+    /// Returns every instantiated circuit to its power-on state.
+    ///
+    /// Stateful components and wires are reinitialised first, then each
+    /// circuit is fully propagated, children before their parents.
+    pub fn reset(&self) {
+        self.instantiated_circuits.iter().for_each(
+            |(circuit, _)| circuit.reset()
+        );
+
+        self.propagate_all();
+    }
+
+    /// Fully propagates each circuit, children before their parents.
+    pub fn propagate_all(&self) {
+        self.instantiated_circuits.iter().for_each(
+            |(circuit, _)| circuit.propagate_all()
+        );
+    }
 }
 
-pub fn compile_project(project: ProjectFile) -> (CircuitIdx, InstantiatedCircuits) {
+pub fn compile_project(project: ProjectFile) -> Result<(CircuitIdx, InstantiatedCircuits), CompileError> {
     let mut canvas_circuits: Vec<CanvasCircuit> = Vec::new();
     let mut compiled_circuits: Vec<Circuit> = Vec::new();
     let mut name_to_idx: HashMap<String, CircuitIdx> = HashMap::new();
     for (name, circ) in project.circuits.into_iter() {
-        let (compiled, canvas) = compile_circuit(name.clone(), circ);
+        let (compiled, canvas) = compile_circuit(name.clone(), circ)?;
         name_to_idx.insert(name, canvas_circuits.len());
         canvas_circuits.push(canvas);
         compiled_circuits.push(compiled);
     }
-    let by_uuid = compiled_circuits.iter()
-        .map(traverse_uuids)
-        .collect();
+
+    instantiate_project(project.top_circuit.as_str(), &name_to_idx, &compiled_circuits, canvas_circuits)
+}
+
+/// Instantiates the simulation tree from already compiled circuits.
+pub(crate) fn instantiate_project(top_circuit: &str,
+                                  name_to_idx: &HashMap<String, CircuitIdx>,
+                                  compiled_circuits: &Vec<Circuit>,
+                                  canvas_circuits: Vec<CanvasCircuit>)
+                                  -> Result<(CircuitIdx, InstantiatedCircuits), CompileError> {
+    let by_uuid = zip(compiled_circuits.iter(), canvas_circuits.iter())
+        .map(|(circuit, canvas)| traverse_uuids(circuit, canvas))
+        .collect::<Result<_, _>>()?;
+
+    let Some(&top_idx) = name_to_idx.get(top_circuit) else {
+        return Err(CompileError::MissingTopCircuit { name: top_circuit.to_string() });
+    };
+
+    find_cycle(top_idx, name_to_idx,
+               compiled_circuits, &canvas_circuits, &mut Vec::new())?;
 
     let mut instantiated_circuits: Vec<(Rc<Circuit>, CircuitIdx)> = Vec::new();
     let mut parents: Vec<Option<(CircuitIdx, ComponentIdx)>> = Vec::new();
-    let simulation_tree = instantiate_tree(project.top_circuit.as_str(), &name_to_idx,
-                                           &compiled_circuits,
+    let simulation_tree = instantiate_tree(top_circuit, name_to_idx,
+                                           compiled_circuits,
+                                           &canvas_circuits,
                                            &mut instantiated_circuits,
-                                           &mut parents);
-    (
+                                           &mut parents)?;
+    Ok((
         instantiated_circuits.len() - 1,
         InstantiatedCircuits {
             canvas_circuits,
@@ -76,15 +204,59 @@ pub fn compile_project(project: ProjectFile) -> (CircuitIdx, InstantiatedCircuit
             by_uuid,
             parents,
         },
-    )
+    ))
+}
+
+/// Walks subcircuit references depth-first keeping the current path in `stack`,
+/// so that a circuit containing itself is reported before it is instantiated.
+fn find_cycle(circuit_idx: CircuitIdx,
+              name_to_idx: &HashMap<String, CircuitIdx>,
+              compiled_circuits: &Vec<Circuit>,
+              canvas_circuits: &Vec<CanvasCircuit>,
+              stack: &mut Vec<CircuitIdx>)
+              -> Result<(), CompileError> {
+    stack.push(circuit_idx);
+
+    for (comp_idx, comp) in compiled_circuits[circuit_idx].components.iter().enumerate() {
+        let ComponentModel::Subcircuit(Subcircuit::NotInstantiated(sub_name)) = &comp.model else {
+            continue;
+        };
+
+        // Unknown names are reported by instantiate_tree
+        let Some(&sub_idx) = name_to_idx.get(sub_name.as_str()) else {
+            continue;
+        };
+
+        if let Some(start) = stack.iter().position(|&idx| idx == sub_idx) {
+            let canvas = &canvas_circuits[circuit_idx];
+            let chain = stack[start..].iter()
+                .chain(std::iter::once(&sub_idx))
+                .map(|&idx| canvas_circuits[idx].name.clone())
+                .collect();
+
+            return Err(CompileError::RecursiveSubcircuit {
+                circuit: canvas.name.clone(),
+                component: comp.uuid,
+                location: canvas.components[comp_idx].loc,
+                chain,
+            });
+        }
+
+        find_cycle(sub_idx, name_to_idx, compiled_circuits, canvas_circuits, stack)?;
+    }
+
+    stack.pop();
+
+    Ok(())
 }
 
 fn instantiate_tree(name: &str,
                     name_to_idx: &HashMap<String, CircuitIdx>,
                     compiled_circuits: &Vec<Circuit>,
+                    canvas_circuits: &Vec<CanvasCircuit>,
                     instantiated_circuits: &mut Vec<(Rc<Circuit>, CircuitIdx)>,
                     parents: &mut Vec<Option<(CircuitIdx, ComponentIdx)>>)
-                    -> SimulationTreeNode {
+                    -> Result<SimulationTreeNode, CompileError> {
     let circuit_idx = name_to_idx[name];
     let mut compiled = compiled_circuits[circuit_idx].clone();
     let mut children_trees: Vec<SimulationTreeNode> = Vec::new();
@@ -92,9 +264,20 @@ fn instantiate_tree(name: &str,
 
     for (comp_idx, comp) in compiled.components.iter_mut().enumerate() {
         if let ComponentModel::Subcircuit(Subcircuit::NotInstantiated(sub_name)) = &comp.model {
+            if !name_to_idx.contains_key(sub_name.as_str()) {
+                let canvas = &canvas_circuits[circuit_idx];
+
+                return Err(CompileError::UnknownSubcircuit {
+                    circuit: canvas.name.clone(),
+                    component: comp.uuid,
+                    location: canvas.components[comp_idx].loc,
+                    name: sub_name.clone(),
+                });
+            }
+
             let sub_tree = instantiate_tree(sub_name,
-                                            name_to_idx, compiled_circuits,
-                                            instantiated_circuits, parents);
+                                            name_to_idx, compiled_circuits, canvas_circuits,
+                                            instantiated_circuits, parents)?;
             let sub_idx = match sub_tree {
                 SimulationTreeNode::Leaf(idx) => { idx }
                 SimulationTreeNode::Node(idx, _) => { idx }
@@ -113,27 +296,31 @@ fn instantiate_tree(name: &str,
     instantiated_circuits.push((Rc::new(compiled), circuit_idx));
     parents.push(None);
     if children_trees.is_empty() {
-        SimulationTreeNode::Leaf(self_idx)
+        Ok(SimulationTreeNode::Leaf(self_idx))
     } else {
         for (circ_idx, &comp_idx) in zip(children_trees.iter(), children_comp_idx.iter()) {
             parents[circ_idx.get_idx()] = Some((self_idx, comp_idx));
         }
-        SimulationTreeNode::Node(self_idx, children_trees)
+        Ok(SimulationTreeNode::Node(self_idx, children_trees))
     }
 }
 
-fn traverse_uuids(circuit: &Circuit)
-                  -> HashMap<Uuid, ComponentIdx> {
+pub(crate) fn traverse_uuids(circuit: &Circuit, canvas: &CanvasCircuit)
+                  -> Result<HashMap<Uuid, ComponentIdx>, CompileError> {
     let mut ret: HashMap<Uuid, ComponentIdx> = HashMap::new();
     for (comp_idx, component) in circuit.components.iter().enumerate() {
         match ret.entry(component.uuid) {
             Entry::Occupied(_) => {
-                panic!("UUID {} clash!!!", component.uuid)
+                return Err(CompileError::UuidClash {
+                    circuit: canvas.name.clone(),
+                    component: component.uuid,
+                    location: canvas.components[comp_idx].loc,
+                });
             }
             Entry::Vacant(vac) => {
                 vac.insert(comp_idx);
             }
         }
     }
-    ret
+    Ok(ret)
 }
@@ -0,0 +1,219 @@
+use std::fmt::{Display, Formatter};
+
+pub mod parser;
+
+pub type BitWidth = u8;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum UnaryOp {
+    Not,
+    LogicalNot,
+    Negate,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BinaryOp {
+    Implies,
+    Or,
+    Xor,
+    And,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    ShiftLeft,
+    ShiftRight,
+    Add,
+    Subtract,
+    Multiply,
+}
+
+impl BinaryOp {
+    fn precedence(&self) -> u8 {
+        match self {
+            BinaryOp::Implies => 0,
+            BinaryOp::Or => 1,
+            BinaryOp::Xor => 2,
+            BinaryOp::And => 3,
+            BinaryOp::Equal | BinaryOp::NotEqual
+            | BinaryOp::Less | BinaryOp::LessEqual
+            | BinaryOp::Greater | BinaryOp::GreaterEqual => 4,
+            BinaryOp::ShiftLeft | BinaryOp::ShiftRight => 5,
+            BinaryOp::Add | BinaryOp::Subtract => 6,
+            BinaryOp::Multiply => 7,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOp::Implies => "->",
+            BinaryOp::Or => "|",
+            BinaryOp::Xor => "^",
+            BinaryOp::And => "&",
+            BinaryOp::Equal => "==",
+            BinaryOp::NotEqual => "!=",
+            BinaryOp::Less => "<",
+            BinaryOp::LessEqual => "<=",
+            BinaryOp::Greater => ">",
+            BinaryOp::GreaterEqual => ">=",
+            BinaryOp::ShiftLeft => "<<",
+            BinaryOp::ShiftRight => ">>",
+            BinaryOp::Add => "+",
+            BinaryOp::Subtract => "-",
+            BinaryOp::Multiply => "*",
+        }
+    }
+}
+
+const UNARY_PRECEDENCE: u8 = 8;
+const ATOM_PRECEDENCE: u8 = 9;
+
+/// Boolean and arithmetic expression over named multi-bit variables.
+///
+/// Every subexpression has a bit width and is truncated to it, similar to
+/// Verilog: bitwise operators take the wider operand, `+` widens by one bit,
+/// `*` by the width of both operands, comparisons, `->` and `!` give one bit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Constant(u64),
+    Variable(String),
+    Slice { name: String, high: BitWidth, low: BitWidth },
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+pub fn width_mask(bit_width: BitWidth) -> u64 {
+    u64::MAX.checked_shl(bit_width as u32).map(|mask| !mask).unwrap_or(u64::MAX)
+}
+
+impl Expr {
+    pub fn variable(name: &str) -> Self {
+        Expr::Variable(name.to_string())
+    }
+
+    pub fn complement(expr: Expr) -> Self {
+        Expr::Unary(UnaryOp::Not, Box::new(expr))
+    }
+
+    pub fn binary(op: BinaryOp, lhs: Expr, rhs: Expr) -> Self {
+        Expr::Binary(op, Box::new(lhs), Box::new(rhs))
+    }
+
+    /// Evaluates the expression, `variables` gives the value and the bit width
+    /// of a variable. Returns the value together with its bit width.
+    pub fn evaluate(&self, variables: &dyn Fn(&str) -> (u64, BitWidth)) -> (u64, BitWidth) {
+        let (value, bit_width) = match self {
+            Expr::Constant(value) => {
+                (*value, (u64::BITS - value.leading_zeros()).max(1) as BitWidth)
+            }
+            Expr::Variable(name) => variables(name),
+            Expr::Slice { name, high, low } => {
+                (variables(name).0 >> low, high - low + 1)
+            }
+            Expr::Unary(op, expr) => {
+                let (value, bit_width) = expr.evaluate(variables);
+
+                match op {
+                    UnaryOp::Not => (!value, bit_width),
+                    UnaryOp::LogicalNot => ((value == 0) as u64, 1),
+                    UnaryOp::Negate => (value.wrapping_neg(), bit_width),
+                }
+            }
+            Expr::Binary(op, lhs, rhs) => {
+                let (lhs, lhs_width) = lhs.evaluate(variables);
+                let (rhs, rhs_width) = rhs.evaluate(variables);
+                let max_width = lhs_width.max(rhs_width);
+
+                match op {
+                    BinaryOp::Implies => ((lhs == 0 || rhs != 0) as u64, 1),
+                    BinaryOp::Or => (lhs | rhs, max_width),
+                    BinaryOp::Xor => (lhs ^ rhs, max_width),
+                    BinaryOp::And => (lhs & rhs, max_width),
+                    BinaryOp::Equal => ((lhs == rhs) as u64, 1),
+                    BinaryOp::NotEqual => ((lhs != rhs) as u64, 1),
+                    BinaryOp::Less => ((lhs < rhs) as u64, 1),
+                    BinaryOp::LessEqual => ((lhs <= rhs) as u64, 1),
+                    BinaryOp::Greater => ((lhs > rhs) as u64, 1),
+                    BinaryOp::GreaterEqual => ((lhs >= rhs) as u64, 1),
+                    BinaryOp::ShiftLeft => (lhs.checked_shl(rhs as u32).unwrap_or(0), lhs_width),
+                    BinaryOp::ShiftRight => (lhs.checked_shr(rhs as u32).unwrap_or(0), lhs_width),
+                    BinaryOp::Add => (lhs.wrapping_add(rhs), (max_width + 1).min(64)),
+                    BinaryOp::Subtract => (lhs.wrapping_sub(rhs), max_width),
+                    BinaryOp::Multiply => (lhs.wrapping_mul(rhs), (lhs_width + rhs_width).min(64)),
+                }
+            }
+        };
+
+        (value & width_mask(bit_width), bit_width)
+    }
+
+    /// Names of all variables in order of first use.
+    pub fn get_variables(&self) -> Vec<String> {
+        let mut variables = Vec::new();
+        self.collect_variables(&mut variables);
+
+        variables
+    }
+
+    fn collect_variables(&self, variables: &mut Vec<String>) {
+        match self {
+            Expr::Constant(_) => {}
+            Expr::Variable(name) | Expr::Slice { name, .. } => {
+                if !variables.contains(name) {
+                    variables.push(name.clone());
+                }
+            }
+            Expr::Unary(_, expr) => expr.collect_variables(variables),
+            Expr::Binary(_, lhs, rhs) => {
+                lhs.collect_variables(variables);
+                rhs.collect_variables(variables);
+            }
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Binary(op, _, _) => op.precedence(),
+            Expr::Unary(_, _) => UNARY_PRECEDENCE,
+            _ => ATOM_PRECEDENCE,
+        }
+    }
+
+    fn fmt_operand(&self, f: &mut Formatter<'_>, parenthesize: bool) -> std::fmt::Result {
+        if parenthesize {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Constant(value) => write!(f, "{}", value),
+            Expr::Variable(name) => write!(f, "{}", name),
+            Expr::Slice { name, high, low } if high == low => write!(f, "{}[{}]", name, high),
+            Expr::Slice { name, high, low } => write!(f, "{}[{}:{}]", name, high, low),
+            Expr::Unary(op, expr) => {
+                let symbol = match op {
+                    UnaryOp::Not => "~",
+                    UnaryOp::LogicalNot => "!",
+                    UnaryOp::Negate => "-",
+                };
+
+                write!(f, "{}", symbol)?;
+                expr.fmt_operand(f, expr.precedence() < UNARY_PRECEDENCE)
+            }
+            Expr::Binary(op, lhs, rhs) => {
+                let precedence = op.precedence();
+
+                lhs.fmt_operand(f, lhs.precedence() < precedence)?;
+                write!(f, " {} ", op.symbol())?;
+                rhs.fmt_operand(f, rhs.precedence() <= precedence)
+            }
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use crate::core::expression::{BinaryOp, Expr, UnaryOp};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Zero-based character offset in the parsed text.
+    pub position: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "column {}: {}", self.position + 1, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Identifier(String),
+    Number(u64),
+    Symbol(&'static str),
+    End,
+}
+
+const SYMBOLS: [&str; 23] = [
+    "->", "==", "!=", "<=", ">=", "<<", ">>",
+    "|", "^", "&", "<", ">", "+", "-", "*", "~", "!", "(", ")", "[", "]", ":", "'",
+];
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars: Peekable<CharIndices> = text.char_indices().peekable();
+
+    while let Some(&(position, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let mut identifier = String::new();
+            while let Some(&(_, c)) = chars.peek().filter(|(_, c)| c.is_ascii_alphanumeric() || *c == '_') {
+                identifier.push(c);
+                chars.next();
+            }
+
+            tokens.push((position, Token::Identifier(identifier)));
+        } else if c.is_ascii_digit() {
+            let mut literal = String::new();
+            while let Some(&(_, c)) = chars.peek().filter(|(_, c)| c.is_ascii_alphanumeric() || *c == '_') {
+                literal.push(c);
+                chars.next();
+            }
+
+            tokens.push((position, Token::Number(parse_number(&literal, position)?)));
+        } else {
+            let rest = &text[position..];
+            let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) else {
+                return Err(ParseError { position, message: format!("unexpected character '{}'", c) });
+            };
+
+            for _ in 0..symbol.len() {
+                chars.next();
+            }
+
+            tokens.push((position, Token::Symbol(symbol)));
+        }
+    }
+
+    tokens.push((text.len(), Token::End));
+
+    Ok(tokens)
+}
+
+fn parse_number(literal: &str, position: usize) -> Result<u64, ParseError> {
+    let literal = literal.replace('_', "");
+
+    let parsed = if let Some(hex) = literal.strip_prefix("0x") {
+        u64::from_str_radix(hex, 16)
+    } else if let Some(binary) = literal.strip_prefix("0b") {
+        u64::from_str_radix(binary, 2)
+    } else {
+        literal.parse()
+    };
+
+    parsed.map_err(|_| ParseError { position, message: format!("invalid number \"{}\"", literal) })
+}
+
+/// Binary operators from the loosest to the tightest binding.
+const BINARY_LEVELS: [&[(&str, BinaryOp)]; 8] = [
+    &[("->", BinaryOp::Implies)],
+    &[("|", BinaryOp::Or)],
+    &[("^", BinaryOp::Xor)],
+    &[("&", BinaryOp::And)],
+    &[
+        ("==", BinaryOp::Equal), ("!=", BinaryOp::NotEqual),
+        ("<", BinaryOp::Less), ("<=", BinaryOp::LessEqual),
+        (">", BinaryOp::Greater), (">=", BinaryOp::GreaterEqual),
+    ],
+    &[("<<", BinaryOp::ShiftLeft), (">>", BinaryOp::ShiftRight)],
+    &[("+", BinaryOp::Add), ("-", BinaryOp::Subtract)],
+    &[("*", BinaryOp::Multiply)],
+];
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    current: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.current].1
+    }
+
+    fn position(&self) -> usize {
+        self.tokens[self.current].0
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.current].1.clone();
+        if token != Token::End {
+            self.current += 1;
+        }
+
+        token
+    }
+
+    fn error<T>(&self, message: impl ToString) -> Result<T, ParseError> {
+        Err(ParseError { position: self.position(), message: message.to_string() })
+    }
+
+    fn eat(&mut self, symbol: &str) -> bool {
+        if matches!(self.peek(), Token::Symbol(s) if *s == symbol) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), ParseError> {
+        if self.eat(symbol) {
+            Ok(())
+        } else {
+            self.error(format!("expected '{}'", symbol))
+        }
+    }
+
+    fn parse_binary(&mut self, level: usize) -> Result<Expr, ParseError> {
+        if level == BINARY_LEVELS.len() {
+            return self.parse_unary();
+        }
+
+        let mut lhs = self.parse_binary(level + 1)?;
+
+        'operators: loop {
+            for (symbol, op) in BINARY_LEVELS[level].iter() {
+                if self.eat(symbol) {
+                    let rhs = self.parse_binary(level + 1)?;
+                    lhs = Expr::binary(*op, lhs, rhs);
+                    continue 'operators;
+                }
+            }
+
+            return Ok(lhs);
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ParseError> {
+        let op = if self.eat("~") {
+            UnaryOp::Not
+        } else if self.eat("!") {
+            UnaryOp::LogicalNot
+        } else if self.eat("-") {
+            UnaryOp::Negate
+        } else {
+            return self.parse_postfix();
+        };
+
+        Ok(Expr::Unary(op, Box::new(self.parse_unary()?)))
+    }
+
+    /// Parses an atom followed by any number of `'` complement suffixes.
+    fn parse_postfix(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.parse_atom()?;
+
+        while self.eat("'") {
+            expr = Expr::complement(expr);
+        }
+
+        Ok(expr)
+    }
+
+    fn parse_atom(&mut self) -> Result<Expr, ParseError> {
+        match self.next() {
+            Token::Number(value) => Ok(Expr::Constant(value)),
+            Token::Identifier(name) => {
+                if !self.eat("[") {
+                    return Ok(Expr::Variable(name));
+                }
+
+                let high = self.parse_index()?;
+                let low = if self.eat(":") { self.parse_index()? } else { high };
+                self.expect("]")?;
+
+                if low > high {
+                    return self.error(format!("bit range [{}:{}] of \"{}\" is reversed", high, low, name));
+                }
+
+                Ok(Expr::Slice { name, high, low })
+            }
+            Token::Symbol("(") => {
+                let expr = self.parse_binary(0)?;
+                self.expect(")")?;
+
+                Ok(expr)
+            }
+            Token::End => self.error("unexpected end of expression"),
+            token => {
+                self.current -= 1;
+                self.error(format!("unexpected {}", describe(&token)))
+            }
+        }
+    }
+
+    fn parse_index(&mut self) -> Result<u8, ParseError> {
+        match *self.peek() {
+            Token::Number(index) if index < 64 => {
+                self.next();
+                Ok(index as u8)
+            }
+            _ => self.error("expected a bit index below 64"),
+        }
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Identifier(name) => format!("\"{}\"", name),
+        Token::Number(value) => format!("number {}", value),
+        Token::Symbol(symbol) => format!("'{}'", symbol),
+        Token::End => "end of expression".to_string(),
+    }
+}
+
+/// Parses an expression such as `a & ~b | c[3:0] + 1`.
+///
+/// Implication `->` binds loosest, the other operators bind like in C: `|`,
+/// then `^`, `&`, comparisons, shifts, `+ -`, `*` and the prefix operators
+/// `~ ! -`. A postfix `'` complements its operand, so `a'b` style products
+/// can be written as `a' & b`.
+pub fn parse_expression(text: &str) -> Result<Expr, ParseError> {
+    let mut parser = Parser { tokens: tokenize(text)?, current: 0 };
+    let expr = parser.parse_binary(0)?;
+
+    match parser.peek() {
+        Token::End => Ok(expr),
+        token => parser.error(format!("unexpected {}", describe(token))),
+    }
+}
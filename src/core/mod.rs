@@ -1,4 +1,8 @@
+pub mod analysis;
 pub mod canvas;
 pub mod simulation;
 pub mod compiler;
-pub mod uuid;
\ No newline at end of file
+pub mod expression;
+pub mod uuid;
+pub mod registry;
+pub mod synthesis;
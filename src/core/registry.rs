@@ -0,0 +1,176 @@
+use std::sync::{OnceLock, RwLock};
+
+use crate::core::simulation::component::Component;
+use crate::core::simulation::components::behavioural::{Behavioural, BEHAVIOURAL_TYPE};
+use crate::core::simulation::components::clock_generator::ClockGenerator;
+use crate::core::simulation::components::extension::Extension;
+use crate::core::simulation::components::external::{External, EXTERNAL_TYPE};
+use crate::core::simulation::components::input::button::InputButton;
+use crate::core::simulation::components::logic::and_gate::AndGate;
+use crate::core::simulation::components::logic::not_gate::NotGate;
+use crate::core::simulation::components::logic::or_gate::OrGate;
+use crate::core::simulation::components::subcircuit::input_pin::InputPin;
+use crate::core::simulation::components::subcircuit::output_pin::OutputPin;
+use crate::core::simulation::components::tunnel::Tunnel;
+use crate::logisim::converter::component::require_param;
+use crate::logisim::converter::error::ConvertError;
+use crate::logisim::parser::component::LogisimComponent;
+
+pub type ExtensionLoader = fn(serde_json::Value) -> Result<Box<dyn Extension>, String>;
+
+/// Logisim component which is imported as this component.
+#[derive(Debug, Copy, Clone)]
+pub struct LogisimMapping {
+    pub library: u32,
+    pub name: &'static str,
+    /// Converts a component of the circuit named by the first argument.
+    pub convert: fn(&str, &LogisimComponent) -> Result<Component, ConvertError>,
+}
+
+/// Everything the editor, importers and loaders need to know about a component type.
+#[derive(Debug, Copy, Clone)]
+pub struct ComponentDescriptor {
+    pub component_type: &'static str,
+    pub name: &'static str,
+    /// Editor palette group, components without one are not shown in the palette.
+    pub category: Option<&'static str>,
+    pub create: fn() -> Component,
+    /// Restores an [`Extension`] from its saved state. Built-in components
+    /// are saved as their own variants and have no loader.
+    pub load: Option<ExtensionLoader>,
+    pub logisim: Option<LogisimMapping>,
+}
+
+static REGISTRY: OnceLock<RwLock<Vec<ComponentDescriptor>>> = OnceLock::new();
+
+fn registry() -> &'static RwLock<Vec<ComponentDescriptor>> {
+    REGISTRY.get_or_init(|| RwLock::new(builtin_descriptors()))
+}
+
+/// Registers a component type, replacing a previous registration with the same type.
+pub fn register(descriptor: ComponentDescriptor) {
+    let mut descriptors = registry().write().unwrap();
+
+    match descriptors.iter_mut().find(|d| d.component_type == descriptor.component_type) {
+        Some(registered) => *registered = descriptor,
+        None => descriptors.push(descriptor),
+    }
+}
+
+pub fn get_descriptors() -> Vec<ComponentDescriptor> {
+    registry().read().unwrap().clone()
+}
+
+pub fn find_descriptor(component_type: &str) -> Option<ComponentDescriptor> {
+    registry().read().unwrap().iter()
+        .find(|descriptor| descriptor.component_type == component_type)
+        .copied()
+}
+
+pub fn find_logisim_mapping(library: u32, name: &str) -> Option<LogisimMapping> {
+    registry().read().unwrap().iter()
+        .filter_map(|descriptor| descriptor.logisim)
+        .find(|mapping| mapping.library == library && mapping.name == name)
+}
+
+fn builtin_descriptors() -> Vec<ComponentDescriptor> {
+    vec![
+        ComponentDescriptor {
+            component_type: "tunnel",
+            name: "Tunnel",
+            category: Some("Wiring"),
+            create: || Tunnel::from_name_width("tunnel", 8),
+            load: None,
+            logisim: Some(LogisimMapping {
+                library: 0,
+                name: "Tunnel",
+                convert: |circuit, c| Ok(Tunnel::from_name_width(require_param(circuit, c, "label")?, 1)),
+            }),
+        },
+        ComponentDescriptor {
+            component_type: "clock_generator",
+            name: "Clock Generator",
+            category: Some("Wiring"),
+            create: ClockGenerator::create,
+            load: None,
+            logisim: Some(LogisimMapping { library: 0, name: "Clock", convert: |_, _| Ok(ClockGenerator::create()) }),
+        },
+        ComponentDescriptor {
+            component_type: "input_pin",
+            name: "Input Pin",
+            category: None,
+            create: || InputPin::create("input"),
+            load: None,
+            logisim: Some(LogisimMapping {
+                library: 0,
+                name: "Pin",
+                // Pins may be unlabelled, their subcircuit ports need labels
+                convert: |_, c| {
+                    let label = c.get_param("label").unwrap_or_default();
+
+                    if let Some("true") = c.get_param("output") {
+                        Ok(OutputPin::create(label))
+                    } else {
+                        Ok(InputPin::create(label))
+                    }
+                },
+            }),
+        },
+        ComponentDescriptor {
+            component_type: "output_pin",
+            name: "Output Pin",
+            category: None,
+            create: || OutputPin::create("output"),
+            load: None,
+            logisim: None,
+        },
+        ComponentDescriptor {
+            component_type: "not_gate",
+            name: "NOT Gate",
+            category: Some("Gates"),
+            create: || NotGate::from_bit_width(8),
+            load: None,
+            logisim: Some(LogisimMapping { library: 1, name: "NOT Gate", convert: |_, _| Ok(NotGate::from_bit_width(1)) }),
+        },
+        ComponentDescriptor {
+            component_type: "and_gate",
+            name: "AND Gate",
+            category: Some("Gates"),
+            create: || AndGate::from_bit_width(8),
+            load: None,
+            logisim: Some(LogisimMapping { library: 1, name: "AND Gate", convert: |_, _| Ok(AndGate::from_bit_width(1)) }),
+        },
+        ComponentDescriptor {
+            component_type: "or_gate",
+            name: "OR Gate",
+            category: Some("Gates"),
+            create: || OrGate::from_bit_width(8),
+            load: None,
+            logisim: Some(LogisimMapping { library: 1, name: "OR Gate", convert: |_, _| Ok(OrGate::from_bit_width(1)) }),
+        },
+        ComponentDescriptor {
+            component_type: BEHAVIOURAL_TYPE,
+            name: "Behavioural",
+            category: Some("Gates"),
+            create: Behavioural::create,
+            load: Some(Behavioural::load),
+            logisim: None,
+        },
+        ComponentDescriptor {
+            component_type: EXTERNAL_TYPE,
+            name: "External",
+            category: Some("Gates"),
+            create: External::create,
+            load: Some(External::load),
+            logisim: None,
+        },
+        ComponentDescriptor {
+            component_type: "button",
+            name: "Button",
+            category: Some("Input and Output"),
+            create: InputButton::create,
+            load: None,
+            logisim: Some(LogisimMapping { library: 5, name: "Button", convert: |_, _| Ok(InputButton::create()) }),
+        },
+    ]
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::compiler::project::InstantiatedCircuits;
+use crate::core::simulation::condition::Condition;
+use crate::core::simulation::probe::CanvasProbe;
+use crate::serde::workbench::{AssertionTrigger, SavedAssertion};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssertionFailure {
+    pub name: String,
+    pub condition: String,
+    pub cycle: usize,
+}
+
+impl AssertionFailure {
+    pub fn describe(&self) -> String {
+        format!("Assertion {} ({}) failed at cycle {}", self.name, self.condition, self.cycle)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Assertion {
+    pub saved: SavedAssertion,
+    condition: Condition,
+    clock: Option<Condition>,
+    last_clock: Option<u64>,
+}
+
+impl Assertion {
+    pub fn from_saved(saved: SavedAssertion, probes: &[CanvasProbe], circuits: &InstantiatedCircuits) -> Result<Assertion, String> {
+        let condition = Condition::parse(&saved.condition, probes, circuits)
+            .map_err(|error| format!("Assertion {}: {}", saved.name, error))?;
+
+        let clock = match &saved.trigger {
+            AssertionTrigger::EveryCycle => None,
+            AssertionTrigger::RisingEdge(clock) | AssertionTrigger::FallingEdge(clock) => Some(
+                Condition::parse(clock, probes, circuits)
+                    .map_err(|error| format!("Assertion {}: {}", saved.name, error))?
+            ),
+        };
+
+        Ok(Assertion { saved, condition, clock, last_clock: None })
+    }
+
+    /// Checks the assertion if its trigger fired since the last check.
+    /// Conditions reading undefined values are not judged.
+    pub fn check(&mut self, cycle: usize, circuits: &InstantiatedCircuits) -> Option<AssertionFailure> {
+        if let Some(clock) = &self.clock {
+            let value = clock.evaluate(circuits).map(|value| value & 1);
+            let last = std::mem::replace(&mut self.last_clock, value);
+
+            let edge = match self.saved.trigger {
+                AssertionTrigger::RisingEdge(_) => (Some(0), Some(1)),
+                _ => (Some(1), Some(0)),
+            };
+
+            if (last, value) != edge {
+                return None;
+            }
+        }
+
+        match self.condition.evaluate(circuits) {
+            Some(0) => Some(AssertionFailure {
+                name: self.saved.name.clone(),
+                condition: self.saved.condition.clone(),
+                cycle,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Forgets the clock value, so no edge is seen right after a reset.
+    pub fn reset(&mut self) {
+        self.last_clock = None;
+    }
+}
+
+/// Resolves saved assertions, returning the ones which could be resolved and
+/// errors for the others.
+pub fn from_saved_assertions(saved: Vec<SavedAssertion>, probes: &[CanvasProbe], circuits: &InstantiatedCircuits) -> (Vec<Assertion>, Vec<String>) {
+    let mut assertions = Vec::new();
+    let mut errors = Vec::new();
+
+    for saved in saved {
+        match Assertion::from_saved(saved, probes, circuits) {
+            Ok(assertion) => assertions.push(assertion),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    (assertions, errors)
+}
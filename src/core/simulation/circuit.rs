@@ -1,6 +1,11 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use serde::{Deserialize, Serialize};
 
 use crate::core::simulation::component::{Component, ComponentIdx, ComponentModel, Tick};
+use crate::core::simulation::components::subcircuit::Subcircuit;
+use crate::core::simulation::contention::{ConflictDetector, ContentionDriver, ContentionLog};
 use crate::core::simulation::pin::{Direction, PinIdx};
 use crate::core::simulation::value::operations::assign;
 use crate::core::simulation::wire::{Wire, WireIdx};
@@ -12,6 +17,8 @@ pub struct Circuit {
     pub clock_generators: Vec<ComponentIdx>,
     pub input_pins: Vec<(PinIdx, ComponentIdx)>,
     pub output_pins: Vec<(PinIdx, ComponentIdx)>,
+    #[serde(skip)]
+    pub contentions: RefCell<ContentionLog>,
 }
 
 pub type CircuitIdx = usize;
@@ -29,16 +36,46 @@ impl Circuit {
         self.wires.get(idx).unwrap()
     }
 
+    /// Clones the circuit together with its subcircuit instances, so that the
+    /// copy can be simulated without touching the original.
+    pub fn deep_clone(&self) -> Circuit {
+        let mut circuit = self.clone();
+
+        for component in circuit.components.iter_mut() {
+            if let ComponentModel::Subcircuit(Subcircuit::Instantiated(child, idx)) = &component.model {
+                component.model = ComponentModel::Subcircuit(
+                    Subcircuit::Instantiated(Rc::new(child.deep_clone()), *idx)
+                );
+            }
+        }
+
+        circuit
+    }
+
     pub fn tick(&self) {
         for clock_idx in self.clock_generators.iter() {
             let clock = self.get_component(*clock_idx);
 
-            if let ComponentModel::ClockGenerator(c) = &clock.model {
-                c.tick()
+            match &clock.model {
+                ComponentModel::ClockGenerator(c) => { c.tick() }
+                ComponentModel::Extension(c) => { c.0.tick() }
+                _ => {}
             }
         }
     }
 
+    pub fn reset(&self) {
+        for component in self.components.iter() {
+            component.reset();
+        }
+
+        for wire in self.wires.iter() {
+            wire.value.set(Default::default());
+        }
+
+        self.contentions.borrow_mut().clear();
+    }
+
     pub fn propagate_ticked(&self) {
         self.propagate(
             self.clock_generators.iter()
@@ -83,15 +120,20 @@ impl Circuit {
                         let wire = self.get_wire(wire_idx);
 
                         if pin.value.get() != wire.value.get() {
-                            dirty_wires.push(wire);
+                            dirty_wires.push(wire_idx);
                         }
                     }
                 }
             }
 
-            for wire in dirty_wires {
+            dirty_wires.sort_unstable();
+            dirty_wires.dedup();
+
+            for wire_idx in dirty_wires {
+                let wire = self.get_wire(wire_idx);
                 wire.value.set(Default::default());
 
+                let mut detector = ConflictDetector::default();
                 for (component_idx, pin_idx) in &wire.connected_components {
                     let component = self.get_component(*component_idx);
                     match component.get_pins().get(*pin_idx).unwrap().direction {
@@ -99,12 +141,17 @@ impl Circuit {
                             second.push(component);
                         }
                         Direction::Output => {
-                            let value = wire.value.get().apply_binary(self.get_component(*component_idx).get_pin_value(*pin_idx), assign);
-                            wire.value.set(value);
+                            let pin_value = component.get_pin_value(*pin_idx);
+                            wire.value.set(wire.value.get().apply_binary(pin_value, assign));
+                            detector.add(pin_value);
                         }
                         Direction::Inout => {}
                     }
                 }
+
+                if detector.get_conflicting_bits() != 0 {
+                    self.record_contention(wire_idx);
+                }
             }
 
             first.clear();
@@ -116,4 +163,19 @@ impl Circuit {
             iterations += 1;
         }
     }
-}
\ No newline at end of file
+
+    fn record_contention(&self, wire_idx: WireIdx) {
+        let drivers = self.get_wire(wire_idx).connected_components.iter()
+            .filter(|(component_idx, pin_idx)| {
+                self.get_component(*component_idx).get_pins()[*pin_idx].direction == Direction::Output
+            })
+            .map(|(component_idx, pin_idx)| ContentionDriver {
+                component: *component_idx,
+                pin: *pin_idx,
+                value: self.get_component(*component_idx).get_pin_value(*pin_idx),
+            })
+            .collect();
+
+        self.contentions.borrow_mut().record(wire_idx, drivers);
+    }
+}
@@ -1,10 +1,13 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::iter::zip;
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::core::canvas::facing::Facing;
 use crate::core::simulation::components::clock_generator::ClockGenerator;
+use crate::core::simulation::components::extension::ExtensionModel;
 use crate::core::simulation::components::input::button::InputButton;
 use crate::core::simulation::components::logic::and_gate::AndGate;
 use crate::core::simulation::components::logic::not_gate::NotGate;
@@ -14,7 +17,7 @@ use crate::core::simulation::components::subcircuit::output_pin::OutputPin;
 use crate::core::simulation::components::subcircuit::Subcircuit;
 use crate::core::simulation::components::tunnel::Tunnel;
 use crate::core::simulation::pin::{Pin, PinIdx};
-use crate::core::simulation::property::Property;
+use crate::core::simulation::property::{FacingProperty, Property, PropertyError, PropertySchema};
 use crate::core::simulation::value::Value;
 use crate::core::simulation::wire::WireIdx;
 
@@ -28,7 +31,12 @@ pub trait Tick {
     fn tick(&self);
 }
 
+pub trait Reset {
+    fn reset(&self);
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "SavedComponentFields")]
 pub struct Component {
     pub pins: ComponentPins,
     pub properties: ComponentProperties,
@@ -36,6 +44,31 @@ pub struct Component {
     pub uuid: Uuid,
 }
 
+#[derive(Deserialize)]
+struct SavedComponentFields {
+    pins: ComponentPins,
+    properties: ComponentProperties,
+    model: ComponentModel,
+    uuid: Uuid,
+}
+
+impl TryFrom<SavedComponentFields> for Component {
+    type Error = String;
+
+    fn try_from(mut saved: SavedComponentFields) -> Result<Self, Self::Error> {
+        if let ComponentModel::Extension(extension) = &mut saved.model {
+            extension.0.load_properties(&saved.properties, &mut saved.pins)?;
+        }
+
+        Ok(Component {
+            pins: saved.pins,
+            properties: saved.properties,
+            model: saved.model,
+            uuid: saved.uuid,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ComponentModel {
     ClockGenerator(ClockGenerator),
@@ -48,6 +81,44 @@ pub enum ComponentModel {
     InputPin(InputPin),
     OutputPin(OutputPin),
     Subcircuit(Subcircuit),
+
+    Extension(ExtensionModel),
+}
+
+impl ComponentModel {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            ComponentModel::ClockGenerator(_) => "Clock Generator",
+            ComponentModel::AndGate(_) => "AND Gate",
+            ComponentModel::OrGate(_) => "OR Gate",
+            ComponentModel::NotGate(_) => "NOT Gate",
+            ComponentModel::InputButton(_) => "Button",
+            ComponentModel::Tunnel(_) => "Tunnel",
+
+            ComponentModel::InputPin(_) => "Input Pin",
+            ComponentModel::OutputPin(_) => "Output Pin",
+            ComponentModel::Subcircuit(_) => "Subcircuit",
+
+            ComponentModel::Extension(c) => c.0.get_name(),
+        }
+    }
+
+    pub fn get_schema(&self) -> Vec<PropertySchema> {
+        match self {
+            ComponentModel::ClockGenerator(_) => ClockGenerator::schema(),
+            ComponentModel::AndGate(_) => AndGate::schema(),
+            ComponentModel::OrGate(_) => OrGate::schema(),
+            ComponentModel::NotGate(_) => NotGate::schema(),
+            ComponentModel::InputButton(_) => InputButton::schema(),
+            ComponentModel::Tunnel(_) => Tunnel::schema(),
+
+            ComponentModel::InputPin(_) => InputPin::schema(),
+            ComponentModel::OutputPin(_) => OutputPin::schema(),
+            ComponentModel::Subcircuit(_) => vec![],
+
+            ComponentModel::Extension(c) => c.0.get_schema(),
+        }
+    }
 }
 
 impl Component {
@@ -59,6 +130,15 @@ impl Component {
     }
 
     pub fn get_properties(&self) -> &ComponentProperties { &self.properties }
+    pub fn get_facing(&self) -> Facing {
+        self.properties.get("facing")
+            .and_then(|property| property.as_facing())
+            .map(|property| property.get())
+            .unwrap_or_default()
+    }
+    pub fn set_facing(&mut self, facing: Facing) {
+        self.properties.set("facing", Property::Facing(FacingProperty::new(facing)))
+    }
     pub fn get_property(&self, name: &str) -> &Property {
         self.properties.get(name).unwrap()
     }
@@ -75,6 +155,45 @@ impl Component {
             ComponentModel::InputPin(c) => { c.propagate(&self.pins, &self.properties) }
             ComponentModel::OutputPin(c) => { c.propagate(&self.pins, &self.properties) }
             ComponentModel::Subcircuit(c) => { c.propagate(&self.pins, &self.properties) }
+
+            ComponentModel::Extension(c) => { c.propagate(&self.pins, &self.properties) }
+        }
+    }
+
+    /// Copies pin values and the internal state of stateful models from
+    /// another compilation of the same component.
+    pub fn copy_state(&self, from: &Component) {
+        if self.get_pins().len() == from.get_pins().len() {
+            for (pin, from_pin) in zip(self.get_pins(), from.get_pins()) {
+                if pin.bit_width == from_pin.bit_width {
+                    pin.value.set(from_pin.value.get());
+                }
+            }
+        }
+
+        match (&self.model, &from.model) {
+            (ComponentModel::ClockGenerator(c), ComponentModel::ClockGenerator(f)) => { c.value.set(f.value.get()) }
+            (ComponentModel::InputButton(c), ComponentModel::InputButton(f)) => { c.state.set(f.state.get()) }
+            (ComponentModel::InputPin(c), ComponentModel::InputPin(f)) => { c.value.set(f.value.get()) }
+            (ComponentModel::OutputPin(c), ComponentModel::OutputPin(f)) => { c.value.set(f.value.get()) }
+            (ComponentModel::Extension(c), ComponentModel::Extension(f))
+                if c.0.get_type() == f.0.get_type() => { c.0.copy_state(&*f.0) }
+            _ => {}
+        }
+    }
+
+    pub fn reset(&self) {
+        for pin in self.get_pins() {
+            pin.value.set(Default::default());
+        }
+
+        match &self.model {
+            ComponentModel::ClockGenerator(c) => { c.reset() }
+            ComponentModel::InputButton(c) => { c.reset() }
+            ComponentModel::InputPin(c) => { c.reset() }
+            ComponentModel::OutputPin(c) => { c.reset() }
+            ComponentModel::Extension(c) => { c.0.reset() }
+            _ => {}
         }
     }
 }
@@ -87,17 +206,54 @@ impl ComponentProperties {
         self.0.get(name)
     }
 
+    /// Reads an integer property whether it is stored bounded or not.
+    pub fn get_integer(&self, name: &str) -> Option<u32> {
+        match self.0.get(name)? {
+            Property::Integer(p) => Some(p.get()),
+            Property::BoundedInteger(p) => Some(p.get()),
+            _ => None,
+        }
+    }
+
+    pub fn get_string(&self, name: &str) -> Option<String> {
+        self.0.get(name)?.as_string().map(|p| p.get())
+    }
+
+    pub fn set(&mut self, name: &str, property: Property) {
+        self.0.insert(name.to_string(), property);
+    }
+
     pub fn new(properties: Vec<(String, Property)>) -> Self {
         let properties_map: HashMap<String, Property> = properties.into_iter().collect();
 
         ComponentProperties(properties_map)
     }
 
-    // pub fn new(properties: HashMap<String, Property>) -> Self {
-    //     ComponentProperties {
-    //         0: properties,
-    //     }
-    // }
+    /// Brings properties in line with a component schema: missing and invalid
+    /// properties get their defaults, the rest are converted to the declared types.
+    /// Properties which are not in the schema are kept as is.
+    pub fn with_schema(mut self, schema: &[PropertySchema]) -> Self {
+        for entry in schema.iter() {
+            let property = match self.0.get(entry.name) {
+                Some(property) => entry.coerce(property).unwrap_or(entry.default.clone()),
+                None => entry.default.clone(),
+            };
+
+            self.0.insert(entry.name.to_string(), property);
+        }
+
+        self
+    }
+
+    pub fn validate(&self, schema: &[PropertySchema]) -> Vec<PropertyError> {
+        schema.iter()
+            .filter_map(|entry| match self.0.get(entry.name) {
+                Some(property) => entry.coerce(property).err(),
+                None if entry.required => Some(PropertyError::Missing { name: entry.name.to_string() }),
+                None => None,
+            })
+            .collect()
+    }
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -115,6 +271,13 @@ impl ComponentPins {
     pub fn get_value(&self, pin_number: PinIdx) -> Value {
         self.0.get(pin_number).unwrap().value.get()
     }
+    /// Turns pin offsets defined for an east-facing component to `facing`.
+    pub fn rotate(&mut self, facing: Facing) {
+        for pin in self.0.iter_mut() {
+            pin.location = facing.rotate(pin.location);
+        }
+    }
+
     pub fn new(pins: Vec<Pin>) -> Self {
         ComponentPins(pins)
     }
@@ -0,0 +1,430 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::expression::{BitWidth, Expr, width_mask};
+use crate::core::expression::parser::parse_expression;
+use crate::core::simulation::value::Value;
+
+/// Inputs with more unknown bits than this make every output unknown
+/// instead of simulating all completions.
+pub const MAX_UNKNOWN_BITS: u32 = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefinitionError {
+    /// One-based line of the definition.
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for DefinitionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for DefinitionError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Port {
+    pub name: String,
+    pub bit_width: BitWidth,
+}
+
+/// Bits of an input column, `care` is cleared for don't-care bits.
+#[derive(Debug, Clone)]
+struct Pattern {
+    bits: u32,
+    care: u32,
+}
+
+#[derive(Debug, Clone)]
+struct Row {
+    inputs: Vec<Pattern>,
+    outputs: Vec<Value>,
+}
+
+#[derive(Debug, Clone)]
+enum Body {
+    Expressions(Vec<Expr>),
+    TruthTable(Vec<Row>),
+}
+
+/// Behaviour of a black box component, written either as assignments
+///
+/// ```text
+/// input a, b, cin
+/// output sum, cout
+/// sum = a ^ b ^ cin
+/// cout = a & b | cin & (a ^ b)
+/// ```
+///
+/// or as a truth table with a header, where `-` or `x` in inputs matches any
+/// bit and in outputs leaves the bit undefined:
+///
+/// ```text
+/// a b cin | sum cout
+/// 0 0 0   | 0   0
+/// 1 1 -   | -   1
+/// ```
+///
+/// Ports take widths as `name[8]`. Without declarations, unknown names in
+/// expressions are one-bit inputs and outputs are as wide as their expression.
+/// Input combinations missing from a truth table give undefined outputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Definition {
+    source: String,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    body: Body,
+}
+
+impl Definition {
+    pub fn parse(source: &str) -> Result<Self, DefinitionError> {
+        let lines: Vec<(usize, &str)> = source.lines().enumerate()
+            .map(|(idx, line)| (idx + 1, strip_comment(line).trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .collect();
+
+        let Some(&(_, first)) = lines.first() else {
+            return Err(DefinitionError { line: 1, message: "definition is empty".to_string() });
+        };
+
+        let (inputs, outputs, body) = if first.contains('|') && !first.contains('=') && !is_declaration(first) {
+            parse_truth_table(&lines)?
+        } else {
+            parse_expressions(&lines)?
+        };
+
+        Ok(Definition { source: source.to_string(), inputs, outputs, body })
+    }
+
+    pub fn get_source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn get_inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    pub fn get_outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    /// Computes outputs for fully defined inputs.
+    pub fn evaluate(&self, inputs: &[u32]) -> Vec<Value> {
+        match &self.body {
+            Body::Expressions(expressions) => {
+                let variables = |name: &str| {
+                    let idx = self.inputs.iter().position(|port| port.name == name).unwrap();
+                    (inputs[idx] as u64, self.inputs[idx].bit_width)
+                };
+
+                expressions.iter().zip(self.outputs.iter())
+                    .map(|(expression, port)| {
+                        Value::create(expression.evaluate(&variables).0 as u32, port.bit_width)
+                    })
+                    .collect()
+            }
+            Body::TruthTable(rows) => {
+                let row = rows.iter().find(|row| {
+                    row.inputs.iter().zip(inputs.iter())
+                        .all(|(pattern, input)| input & pattern.care == pattern.bits)
+                });
+
+                match row {
+                    Some(row) => row.outputs.clone(),
+                    None => self.outputs.iter().map(|_| undefined()).collect(),
+                }
+            }
+        }
+    }
+
+    /// Computes outputs for inputs which may have unknown or error bits.
+    ///
+    /// Any error bit makes every output an error. Unknown bits are tried with
+    /// both values, output bits which come out the same every time stay defined.
+    pub fn propagate(&self, inputs: &[Value]) -> Vec<Value> {
+        let masks: Vec<u32> = self.inputs.iter().map(|port| width_mask(port.bit_width) as u32).collect();
+
+        if inputs.iter().zip(masks.iter()).any(|(value, mask)| value.get_error() & mask != 0) {
+            return self.outputs.iter().map(|port| error(port.bit_width)).collect();
+        }
+
+        let unknown: Vec<(usize, u32)> = inputs.iter().zip(masks.iter()).enumerate()
+            .flat_map(|(idx, (value, mask))| {
+                let undefined = value.get_undefined() & mask;
+                (0..32).filter(move |bit| undefined & (1 << bit) != 0).map(move |bit| (idx, bit))
+            })
+            .collect();
+
+        if unknown.len() as u32 > MAX_UNKNOWN_BITS {
+            return self.outputs.iter().map(|_| undefined()).collect();
+        }
+
+        let defined: Vec<u32> = inputs.iter().zip(masks.iter())
+            .map(|(value, mask)| value.get_defined_value() & mask)
+            .collect();
+
+        let mut merged: Option<Vec<Value>> = None;
+
+        for completion in 0..(1u32 << unknown.len()) {
+            let mut values = defined.clone();
+            for (n, (idx, bit)) in unknown.iter().enumerate() {
+                if completion & (1 << n) != 0 {
+                    values[*idx] |= 1 << bit;
+                }
+            }
+
+            let outputs = self.evaluate(&values);
+            merged = Some(match merged {
+                None => outputs,
+                Some(merged) => merged.into_iter().zip(outputs).zip(self.outputs.iter())
+                    .map(|((a, b), port)| merge(a, b, port.bit_width))
+                    .collect(),
+            });
+        }
+
+        merged.unwrap()
+    }
+}
+
+impl TryFrom<String> for Definition {
+    type Error = DefinitionError;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        Definition::parse(&source)
+    }
+}
+
+impl From<Definition> for String {
+    fn from(definition: Definition) -> Self {
+        definition.source
+    }
+}
+
+fn undefined() -> Value {
+    Value::new(0, u32::MAX)
+}
+
+fn error(bit_width: BitWidth) -> Value {
+    Value::new(width_mask(bit_width) as u32, u32::MAX)
+}
+
+/// Keeps bits on which `a` and `b` agree, the rest become unknown.
+fn merge(a: Value, b: Value, bit_width: BitWidth) -> Value {
+    let mask = width_mask(bit_width) as u32;
+    let agree = !a.get_raw_mask() & !b.get_raw_mask() & !(a.get_raw_value() ^ b.get_raw_value()) & mask;
+
+    Value::new(a.get_raw_value() & agree, !agree)
+}
+
+fn strip_comment(line: &str) -> &str {
+    let end = [line.find('#'), line.find("//")].into_iter().flatten().min().unwrap_or(line.len());
+    &line[..end]
+}
+
+fn is_declaration(line: &str) -> bool {
+    line.starts_with("input ") || line.starts_with("output ")
+}
+
+pub(crate) fn parse_port(text: &str, line: usize) -> Result<Port, DefinitionError> {
+    let error = |message: String| DefinitionError { line, message };
+    let text = text.trim();
+
+    let (name, bit_width) = match text.split_once('[') {
+        Some((name, width)) => {
+            let width = width.strip_suffix(']')
+                .and_then(|width| width.trim().parse::<BitWidth>().ok())
+                .filter(|width| (1..=32).contains(width))
+                .ok_or_else(|| error(format!("invalid bit width in \"{}\", expected 1 to 32", text)))?;
+
+            (name.trim(), width)
+        }
+        None => (text, 1),
+    };
+
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if !valid {
+        return Err(error(format!("invalid port name \"{}\"", name)));
+    }
+
+    Ok(Port { name: name.to_string(), bit_width })
+}
+
+fn add_port(ports: &mut Vec<Port>, others: &[Port], port: Port, line: usize) -> Result<(), DefinitionError> {
+    if ports.iter().chain(others.iter()).any(|p| p.name == port.name) {
+        return Err(DefinitionError { line, message: format!("port \"{}\" is declared twice", port.name) });
+    }
+
+    ports.push(port);
+    Ok(())
+}
+
+fn parse_expressions(lines: &[(usize, &str)]) -> Result<(Vec<Port>, Vec<Port>, Body), DefinitionError> {
+    let mut inputs: Vec<Port> = Vec::new();
+    let mut outputs: Vec<Port> = Vec::new();
+    let mut assignments: Vec<(usize, Port, Expr, bool)> = Vec::new();
+    let mut declared_inputs = false;
+
+    for &(line, text) in lines.iter() {
+        if let Some(ports) = text.strip_prefix("input ") {
+            declared_inputs = true;
+            for port in ports.split(',') {
+                add_port(&mut inputs, &outputs, parse_port(port, line)?, line)?;
+            }
+        } else if let Some(ports) = text.strip_prefix("output ") {
+            for port in ports.split(',') {
+                add_port(&mut outputs, &inputs, parse_port(port, line)?, line)?;
+            }
+        } else if let Some((target, expression)) = text.split_once('=') {
+            let port = parse_port(target, line)?;
+            let sized = target.contains('[');
+            let expression = parse_expression(expression)
+                .map_err(|error| DefinitionError { line, message: error.to_string() })?;
+
+            if assignments.iter().any(|(_, assigned, _, _)| assigned.name == port.name) {
+                return Err(DefinitionError { line, message: format!("output \"{}\" is assigned twice", port.name) });
+            }
+
+            assignments.push((line, port, expression, sized));
+        } else {
+            return Err(DefinitionError { line, message: format!("expected \"name = expression\", found \"{}\"", text) });
+        }
+    }
+
+    let output_names: Vec<String> = outputs.iter().chain(assignments.iter().map(|(_, port, _, _)| port))
+        .map(|port| port.name.clone())
+        .collect();
+
+    for (line, _, expression, _) in assignments.iter() {
+        for name in expression.get_variables() {
+            if output_names.contains(&name) {
+                return Err(DefinitionError { line: *line, message: format!("output \"{}\" cannot be read", name) });
+            }
+
+            if !inputs.iter().any(|port| port.name == name) {
+                if declared_inputs {
+                    return Err(DefinitionError { line: *line, message: format!("unknown input \"{}\"", name) });
+                }
+
+                inputs.push(Port { name, bit_width: 1 });
+            }
+        }
+    }
+
+    let mut expressions = Vec::new();
+    let mut ports = Vec::new();
+
+    for declared in outputs.iter() {
+        let Some(position) = assignments.iter().position(|(_, port, _, _)| port.name == declared.name) else {
+            let line = lines.last().unwrap().0;
+            return Err(DefinitionError { line, message: format!("output \"{}\" is never assigned", declared.name) });
+        };
+
+        let (_, _, expression, _) = assignments.remove(position);
+        expressions.push(expression);
+        ports.push(declared.clone());
+    }
+
+    for (_, port, expression, sized) in assignments.into_iter() {
+        let bit_width = if sized {
+            port.bit_width
+        } else {
+            let variables = |name: &str| {
+                (0, inputs.iter().find(|port| port.name == name).unwrap().bit_width)
+            };
+            expression.evaluate(&variables).1.min(32)
+        };
+
+        expressions.push(expression);
+        ports.push(Port { name: port.name, bit_width });
+    }
+
+    if ports.is_empty() {
+        return Err(DefinitionError { line: lines.last().unwrap().0, message: "definition has no outputs".to_string() });
+    }
+
+    Ok((inputs, ports, Body::Expressions(expressions)))
+}
+
+fn parse_truth_table(lines: &[(usize, &str)]) -> Result<(Vec<Port>, Vec<Port>, Body), DefinitionError> {
+    let (header_line, header) = lines[0];
+    let (input_header, output_header) = header.split_once('|').unwrap();
+
+    let mut inputs: Vec<Port> = Vec::new();
+    let mut outputs: Vec<Port> = Vec::new();
+    for port in input_header.split_whitespace() {
+        add_port(&mut inputs, &[], parse_port(port, header_line)?, header_line)?;
+    }
+    for port in output_header.split_whitespace() {
+        add_port(&mut outputs, &inputs, parse_port(port, header_line)?, header_line)?;
+    }
+
+    if outputs.is_empty() {
+        return Err(DefinitionError { line: header_line, message: "truth table has no outputs".to_string() });
+    }
+
+    let mut rows = Vec::new();
+    for &(line, text) in lines[1..].iter() {
+        let Some((input_bits, output_bits)) = text.split_once('|') else {
+            return Err(DefinitionError { line, message: "expected \"inputs | outputs\"".to_string() });
+        };
+
+        let input_bits = split_columns(input_bits, &inputs, line)?;
+        let output_bits = split_columns(output_bits, &outputs, line)?;
+
+        rows.push(Row {
+            inputs: input_bits.into_iter().map(|(bits, care)| Pattern { bits, care }).collect(),
+            outputs: output_bits.into_iter().zip(outputs.iter())
+                .map(|((bits, care), port)| {
+                    let mask = width_mask(port.bit_width) as u32;
+                    Value::new(bits, !(care & mask))
+                })
+                .collect(),
+        });
+    }
+
+    Ok((inputs, outputs, Body::TruthTable(rows)))
+}
+
+/// Splits row cells by column widths, returning `(bits, care)` per column.
+fn split_columns(text: &str, ports: &[Port], line: usize) -> Result<Vec<(u32, u32)>, DefinitionError> {
+    let cells: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let expected: usize = ports.iter().map(|port| port.bit_width as usize).sum();
+
+    if cells.len() != expected {
+        return Err(DefinitionError { line, message: format!("expected {} bits, found {}", expected, cells.len()) });
+    }
+
+    let mut columns = Vec::new();
+    let mut cells = cells.into_iter();
+
+    for port in ports.iter() {
+        let (mut bits, mut care) = (0, 0);
+
+        for cell in cells.by_ref().take(port.bit_width as usize) {
+            bits <<= 1;
+            care <<= 1;
+
+            match cell {
+                '0' => care |= 1,
+                '1' => {
+                    bits |= 1;
+                    care |= 1;
+                }
+                '-' | 'x' | 'X' | '?' => {}
+                c => return Err(DefinitionError { line, message: format!("unexpected '{}' in truth table", c) }),
+            }
+        }
+
+        columns.push((bits, care));
+    }
+
+    Ok(columns)
+}
@@ -0,0 +1,123 @@
+use std::any::Any;
+use std::cell::Cell;
+
+use crate::core::canvas::location::Location;
+use crate::core::simulation::component::{Behaviour, Component, ComponentModel, ComponentPins, ComponentProperties};
+use crate::core::simulation::components::behavioural::definition::{Definition, DefinitionError, Port};
+use crate::core::simulation::components::extension::{Extension, ExtensionModel};
+use crate::core::simulation::pin::{Direction, Pin};
+use crate::core::simulation::property::{Property, PropertySchema, StringProperty};
+use crate::core::simulation::value::Value;
+use crate::core::uuid::make_uuid;
+
+pub mod definition;
+
+pub const BEHAVIOURAL_TYPE: &str = "behavioural";
+
+pub const DEFAULT_DEFINITION: &str = "y = a & b";
+
+/// Distance between neighbouring ports on the same side.
+pub const PORT_SPACING: i16 = 20;
+pub const BODY_WIDTH: i16 = 60;
+
+/// Black box whose ports and outputs come from a [`Definition`].
+/// Inputs are the first pins, followed by outputs.
+///
+/// The `definition` property is the only saved copy of the definition, it is
+/// parsed again when the component is loaded.
+#[derive(Debug, Clone)]
+pub struct Behavioural {
+    pub definition: Definition,
+}
+
+impl Behaviour for Behavioural {
+    fn propagate(&self, pins: &ComponentPins, _properties: &ComponentProperties) {
+        let input_count = self.definition.get_inputs().len();
+        let inputs: Vec<Value> = (0..input_count).map(|idx| pins.get_value(idx)).collect();
+
+        for (idx, value) in self.definition.propagate(&inputs).into_iter().enumerate() {
+            pins.set_value(input_count + idx, value);
+        }
+    }
+}
+
+/// Pins of a box with inputs on the left edge and outputs on the right, each
+/// side from the top in the given order.
+pub fn port_box_pins(inputs: &[Port], outputs: &[Port]) -> ComponentPins {
+    let inputs = inputs.iter().enumerate()
+        .map(|(idx, port)| (port, Direction::Input, Location::new(-BODY_WIDTH, PORT_SPACING * idx as i16)));
+    let outputs = outputs.iter().enumerate()
+        .map(|(idx, port)| (port, Direction::Output, Location::new(0, PORT_SPACING * idx as i16)));
+
+    let pins = inputs.chain(outputs)
+        .map(|(port, direction, location)| Pin {
+            value: Cell::new(Default::default()),
+            bit_width: port.bit_width,
+            direction,
+            wire: Cell::new(None),
+            location,
+        })
+        .collect();
+
+    ComponentPins::new(pins)
+}
+
+impl Behavioural {
+    pub fn schema() -> Vec<PropertySchema> {
+        vec![
+            PropertySchema::new("definition", "Definition", Property::String(StringProperty::new(DEFAULT_DEFINITION.to_string()))),
+            PropertySchema::facing(),
+        ]
+    }
+
+    pub fn from_properties(properties: ComponentProperties) -> Result<Component, DefinitionError> {
+        let properties = properties.with_schema(&Self::schema());
+        let definition = Definition::parse(&properties.get_string("definition").unwrap())?;
+
+        Ok(Component {
+            pins: port_box_pins(definition.get_inputs(), definition.get_outputs()),
+            properties,
+            model: ComponentModel::Extension(ExtensionModel::new(Behavioural { definition })),
+            uuid: make_uuid(),
+        })
+    }
+
+    pub fn from_definition(definition: &str) -> Result<Component, DefinitionError> {
+        let properties = ComponentProperties::new(vec![
+            ("definition".to_string(), Property::String(StringProperty::new(definition.to_string())))
+        ]);
+
+        Self::from_properties(properties)
+    }
+
+    pub fn create() -> Component {
+        Self::from_definition(DEFAULT_DEFINITION).unwrap()
+    }
+
+    /// Placeholder until [`load_properties`](Extension::load_properties)
+    /// parses the saved definition.
+    pub fn load(_state: serde_json::Value) -> Result<Box<dyn Extension>, String> {
+        let definition = Definition::parse(DEFAULT_DEFINITION).map_err(|error| error.to_string())?;
+
+        Ok(Box::new(Behavioural { definition }))
+    }
+}
+
+impl Extension for Behavioural {
+    fn get_type(&self) -> &'static str { BEHAVIOURAL_TYPE }
+
+    fn get_name(&self) -> &'static str { "Behavioural" }
+
+    fn get_schema(&self) -> Vec<PropertySchema> { Behavioural::schema() }
+
+    fn load_properties(&mut self, properties: &ComponentProperties, pins: &mut ComponentPins) -> Result<(), String> {
+        let source = properties.get_string("definition").ok_or("Behavioural component has no definition")?;
+        self.definition = Definition::parse(&source).map_err(|error| error.to_string())?;
+        *pins = port_box_pins(self.definition.get_inputs(), self.definition.get_outputs());
+
+        Ok(())
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Extension> { Box::new(self.clone()) }
+    fn as_any(&self) -> &dyn Any { self }
+}
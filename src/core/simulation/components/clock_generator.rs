@@ -4,8 +4,9 @@ use std::fmt::{Debug, Formatter};
 use serde::{Deserialize, Serialize};
 
 use crate::core::canvas::location::Location;
-use crate::core::simulation::component::{Behaviour, Component, ComponentModel, ComponentPins, ComponentProperties, Tick};
+use crate::core::simulation::component::{Behaviour, Component, ComponentModel, ComponentPins, ComponentProperties, Reset, Tick};
 use crate::core::simulation::pin::{Direction, Pin};
+use crate::core::simulation::property::PropertySchema;
 use crate::core::simulation::value::Value;
 use crate::core::uuid::make_uuid;
 
@@ -27,6 +28,10 @@ impl Debug for ClockGenerator {
 }
 
 impl ClockGenerator {
+    pub fn schema() -> Vec<PropertySchema> {
+        vec![PropertySchema::facing()]
+    }
+
     pub fn create() -> Component {
         let pins = vec![
             Pin {
@@ -61,4 +66,10 @@ impl Tick for ClockGenerator {
 
         self.value.set(new_value);
     }
-}
\ No newline at end of file
+}
+
+impl Reset for ClockGenerator {
+    fn reset(&self) {
+        self.value.set(0);
+    }
+}
@@ -0,0 +1,102 @@
+use std::any::Any;
+use std::fmt::Debug;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::Error as DeError;
+use serde::ser::Error as SerError;
+
+use crate::core::registry::find_descriptor;
+use crate::core::simulation::component::{Behaviour, ComponentPins, ComponentProperties};
+use crate::core::simulation::property::PropertySchema;
+use crate::gui::component::{AsShapes, Bounds, Poke};
+
+/// Component defined outside the built-in [`ComponentModel`] variants.
+///
+/// An extension bundles simulation behaviour with its shapes, bounds and poke
+/// handling. Its state is saved with [`save`](Extension::save) and restored
+/// by the `load` function of the [`ComponentDescriptor`] registered under
+/// [`get_type`](Extension::get_type).
+///
+/// [`ComponentModel`]: crate::core::simulation::component::ComponentModel
+/// [`ComponentDescriptor`]: crate::core::registry::ComponentDescriptor
+pub trait Extension: Behaviour + AsShapes + Bounds + Poke + Debug {
+    /// Registry key, unique among all components.
+    fn get_type(&self) -> &'static str;
+
+    fn get_name(&self) -> &'static str;
+
+    fn get_schema(&self) -> Vec<PropertySchema> { vec![] }
+
+    /// Components which tick are advanced together with clock generators.
+    fn ticks(&self) -> bool { false }
+    fn tick(&self) {}
+    fn reset(&self) {}
+
+    /// Copies internal state from another instance of the same type.
+    fn copy_state(&self, _from: &dyn Extension) {}
+
+    fn save(&self) -> Result<serde_json::Value, String> { Ok(serde_json::Value::Null) }
+
+    /// Rebuilds state and pins derived from the component properties once a
+    /// saved component is loaded, so properties need not be saved twice and
+    /// the pins always match them.
+    fn load_properties(&mut self, _properties: &ComponentProperties, _pins: &mut ComponentPins) -> Result<(), String> { Ok(()) }
+
+    fn boxed_clone(&self) -> Box<dyn Extension>;
+    fn as_any(&self) -> &dyn Any;
+}
+
+#[derive(Debug)]
+pub struct ExtensionModel(pub Box<dyn Extension>);
+
+impl ExtensionModel {
+    pub fn new(extension: impl Extension + 'static) -> Self {
+        ExtensionModel(Box::new(extension))
+    }
+}
+
+impl Clone for ExtensionModel {
+    fn clone(&self) -> Self {
+        ExtensionModel(self.0.boxed_clone())
+    }
+}
+
+impl Behaviour for ExtensionModel {
+    fn propagate(&self, pins: &ComponentPins, properties: &ComponentProperties) {
+        self.0.propagate(pins, properties)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedExtension {
+    #[serde(rename = "type")]
+    component_type: String,
+    state: serde_json::Value,
+}
+
+impl Serialize for ExtensionModel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+    {
+        SavedExtension {
+            component_type: self.0.get_type().to_string(),
+            state: self.0.save().map_err(S::Error::custom)?,
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExtensionModel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+    {
+        let saved = SavedExtension::deserialize(deserializer)?;
+
+        let load = find_descriptor(saved.component_type.as_str())
+            .and_then(|descriptor| descriptor.load)
+            .ok_or_else(|| D::Error::custom(format!("Unknown component type \"{}\"", saved.component_type)))?;
+
+        load(saved.state).map(ExtensionModel).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,326 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, sync_channel, SyncSender};
+use std::thread;
+use std::time::Duration;
+
+use crate::core::expression::width_mask;
+use crate::core::simulation::component::{Behaviour, Component, ComponentModel, ComponentPins, ComponentProperties};
+use crate::core::simulation::components::behavioural::port_box_pins;
+use crate::core::simulation::components::behavioural::definition::{parse_port, DefinitionError, Port};
+use crate::core::simulation::components::extension::{Extension, ExtensionModel};
+use crate::core::simulation::property::{Property, PropertySchema, StringProperty};
+use crate::core::simulation::value::{parse_number, Value};
+use crate::core::uuid::make_uuid;
+
+pub const EXTERNAL_TYPE: &str = "external";
+
+/// How long a process may take to answer one line before it is considered
+/// hung and stopped.
+pub const RESPONSE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Lines read ahead from a process which answers faster than it is asked.
+const OUTPUT_BUFFER_LINES: usize = 16;
+
+/// Projects are shared between users, so commands of external components are
+/// only run once the user allowed it, e.g. with `--allow-external`.
+static COMMANDS_ALLOWED: AtomicBool = AtomicBool::new(false);
+
+/// Allows or forbids starting processes of external components in this
+/// process. Components which are refused fail like a missing program.
+pub fn allow_external_commands(allowed: bool) {
+    COMMANDS_ALLOWED.store(allowed, Ordering::Relaxed);
+}
+
+pub fn external_commands_allowed() -> bool {
+    COMMANDS_ALLOWED.load(Ordering::Relaxed)
+}
+
+/// Properties of an external component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalConfig {
+    /// Program and its arguments separated by whitespace, run without a shell.
+    pub command: String,
+    /// Comma separated ports like `a, b[8]`.
+    pub inputs: String,
+    pub outputs: String,
+}
+
+impl Default for ExternalConfig {
+    fn default() -> Self {
+        ExternalConfig {
+            command: String::new(),
+            inputs: "a".to_string(),
+            outputs: "y".to_string(),
+        }
+    }
+}
+
+/// Standard input and output are served by their own threads, so a process
+/// which stops reading or answering cannot block the simulation.
+struct ExternalProcess {
+    child: Child,
+    /// Lines for the standard input. Only one line may wait while another
+    /// is being written, more mean the process stopped reading.
+    input: SyncSender<String>,
+    lines: Receiver<String>,
+}
+
+impl Drop for ExternalProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Component whose outputs are computed by a child process.
+///
+/// Every propagation writes one line with the input values in decimal, `x`
+/// for values with undefined bits, and reads back one line with the output
+/// values as decimal, `0x` hexadecimal, `0b` binary or `x`. The process is
+/// started on the first propagation, if [allowed](allow_external_commands),
+/// and restarted on reset. Once it fails or does not answer within
+/// [`RESPONSE_TIMEOUT`], outputs are errors until the next reset.
+pub struct External {
+    pub config: ExternalConfig,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    process: RefCell<Option<ExternalProcess>>,
+    failed: Cell<bool>,
+}
+
+impl Debug for External {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("External")
+            .field("config", &self.config)
+            .field("failed", &self.failed.get())
+            .finish()
+    }
+}
+
+/// Copies get their own process, so every instance of a subcircuit runs a
+/// separate model.
+impl Clone for External {
+    fn clone(&self) -> Self {
+        External::from_config(self.config.clone()).unwrap()
+    }
+}
+
+impl Behaviour for External {
+    fn propagate(&self, pins: &ComponentPins, _properties: &ComponentProperties) {
+        let inputs: Vec<Value> = (0..self.inputs.len()).map(|idx| pins.get_value(idx)).collect();
+
+        let outputs = match self.exchange(&inputs) {
+            Some(outputs) => outputs,
+            None => {
+                self.failed.set(true);
+                *self.process.borrow_mut() = None;
+
+                self.outputs.iter()
+                    .map(|port| Value::new(width_mask(port.bit_width) as u32, u32::MAX))
+                    .collect()
+            }
+        };
+
+        for (idx, value) in outputs.into_iter().enumerate() {
+            pins.set_value(self.inputs.len() + idx, value);
+        }
+    }
+}
+
+impl External {
+    pub fn schema() -> Vec<PropertySchema> {
+        let config = ExternalConfig::default();
+
+        vec![
+            PropertySchema::new("command", "Command", Property::String(StringProperty::new(config.command))),
+            PropertySchema::new("inputs", "Inputs", Property::String(StringProperty::new(config.inputs))),
+            PropertySchema::new("outputs", "Outputs", Property::String(StringProperty::new(config.outputs))),
+            PropertySchema::facing(),
+        ]
+    }
+
+    pub fn from_config(config: ExternalConfig) -> Result<External, DefinitionError> {
+        Ok(External {
+            inputs: parse_ports(&config.inputs)?,
+            outputs: parse_ports(&config.outputs)?,
+            config,
+            process: RefCell::new(None),
+            failed: Cell::new(false),
+        })
+    }
+
+    pub fn from_properties(properties: ComponentProperties) -> Result<Component, DefinitionError> {
+        let properties = properties.with_schema(&Self::schema());
+        let external = External::from_config(ExternalConfig {
+            command: properties.get_string("command").unwrap(),
+            inputs: properties.get_string("inputs").unwrap(),
+            outputs: properties.get_string("outputs").unwrap(),
+        })?;
+
+        Ok(Component {
+            pins: port_box_pins(&external.inputs, &external.outputs),
+            properties,
+            model: ComponentModel::Extension(ExtensionModel::new(external)),
+            uuid: make_uuid(),
+        })
+    }
+
+    pub fn from_command(command: &str, inputs: &str, outputs: &str) -> Result<Component, DefinitionError> {
+        let properties = ComponentProperties::new(vec![
+            ("command".to_string(), Property::String(StringProperty::new(command.to_string()))),
+            ("inputs".to_string(), Property::String(StringProperty::new(inputs.to_string()))),
+            ("outputs".to_string(), Property::String(StringProperty::new(outputs.to_string()))),
+        ]);
+
+        Self::from_properties(properties)
+    }
+
+    pub fn create() -> Component {
+        let config = ExternalConfig::default();
+        Self::from_command(&config.command, &config.inputs, &config.outputs).unwrap()
+    }
+
+    /// Placeholder until [`load_properties`](Extension::load_properties)
+    /// reads the saved properties.
+    pub fn load(_state: serde_json::Value) -> Result<Box<dyn Extension>, String> {
+        let external = External::from_config(ExternalConfig::default()).map_err(|error| error.to_string())?;
+
+        Ok(Box::new(external))
+    }
+
+    pub fn get_inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    pub fn get_outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    /// Whether the process was not allowed, could not be started or stopped
+    /// answering.
+    pub fn has_failed(&self) -> bool {
+        self.failed.get()
+    }
+
+    fn exchange(&self, inputs: &[Value]) -> Option<Vec<Value>> {
+        if self.failed.get() {
+            return None;
+        }
+
+        let mut process = self.process.borrow_mut();
+        if process.is_none() {
+            *process = Some(self.spawn()?);
+        }
+
+        let ExternalProcess { input, lines, .. } = process.as_mut().unwrap();
+
+        let line: Vec<String> = inputs.iter().zip(self.inputs.iter())
+            .map(|(value, port)| {
+                let mask = width_mask(port.bit_width) as u32;
+
+                match value.get_raw_mask() & mask {
+                    0 => (value.get_raw_value() & mask).to_string(),
+                    _ => "x".to_string(),
+                }
+            })
+            .collect();
+
+        input.try_send(line.join(" ")).ok()?;
+
+        let response = lines.recv_timeout(RESPONSE_TIMEOUT).ok()?;
+
+        let values: Vec<&str> = response.split_whitespace().collect();
+        if values.len() != self.outputs.len() {
+            return None;
+        }
+
+        values.into_iter().zip(self.outputs.iter())
+            .map(|(value, port)| match value {
+                "x" | "X" => Some(Value::default()),
+                value => parse_number(value).map(|number| Value::create(number, port.bit_width)),
+            })
+            .collect()
+    }
+
+    fn spawn(&self) -> Option<ExternalProcess> {
+        if !external_commands_allowed() {
+            return None;
+        }
+
+        let mut arguments = self.config.command.split_whitespace();
+
+        let mut child = Command::new(arguments.next()?)
+            .args(arguments)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .ok()?;
+
+        let mut stdin = child.stdin.take()?;
+        let stdout = BufReader::new(child.stdout.take()?);
+
+        // Both threads end once the process is killed and its pipes closed
+        let (input, receiver) = sync_channel::<String>(1);
+        thread::spawn(move || {
+            for line in receiver {
+                if writeln!(stdin, "{}", line).and_then(|_| stdin.flush()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let (sender, lines) = sync_channel(OUTPUT_BUFFER_LINES);
+        thread::spawn(move || {
+            for line in stdout.lines() {
+                let Ok(line) = line else { break; };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Some(ExternalProcess { child, input, lines })
+    }
+}
+
+impl Extension for External {
+    fn get_type(&self) -> &'static str { EXTERNAL_TYPE }
+
+    fn get_name(&self) -> &'static str { "External" }
+
+    fn get_schema(&self) -> Vec<PropertySchema> { External::schema() }
+
+    fn reset(&self) {
+        *self.process.borrow_mut() = None;
+        self.failed.set(false);
+    }
+
+    fn load_properties(&mut self, properties: &ComponentProperties, pins: &mut ComponentPins) -> Result<(), String> {
+        let property = |name: &str| properties.get_string(name)
+            .ok_or(format!("External component has no {} property", name));
+
+        *self = External::from_config(ExternalConfig {
+            command: property("command")?,
+            inputs: property("inputs")?,
+            outputs: property("outputs")?,
+        }).map_err(|error| error.to_string())?;
+        *pins = port_box_pins(&self.inputs, &self.outputs);
+
+        Ok(())
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Extension> { Box::new(self.clone()) }
+    fn as_any(&self) -> &dyn Any { self }
+}
+
+fn parse_ports(text: &str) -> Result<Vec<Port>, DefinitionError> {
+    text.split(',')
+        .filter(|port| !port.trim().is_empty())
+        .map(|port| parse_port(port, 1))
+        .collect()
+}
@@ -4,8 +4,9 @@ use std::fmt::{Debug, Formatter};
 use serde::{Deserialize, Serialize};
 
 use crate::core::canvas::location::Location;
-use crate::core::simulation::component::{Behaviour, Component, ComponentModel, ComponentPins, ComponentProperties};
+use crate::core::simulation::component::{Behaviour, Component, ComponentModel, ComponentPins, ComponentProperties, Reset};
 use crate::core::simulation::pin::{Direction, Pin};
+use crate::core::simulation::property::PropertySchema;
 use crate::core::simulation::value::Value;
 use crate::core::uuid::make_uuid;
 
@@ -20,6 +21,12 @@ impl Behaviour for InputButton {
     }
 }
 
+impl Reset for InputButton {
+    fn reset(&self) {
+        self.state.set(0);
+    }
+}
+
 impl Debug for InputButton {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str(format!("InputButton: {}", self.state.get()).as_str())
@@ -27,6 +34,10 @@ impl Debug for InputButton {
 }
 
 impl InputButton {
+    pub fn schema() -> Vec<PropertySchema> {
+        vec![PropertySchema::facing()]
+    }
+
     pub fn create() -> Component {
         let pins = vec![
             Pin {
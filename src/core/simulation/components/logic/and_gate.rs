@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use crate::core::canvas::location::Location;
 use crate::core::simulation::component::{Behaviour, Component, ComponentModel, ComponentPins, ComponentProperties};
 use crate::core::simulation::pin::{Direction, Pin};
-use crate::core::simulation::property::{BoundedIntegerProperty, IntegerProperty, Property};
+use crate::core::simulation::property::{BoundedIntegerProperty, Property, PropertySchema};
 use crate::core::uuid::make_uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,14 +22,13 @@ impl Behaviour for AndGate {
 }
 
 impl AndGate {
+    pub fn schema() -> Vec<PropertySchema> {
+        vec![PropertySchema::bit_width(), PropertySchema::facing()]
+    }
+
     pub fn from_properties(properties: ComponentProperties) -> Component {
-        let bit_width =
-            properties
-                .get("bit_width")
-                .unwrap()
-                .as_bounded_integer()
-                .unwrap_or(&BoundedIntegerProperty::new(1, 10, 1))
-                .get() as u8;
+        let properties = properties.with_schema(&Self::schema());
+        let bit_width = properties.get_integer("bit_width").unwrap() as u8;
 
         let pins = vec![
             Pin {
@@ -65,7 +64,7 @@ impl AndGate {
 
     pub fn from_bit_width(bit_width: u8) -> Component {
         let properties = ComponentProperties::new(vec![
-            ("bit_width".to_string(), Property::Integer(IntegerProperty::new(bit_width as u32)))
+            ("bit_width".to_string(), Property::BoundedInteger(BoundedIntegerProperty::new(1, 33, bit_width as u32)))
         ]);
 
         Self::from_properties(properties)
@@ -3,3 +3,6 @@ pub mod logic;
 pub mod input;
 pub mod tunnel;
 pub mod subcircuit;
+pub mod extension;
+pub mod behavioural;
+pub mod external;
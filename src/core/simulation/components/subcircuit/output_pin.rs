@@ -3,9 +3,9 @@ use std::cell::Cell;
 use serde::{Deserialize, Serialize};
 
 use crate::core::canvas::location::Location;
-use crate::core::simulation::component::{Behaviour, Component, ComponentModel, ComponentPins, ComponentProperties};
+use crate::core::simulation::component::{Behaviour, Component, ComponentModel, ComponentPins, ComponentProperties, Reset};
 use crate::core::simulation::pin::{Direction, Pin};
-use crate::core::simulation::property::{Property, StringProperty};
+use crate::core::simulation::property::{Property, PropertySchema, StringProperty};
 use crate::core::simulation::value::Value;
 use crate::core::uuid::make_uuid;
 
@@ -20,7 +20,17 @@ impl Behaviour for OutputPin {
     }
 }
 
+impl Reset for OutputPin {
+    fn reset(&self) {
+        self.value.set(Default::default());
+    }
+}
+
 impl OutputPin {
+    pub fn schema() -> Vec<PropertySchema> {
+        vec![PropertySchema::label(), PropertySchema::facing()]
+    }
+
     pub fn create(label: &str) -> Component {
         let pins = vec![
             Pin {
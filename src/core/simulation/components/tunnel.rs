@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use crate::core::canvas::location::Location;
 use crate::core::simulation::component::{Component, ComponentModel, ComponentPins, ComponentProperties};
 use crate::core::simulation::pin::{Direction, Pin};
-use crate::core::simulation::property::{BoundedIntegerProperty, IntegerProperty, Property, StringProperty};
+use crate::core::simulation::property::{BoundedIntegerProperty, Property, PropertySchema, StringProperty};
 use crate::core::uuid::make_uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,22 +14,18 @@ pub struct Tunnel {
 }
 
 impl Tunnel {
+    pub fn schema() -> Vec<PropertySchema> {
+        vec![
+            PropertySchema::bit_width(),
+            PropertySchema::new("name", "Name", Property::String(StringProperty::new("Tunnel".to_string()))),
+            PropertySchema::facing(),
+        ]
+    }
+
     pub fn from_properties(properties: ComponentProperties) -> Component {
-        let bit_width =
-            properties
-                .get("bit_width")
-                .unwrap()
-                .as_bounded_integer()
-                .unwrap_or(&BoundedIntegerProperty::new(1, 10, 1))
-                .get() as u8;
-
-        let name =
-            properties
-                .get("name")
-                .unwrap()
-                .as_string()
-                .unwrap_or(&StringProperty::new("Tunnel".to_string()))
-                .get();
+        let properties = properties.with_schema(&Self::schema());
+        let bit_width = properties.get_integer("bit_width").unwrap() as u8;
+        let name = properties.get_string("name").unwrap();
 
         let pins = vec![
             Pin {
@@ -51,7 +47,7 @@ impl Tunnel {
 
     pub fn from_name_width(name: &str, bit_width: u8) -> Component {
         let properties = ComponentProperties::new(vec![
-            ("bit_width".to_string(), Property::Integer(IntegerProperty::new(bit_width as u32))),
+            ("bit_width".to_string(), Property::BoundedInteger(BoundedIntegerProperty::new(1, 33, bit_width as u32))),
             ("name".to_string(), Property::String(StringProperty::new(name.to_string()))),
         ]);
 
@@ -0,0 +1,61 @@
+use crate::core::compiler::project::InstantiatedCircuits;
+use crate::core::expression::{BitWidth, Expr};
+use crate::core::expression::parser::parse_expression;
+use crate::core::simulation::probe::{CanvasProbe, Probe};
+
+/// Expression over probe names, holding when it evaluates to non-zero.
+#[derive(Debug, Clone)]
+pub struct Condition {
+    pub source: String,
+    expression: Expr,
+    probes: Vec<(String, Probe, BitWidth)>,
+}
+
+impl Condition {
+    pub fn parse(source: &str, probes: &[CanvasProbe], circuits: &InstantiatedCircuits) -> Result<Condition, String> {
+        let expression = parse_expression(source)
+            .map_err(|error| format!("Condition {}: {}", source, error))?;
+
+        let probes = expression.get_variables().into_iter()
+            .map(|name| {
+                let CanvasProbe { probe, .. } = probes.iter()
+                    .find(|CanvasProbe { probe, .. }| probe.name == name)
+                    .ok_or(format!("Condition {}: there is no probe named {}", source, name))?;
+
+                let bit_width = probe.get_bit_width(circuits);
+                Ok((name, probe.clone(), bit_width))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Condition { source: source.to_string(), expression, probes })
+    }
+
+    /// Value of the expression, or `None` while a probe it reads has
+    /// undefined or error bits.
+    pub fn evaluate(&self, circuits: &InstantiatedCircuits) -> Option<u64> {
+        let mut values = Vec::new();
+
+        for (_, probe, bit_width) in self.probes.iter() {
+            let (circuit, _) = &circuits.instantiated_circuits[probe.circuit];
+            let value = circuit.wires[probe.wire].value.get();
+            let mask = value.get_raw_mask() & !u32::MAX.checked_shl(*bit_width as u32).unwrap_or(0);
+
+            if mask != 0 {
+                return None;
+            }
+
+            values.push(value.get_raw_value() as u64);
+        }
+
+        let variables = |name: &str| {
+            let idx = self.probes.iter().position(|(probe, _, _)| probe == name).unwrap();
+            (values[idx], self.probes[idx].2)
+        };
+
+        Some(self.expression.evaluate(&variables).0)
+    }
+
+    pub fn holds(&self, circuits: &InstantiatedCircuits) -> bool {
+        self.evaluate(circuits).is_some_and(|value| value != 0)
+    }
+}
@@ -0,0 +1,120 @@
+use uuid::Uuid;
+
+use crate::core::compiler::project::InstantiatedCircuits;
+use crate::core::simulation::circuit::CircuitIdx;
+use crate::core::simulation::component::ComponentIdx;
+use crate::core::simulation::pin::PinIdx;
+use crate::core::simulation::value::Value;
+use crate::core::simulation::wire::WireIdx;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentionDriver {
+    pub component: ComponentIdx,
+    pub pin: PinIdx,
+    pub value: Value,
+}
+
+/// Two or more outputs driving conflicting defined bits onto one wire.
+#[derive(Debug, Clone)]
+pub struct Contention {
+    pub wire: WireIdx,
+    pub drivers: Vec<ContentionDriver>,
+    pub occurrences: u64,
+}
+
+/// Accumulates driver values and tracks bits driven both to `0` and to `1`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConflictDetector {
+    ones: u32,
+    zeros: u32,
+    conflict: u32,
+}
+
+impl ConflictDetector {
+    pub fn add(&mut self, value: Value) {
+        let defined = !value.get_raw_mask();
+        let driver_ones = value.get_raw_value() & defined;
+        let driver_zeros = !value.get_raw_value() & defined;
+
+        self.conflict |= (self.ones & driver_zeros) | (self.zeros & driver_ones);
+        self.ones |= driver_ones;
+        self.zeros |= driver_zeros;
+    }
+
+    pub fn get_conflicting_bits(&self) -> u32 {
+        self.conflict
+    }
+}
+
+impl Contention {
+    /// Returns bits which are driven to `0` by one driver and to `1` by another.
+    pub fn conflicting_bits(&self) -> u32 {
+        let mut detector = ConflictDetector::default();
+        self.drivers.iter().for_each(|driver| detector.add(driver.value));
+
+        detector.get_conflicting_bits()
+    }
+
+    fn same_drivers(&self, drivers: &[ContentionDriver]) -> bool {
+        self.drivers.len() == drivers.len()
+            && self.drivers.iter().zip(drivers.iter())
+            .all(|(a, b)| a.component == b.component && a.pin == b.pin)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ContentionLog(Vec<Contention>);
+
+impl ContentionLog {
+    pub fn record(&mut self, wire: WireIdx, drivers: Vec<ContentionDriver>) {
+        match self.0.iter_mut().find(|c| c.wire == wire && c.same_drivers(&drivers)) {
+            Some(contention) => {
+                contention.drivers = drivers;
+                contention.occurrences += 1;
+            }
+            None => {
+                self.0.push(Contention { wire, drivers, occurrences: 1 });
+            }
+        }
+    }
+
+    pub fn get_contentions(&self) -> &[Contention] {
+        self.0.as_slice()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// Contention located in the simulation tree.
+#[derive(Debug, Clone)]
+pub struct ContentionReport {
+    pub circuit: CircuitIdx,
+    pub instance_path: Vec<Uuid>,
+    pub contention: Contention,
+}
+
+impl ContentionReport {
+    pub fn describe(&self, circuits: &InstantiatedCircuits) -> String {
+        let (circuit, _) = circuits.instantiated_circuits.get(self.circuit).unwrap();
+
+        let drivers: Vec<String> = self.contention.drivers.iter()
+            .map(|driver| {
+                let component = circuit.get_component(driver.component);
+                let bit_width = component.get_pins()[driver.pin].bit_width;
+
+                format!("{} {} pin {} = {}",
+                        component.model.get_name(),
+                        component.uuid,
+                        driver.pin,
+                        driver.value.to_bit_string(bit_width))
+            })
+            .collect();
+
+        format!("{}: wire {} driven by {}",
+                circuits.get_instance_name(self.circuit),
+                self.contention.wire,
+                drivers.join(", "))
+    }
+}
@@ -8,4 +8,8 @@ pub mod value;
 pub mod components;
 pub mod probe;
 pub mod workbench;
-pub mod trace;
\ No newline at end of file
+pub mod trace;
+pub mod contention;
+pub mod stimulus;
+pub mod condition;
+pub mod assertion;
\ No newline at end of file
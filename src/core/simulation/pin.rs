@@ -8,7 +8,7 @@ use crate::core::simulation::wire::WireIdx;
 
 pub type PinIdx = usize;
 
-#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize)]
 pub enum Direction {
     Input,
     Output,
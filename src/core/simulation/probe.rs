@@ -1,6 +1,8 @@
 use crate::core::canvas::location::Location;
+use crate::core::compiler::project::InstantiatedCircuits;
 use crate::core::simulation::circuit::CircuitIdx;
 use crate::core::simulation::wire::WireIdx;
+use crate::serde::vcd::VcdSignal;
 
 #[derive(Debug, Clone)]
 pub struct CanvasProbe {
@@ -14,3 +16,26 @@ pub struct Probe {
     pub circuit: CircuitIdx,
     pub wire: WireIdx,
 }
+
+impl Probe {
+    /// Width of the probed wire, taken from the pins it connects.
+    pub fn get_bit_width(&self, circuits: &InstantiatedCircuits) -> u8 {
+        let (circuit, _) = &circuits.instantiated_circuits[self.circuit];
+
+        circuit.wires[self.wire].connected_components.iter()
+            .map(|(component, pin)| circuit.components[*component].get_pins()[*pin].bit_width)
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Signal named `name` in the scope of the probed circuit instance, with
+    /// one nested scope per subcircuit component on the instance path.
+    pub fn to_vcd_signal(&self, name: &str, trace_idx: usize, circuits: &InstantiatedCircuits) -> VcdSignal {
+        VcdSignal {
+            name: name.to_string(),
+            scope: circuits.get_instance_labels(self.circuit),
+            bit_width: self.get_bit_width(circuits),
+            trace_idx,
+        }
+    }
+}
@@ -4,11 +4,20 @@ use std::fmt::{Debug, Display, Formatter};
 
 use serde::{Deserialize, Serialize};
 
+use crate::core::canvas::facing::Facing;
+use crate::core::simulation::value::Value;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Property {
     Integer(IntegerProperty),
     BoundedInteger(BoundedIntegerProperty),
     String(StringProperty),
+    Bool(BoolProperty),
+    Enum(EnumProperty),
+    Color(ColorProperty),
+    Value(ValueProperty),
+    Facing(FacingProperty),
+    Font(FontProperty),
 }
 
 impl Property {
@@ -23,6 +32,44 @@ impl Property {
     pub fn as_string(&self) -> Option<&StringProperty> {
         if let Property::String(p) = self { Some(p) } else { None }
     }
+
+    pub fn as_bool(&self) -> Option<&BoolProperty> {
+        if let Property::Bool(p) = self { Some(p) } else { None }
+    }
+
+    pub fn as_enum(&self) -> Option<&EnumProperty> {
+        if let Property::Enum(p) = self { Some(p) } else { None }
+    }
+
+    pub fn as_color(&self) -> Option<&ColorProperty> {
+        if let Property::Color(p) = self { Some(p) } else { None }
+    }
+
+    pub fn as_value(&self) -> Option<&ValueProperty> {
+        if let Property::Value(p) = self { Some(p) } else { None }
+    }
+
+    pub fn as_facing(&self) -> Option<&FacingProperty> {
+        if let Property::Facing(p) = self { Some(p) } else { None }
+    }
+
+    pub fn as_font(&self) -> Option<&FontProperty> {
+        if let Property::Font(p) = self { Some(p) } else { None }
+    }
+
+    pub fn get_type_name(&self) -> &'static str {
+        match self {
+            Property::Integer(_) => "integer",
+            Property::BoundedInteger(_) => "bounded integer",
+            Property::String(_) => "string",
+            Property::Bool(_) => "bool",
+            Property::Enum(_) => "enum",
+            Property::Color(_) => "color",
+            Property::Value(_) => "value",
+            Property::Facing(_) => "facing",
+            Property::Font(_) => "font",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +117,100 @@ impl<V> RefCellProperty<V> {
 
 pub type IntegerProperty = CellProperty<u32>;
 pub type StringProperty = RefCellProperty<String>;
+pub type BoolProperty = CellProperty<bool>;
+pub type ColorProperty = CellProperty<Color>;
+pub type ValueProperty = CellProperty<Value>;
+pub type FacingProperty = CellProperty<Facing>;
+pub type FontProperty = RefCellProperty<Font>;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color { r: 0, g: 0, b: 0, a: 255 };
+
+    pub fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Color { r, g, b, a }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Font {
+    pub family: String,
+    pub size: u8,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+impl Default for Font {
+    fn default() -> Self {
+        Font {
+            family: "SansSerif".to_string(),
+            size: 12,
+            bold: false,
+            italic: false,
+        }
+    }
+}
+
+/// One of a fixed list of named variants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnumProperty {
+    variants: Vec<String>,
+    value: RefCell<String>,
+}
+
+#[derive(Debug)]
+pub struct VariantError {
+    variants: Vec<String>,
+    value: String,
+}
+
+impl Display for VariantError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            format!("Value \"{}\" is not one of [{}]",
+                    self.value, self.variants.join(", "))
+                .as_str()
+        )
+    }
+}
+
+impl Error for VariantError {}
+
+impl EnumProperty {
+    pub fn get(&self) -> String {
+        self.value.borrow().clone()
+    }
+
+    pub fn get_variants(&self) -> &[String] {
+        self.variants.as_slice()
+    }
+
+    pub fn set(&self, value: &str) -> Result<(), VariantError> {
+        if self.variants.iter().any(|variant| variant == value) {
+            self.value.replace(value.to_string());
+            Ok(())
+        } else {
+            Err(VariantError {
+                variants: self.variants.clone(),
+                value: value.to_string(),
+            })
+        }
+    }
+
+    pub fn new(variants: &[&str], value: &str) -> Self {
+        EnumProperty {
+            variants: variants.iter().map(|variant| variant.to_string()).collect(),
+            value: RefCell::new(value.to_string()),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BoundedIntegerProperty {
@@ -115,6 +256,10 @@ impl BoundedIntegerProperty {
         }
     }
 
+    pub fn get_bounds(&self) -> (u32, u32) {
+        (self.min, self.max)
+    }
+
     pub fn new(min: u32, max: u32, value: u32) -> Self {
         BoundedIntegerProperty {
             min,
@@ -123,3 +268,135 @@ impl BoundedIntegerProperty {
         }
     }
 }
+
+/// Declares a component property: its key, display label and default value.
+///
+/// Constraints live in the default itself: bounds of a
+/// [`BoundedIntegerProperty`] and variants of an [`EnumProperty`] apply to
+/// every value of the property. Properties which are not `required` may be
+/// absent in saved components and silently take their default.
+#[derive(Debug, Clone)]
+pub struct PropertySchema {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub default: Property,
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyError {
+    Missing { name: String },
+    WrongType { name: String, expected: &'static str, found: &'static str },
+    OutOfBounds { name: String, min: u32, max: u32, value: u32 },
+    UnknownVariant { name: String, value: String },
+}
+
+impl Display for PropertyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropertyError::Missing { name } => {
+                write!(f, "property \"{}\" is missing", name)
+            }
+            PropertyError::WrongType { name, expected, found } => {
+                write!(f, "property \"{}\" should be {}, found {}", name, expected, found)
+            }
+            PropertyError::OutOfBounds { name, min, max, value } => {
+                write!(f, "property \"{}\" value {} is out of bounds: [{}; {})", name, value, min, max)
+            }
+            PropertyError::UnknownVariant { name, value } => {
+                write!(f, "property \"{}\" has unknown variant \"{}\"", name, value)
+            }
+        }
+    }
+}
+
+impl Error for PropertyError {}
+
+impl PropertySchema {
+    pub fn new(name: &'static str, label: &'static str, default: Property) -> Self {
+        PropertySchema { name, label, default, required: true }
+    }
+
+    pub fn optional(self) -> Self {
+        PropertySchema { required: false, ..self }
+    }
+
+    pub fn bit_width() -> Self {
+        Self::new("bit_width", "Bit Width", Property::BoundedInteger(BoundedIntegerProperty::new(1, 33, 1)))
+    }
+
+    pub fn facing() -> Self {
+        Self::new("facing", "Facing", Property::Facing(FacingProperty::new(Facing::East))).optional()
+    }
+
+    pub fn label() -> Self {
+        Self::new("label", "Label", Property::String(StringProperty::new(String::new())))
+    }
+
+    /// Converts a stored property to the declared type, so that an `Integer`
+    /// saved by older versions becomes a `BoundedInteger`.
+    pub fn coerce(&self, property: &Property) -> Result<Property, PropertyError> {
+        match (&self.default, property) {
+            (Property::BoundedInteger(default), Property::Integer(value)) => {
+                let (min, max) = default.get_bounds();
+                let coerced = Property::BoundedInteger(BoundedIntegerProperty::new(min, max, value.get()));
+
+                self.validate(&coerced)?;
+                Ok(coerced)
+            }
+            (Property::BoundedInteger(default), Property::BoundedInteger(value)) => {
+                let (min, max) = default.get_bounds();
+                let coerced = Property::BoundedInteger(BoundedIntegerProperty::new(min, max, value.get()));
+
+                self.validate(&coerced)?;
+                Ok(coerced)
+            }
+            (Property::Enum(default), Property::Enum(value)) => {
+                let variants: Vec<&str> = default.get_variants().iter().map(|v| v.as_str()).collect();
+                let coerced = Property::Enum(EnumProperty::new(variants.as_slice(), value.get().as_str()));
+
+                self.validate(&coerced)?;
+                Ok(coerced)
+            }
+            _ => {
+                self.validate(property)?;
+                Ok(property.clone())
+            }
+        }
+    }
+
+    pub fn validate(&self, property: &Property) -> Result<(), PropertyError> {
+        if std::mem::discriminant(&self.default) != std::mem::discriminant(property) {
+            return Err(PropertyError::WrongType {
+                name: self.name.to_string(),
+                expected: self.default.get_type_name(),
+                found: property.get_type_name(),
+            });
+        }
+
+        match (&self.default, property) {
+            (Property::BoundedInteger(default), Property::BoundedInteger(value)) => {
+                let (min, max) = default.get_bounds();
+
+                if value.get() < min || value.get() >= max {
+                    return Err(PropertyError::OutOfBounds {
+                        name: self.name.to_string(),
+                        min,
+                        max,
+                        value: value.get(),
+                    });
+                }
+            }
+            (Property::Enum(default), Property::Enum(value))
+            if !default.get_variants().contains(&value.get()) => {
+                return Err(PropertyError::UnknownVariant {
+                    name: self.name.to_string(),
+                    value: value.get(),
+                });
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
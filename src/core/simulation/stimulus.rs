@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+
+use uuid::Uuid;
+
+use crate::core::compiler::project::InstantiatedCircuits;
+use crate::core::simulation::circuit::CircuitIdx;
+use crate::core::simulation::component::{Component, ComponentIdx, ComponentModel};
+use crate::core::simulation::value::Value;
+use crate::serde::stimulus::StimulusFile;
+
+/// Stimulus with every input resolved to the components it drives.
+#[derive(Debug, Clone, Default)]
+pub struct Stimulus {
+    cycles: BTreeMap<usize, Vec<(CircuitIdx, ComponentIdx, u32)>>,
+}
+
+fn is_input(component: &Component) -> bool {
+    matches!(component.model, ComponentModel::InputPin(_) | ComponentModel::InputButton(_))
+}
+
+impl Stimulus {
+    /// Labels are looked up among the input pins of the top circuit, UUIDs
+    /// among input pins of the top circuit and buttons of every instantiated
+    /// circuit, so a button UUID in a subcircuit drives all its instances.
+    pub fn from_file(file: StimulusFile, top_circuit: CircuitIdx, circuits: &InstantiatedCircuits) -> Result<Stimulus, String> {
+        let mut targets: BTreeMap<String, Vec<(CircuitIdx, ComponentIdx)>> = BTreeMap::new();
+        let mut cycles = BTreeMap::new();
+
+        for (cycle, values) in file.cycles {
+            let mut pokes = Vec::new();
+
+            for (name, value) in values {
+                if !targets.contains_key(&name) {
+                    targets.insert(name.clone(), find_inputs(&name, top_circuit, circuits)
+                        .map_err(|error| format!("Stimulus: {}", error))?);
+                }
+
+                pokes.extend(targets[&name].iter().map(|&(circuit, component)| (circuit, component, value)));
+            }
+
+            cycles.insert(cycle, pokes);
+        }
+
+        Ok(Stimulus { cycles })
+    }
+
+    /// Sets inputs scheduled for `cycle`.
+    pub fn apply(&self, cycle: usize, circuits: &InstantiatedCircuits) {
+        let Some(pokes) = self.cycles.get(&cycle) else { return; };
+
+        for &(circuit_idx, component_idx, value) in pokes.iter() {
+            poke_input(circuit_idx, component_idx, value, circuits);
+        }
+    }
+}
+
+/// Sets an input pin or a button found by [`find_inputs`] and propagates
+/// the change up to the top circuit.
+pub fn poke_input(circuit_idx: CircuitIdx, component_idx: ComponentIdx, value: u32, circuits: &InstantiatedCircuits) {
+    let circuit = &circuits.instantiated_circuits[circuit_idx].0;
+    let component = &circuit.components[component_idx];
+
+    match &component.model {
+        ComponentModel::InputPin(pin) => {
+            pin.value.set(Value::create(value, component.get_pins()[0].bit_width))
+        }
+        ComponentModel::InputButton(button) => button.state.set(value & 1),
+        _ => unreachable!(),
+    }
+
+    circuit.propagate(vec![component]);
+
+    let mut current = circuit_idx;
+    while let Some((parent_idx, subcircuit)) = circuits.parents[current] {
+        let parent = &circuits.instantiated_circuits[parent_idx].0;
+        parent.propagate(vec![&parent.components[subcircuit]]);
+        current = parent_idx;
+    }
+}
+
+/// Input pins of the top circuit labelled `name` or with UUID `name`, or
+/// buttons with UUID `name` in every instantiated circuit.
+///
+/// Input pins of subcircuits are rejected, their parent overwrites them with
+/// the value at the subcircuit port on the next propagation.
+pub fn find_inputs(name: &str, top_circuit: CircuitIdx, circuits: &InstantiatedCircuits) -> Result<Vec<(CircuitIdx, ComponentIdx)>, String> {
+    if let Ok(uuid) = Uuid::parse_str(name) {
+        let found: Vec<(CircuitIdx, ComponentIdx)> = circuits.instantiated_circuits.iter().enumerate()
+            .filter_map(|(circuit_idx, (_, circuit_type))| {
+                circuits.by_uuid[*circuit_type].get(&uuid).map(|component| (circuit_idx, *component))
+            })
+            .collect();
+
+        if let Some(&(circuit, component)) = found.first() {
+            match &circuits.instantiated_circuits[circuit].0.components[component].model {
+                ComponentModel::InputButton(_) => {}
+                ComponentModel::InputPin(_) if found.iter().all(|&(circuit, _)| circuit == top_circuit) => {}
+                ComponentModel::InputPin(_) => {
+                    return Err(format!("Input pin {} is inside a subcircuit, set the input of the top circuit driving it instead", name));
+                }
+                _ => return Err(format!("Component {} is not an input pin or a button", name)),
+            }
+
+            return Ok(found);
+        }
+    }
+
+    let top = &circuits.instantiated_circuits[top_circuit].0;
+    let found: Vec<(CircuitIdx, ComponentIdx)> = top.components.iter().enumerate()
+        .filter(|(_, component)| is_input(component))
+        .filter(|(_, component)| component.get_properties().get_string("label").as_deref() == Some(name))
+        .map(|(component_idx, _)| (top_circuit, component_idx))
+        .collect();
+
+    if found.is_empty() {
+        return Err(format!("Top circuit has no input labelled {} and no component has this UUID", name));
+    }
+
+    Ok(found)
+}
@@ -43,8 +43,8 @@ impl Value {
     /// Creates new [`Value`](Value) with given bit width
     pub fn create(value: u32, bits: u8) -> Self {
         Self::new(
-            value & !(u32::MAX << bits),
-            u32::MAX << bits,
+            value & !u32::MAX.checked_shl(bits as u32).unwrap_or(0),
+            u32::MAX.checked_shl(bits as u32).unwrap_or(0),
         )
     }
 }
@@ -86,6 +86,18 @@ impl Value {
     pub fn is_error(&self) -> bool {
         self.get_error().count_ones() != 0
     }
+
+    /// Formats lowest `bits` bits, most significant first, as `0`, `1`, `x` or `E`.
+    pub fn to_bit_string(&self, bits: u8) -> String {
+        (0..bits.min(Self::BITS)).rev()
+            .map(|i| match self.get_bit_state(i) {
+                BitState::F => '0',
+                BitState::T => '1',
+                BitState::X => 'x',
+                BitState::E => 'E',
+            })
+            .collect()
+    }
 }
 
 impl Value {
@@ -250,3 +262,14 @@ impl Not for Value {
 //     value: u64,
 //     foo: CompositeValue
 // }
+
+/// Decimal, or hexadecimal and binary with `0x` and `0b` prefixes.
+pub fn parse_number(text: &str) -> Option<u32> {
+    let lowercase = text.to_ascii_lowercase();
+
+    match lowercase.get(..2) {
+        Some("0x") => u32::from_str_radix(&lowercase[2..], 16).ok(),
+        Some("0b") => u32::from_str_radix(&lowercase[2..], 2).ok(),
+        _ => lowercase.parse().ok(),
+    }
+}
@@ -1,5 +1,3 @@
-use uuid::Uuid;
-
 use crate::core::compiler::project::InstantiatedCircuits;
 use crate::core::simulation::component::ComponentModel;
 use crate::core::simulation::components::subcircuit::Subcircuit;
@@ -8,7 +6,7 @@ use crate::core::simulation::probe::{CanvasProbe, Probe};
 use crate::core::simulation::wire::WireIdx;
 use crate::player::osc;
 use crate::player::probe_location::fix_loaded_probe;
-use crate::serde::workbench::{OscilloscopeConfig, OscilloscopeRow, ProbePin, SavedProbe, WorkbenchFile};
+use crate::serde::workbench::{OscilloscopeConfig, OscilloscopeRow, ProbePin, SavedAssertion, SavedProbe, WorkbenchFile};
 
 impl CanvasProbe {
     fn from_saved(saved: SavedProbe,
@@ -75,20 +73,7 @@ impl CanvasProbe {
 
     fn to_saved(&self, circuits: &InstantiatedCircuits)
                 -> SavedProbe {
-        let mut subcircuit_path: Vec<Uuid> = Vec::new();
-        {
-            let mut cur_circ = self.probe.circuit;
-            loop {
-                match circuits.parents[cur_circ] {
-                    None => break,
-                    Some((parent, comp)) => {
-                        subcircuit_path.push(circuits.instantiated_circuits[parent].0.components[comp].uuid);
-                        cur_circ = parent;
-                    }
-                }
-            }
-        }
-        subcircuit_path.reverse();
+        let subcircuit_path = circuits.get_instance_path(self.probe.circuit);
         let circult = &circuits.instantiated_circuits[self.probe.circuit].0;
         let pins: Vec<ProbePin> = circult.wires[self.probe.wire].connected_components
             .iter()
@@ -119,7 +104,8 @@ pub fn from_workbench_file(workbench_file: WorkbenchFile,
                            circuits: &InstantiatedCircuits)
                            -> (Vec<Result<CanvasProbe, String>>,
                                osc::Oscilloscope,
-                               usize) {
+                               usize,
+                               Vec<SavedAssertion>) {
     let canvas_probes: Vec<Result<CanvasProbe, String>> = workbench_file.probes
         .into_iter()
         .map(|x| CanvasProbe::from_saved(x, circuits))
@@ -143,13 +129,14 @@ pub fn from_workbench_file(workbench_file: WorkbenchFile,
         })
     }
 
-    (canvas_probes, osciloscope, workbench_file.last_probe_id)
+    (canvas_probes, osciloscope, workbench_file.last_probe_id, workbench_file.assertions)
 }
 
 pub fn to_workbench_file(canvas_probes: &[CanvasProbe],
                          oscilloscope: &osc::Oscilloscope,
                          circuits: &InstantiatedCircuits,
-                         last_probe_id: usize)
+                         last_probe_id: usize,
+                         assertions: &[SavedAssertion])
                          -> WorkbenchFile {
     let saved_probes: Vec<SavedProbe> = canvas_probes
         .iter()
@@ -173,5 +160,6 @@ pub fn to_workbench_file(canvas_probes: &[CanvasProbe],
         probes: saved_probes,
         oscilloscope_config,
         last_probe_id,
+        assertions: assertions.to_vec(),
     }
 }
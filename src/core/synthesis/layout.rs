@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::core::canvas::location::Location;
+use crate::core::simulation::component::Component;
+use crate::core::simulation::pin::PinIdx;
+use crate::serde::project::{SavedComponent, SavedWire};
+
+pub type NetId = usize;
+
+/// Spacing between rails and the free space kept between cell bands.
+const TRACK: i64 = 10;
+const BAND_GAP: i64 = 20;
+
+/// Coordinates are computed wider than canvas locations, this is a point in
+/// those coordinates.
+type Point = (i64, i64);
+
+/// The placed cells or wires reach outside the canvas coordinate range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutError;
+
+impl Display for LayoutError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Layout does not fit on the canvas, the netlist has too many cells")
+    }
+}
+
+impl Error for LayoutError {}
+
+fn to_location((x, y): Point) -> Result<Location, LayoutError> {
+    match (i16::try_from(x), i16::try_from(y)) {
+        (Ok(x), Ok(y)) => Ok(Location::new(x, y)),
+        _ => Err(LayoutError),
+    }
+}
+
+/// Component to be placed, with its pins assigned to nets. Input pins must
+/// be to the left of output pins.
+#[derive(Debug, Clone)]
+pub struct Cell {
+    pub component: Component,
+    pub inputs: Vec<(PinIdx, NetId)>,
+    pub outputs: Vec<(PinIdx, NetId)>,
+}
+
+/// Places cells in columns by logic depth and routes every net.
+///
+/// Every cell gets a horizontal band of its own, so no two cells have pins on
+/// the same row. A net runs as a trunk to the right from its driver along the
+/// driver pin row, and in the channel in front of each column which reads it
+/// it drops a vertical rail to stubs leading into the input pins. Nets read by
+/// a column which is not to the right of the driver, as in feedback loops,
+/// go down after the driver column and back left along a row below all cells.
+/// Wires only cross in the middle, which does not connect them.
+///
+/// Fails if a cell or wire would be placed outside the canvas.
+pub fn layout(cells: Vec<Cell>) -> Result<(Vec<SavedComponent>, Vec<SavedWire>), LayoutError> {
+    let levels = get_levels(&cells);
+    let column_count = levels.iter().max().map(|level| level + 1).unwrap_or(0);
+
+    let drivers: HashMap<NetId, (usize, PinIdx)> = cells.iter().enumerate()
+        .flat_map(|(cell_idx, cell)| cell.outputs.iter().map(move |(pin, net)| (*net, (cell_idx, *pin))))
+        .collect();
+
+    // Channel `n` is in front of column `n`, the last one is behind all columns
+    let mut reads: Vec<Vec<NetId>> = vec![vec![]; column_count + 1];
+    let mut feedback: Vec<Vec<NetId>> = vec![vec![]; column_count + 1];
+
+    for (cell_idx, cell) in cells.iter().enumerate() {
+        let level = levels[cell_idx];
+
+        for (_, net) in cell.inputs.iter() {
+            if !reads[level].contains(net) {
+                reads[level].push(*net);
+            }
+
+            if let Some(&(driver, _)) = drivers.get(net) {
+                let driver_level = levels[driver];
+                if driver_level >= level && !feedback[driver_level + 1].contains(net) {
+                    feedback[driver_level + 1].push(*net);
+                }
+            }
+        }
+    }
+
+    let pin_span = |cell: &Cell| -> (i64, i64, i64, i64) {
+        let pins = cell.component.get_pins();
+        (
+            pins.iter().map(|pin| pin.location.x as i64).min().unwrap_or(0),
+            pins.iter().map(|pin| pin.location.x as i64).max().unwrap_or(0),
+            pins.iter().map(|pin| pin.location.y as i64).min().unwrap_or(0),
+            pins.iter().map(|pin| pin.location.y as i64).max().unwrap_or(0),
+        )
+    };
+
+    // Start of every channel and right edge of every column, cells are
+    // aligned by their rightmost pin
+    let mut channel_x: Vec<i64> = vec![0];
+    let mut column_x: Vec<i64> = Vec::new();
+    for level in 0..column_count {
+        let width = cells.iter().enumerate()
+            .filter(|(cell_idx, _)| levels[*cell_idx] == level)
+            .map(|(_, cell)| {
+                let (min_x, max_x, _, _) = pin_span(cell);
+                max_x - min_x
+            })
+            .max()
+            .unwrap_or(0);
+
+        let tracks = (feedback[level].len() + reads[level].len()) as i64;
+        let x = channel_x[level] + TRACK * (tracks + 1) + width;
+        column_x.push(x);
+        channel_x.push(x);
+    }
+
+    let mut order: Vec<usize> = (0..cells.len()).collect();
+    order.sort_by_key(|cell_idx| (levels[*cell_idx], *cell_idx));
+
+    let mut locations: Vec<Point> = vec![(0, 0); cells.len()];
+    let mut top = 0;
+    for cell_idx in order {
+        let (_, max_x, min_y, max_y) = pin_span(&cells[cell_idx]);
+
+        locations[cell_idx] = (column_x[levels[cell_idx]] - max_x, top - min_y);
+        top += max_y - min_y + BAND_GAP;
+    }
+
+    let feedback_rows: HashMap<NetId, i64> = feedback.iter().flatten().enumerate()
+        .map(|(idx, net)| (*net, top + TRACK * idx as i64))
+        .collect();
+
+    let pin_location = |cell_idx: usize, pin: PinIdx| -> Point {
+        let (x, y) = locations[cell_idx];
+        let location = cells[cell_idx].component.get_pins()[pin].location;
+        (x + location.x as i64, y + location.y as i64)
+    };
+
+    // Pins which are not wired must be on the canvas as well
+    for (cell_idx, cell) in cells.iter().enumerate() {
+        for pin in 0..cell.component.get_pins().len() {
+            to_location(pin_location(cell_idx, pin))?;
+        }
+    }
+
+    let mut wires: Vec<SavedWire> = Vec::new();
+    let mut push_wire = |start: Point, end: Point| -> Result<(), LayoutError> {
+        if start != end {
+            wires.push(SavedWire { start: to_location(start)?, end: to_location(end)? });
+        }
+        Ok(())
+    };
+
+    let mut trunk_ends: HashMap<NetId, i64> = HashMap::new();
+    let mut feedback_starts: HashMap<NetId, i64> = HashMap::new();
+    let mut feedback_ends: HashMap<NetId, i64> = HashMap::new();
+
+    for level in 0..=column_count {
+        let tracks = feedback[level].iter().map(|net| (net, true))
+            .chain(reads[level].iter().map(|net| (net, false)));
+
+        for (track, (net, is_feedback)) in tracks.enumerate() {
+            let x = channel_x[level] + TRACK * (track as i64 + 1);
+            let driver = drivers.get(net).map(|(driver, pin)| pin_location(*driver, *pin));
+
+            if is_feedback {
+                let driver = driver.unwrap();
+                push_wire((x, driver.1), (x, feedback_rows[net]))?;
+                trunk_ends.entry(*net).and_modify(|end| *end = (*end).max(x)).or_insert(x);
+                feedback_ends.insert(*net, x);
+                continue;
+            }
+
+            let mut rows: Vec<i64> = Vec::new();
+
+            for (cell_idx, cell) in cells.iter().enumerate().filter(|(cell_idx, _)| levels[*cell_idx] == level) {
+                for (pin, _) in cell.inputs.iter().filter(|(_, input_net)| input_net == net) {
+                    let location = pin_location(cell_idx, *pin);
+                    push_wire((x, location.1), location)?;
+                    rows.push(location.1);
+                }
+            }
+
+            match driver {
+                Some(driver) if driver.0 < x => {
+                    rows.push(driver.1);
+                    trunk_ends.entry(*net).and_modify(|end| *end = (*end).max(x)).or_insert(x);
+                }
+                Some(_) => {
+                    rows.push(feedback_rows[net]);
+                    feedback_starts.entry(*net).and_modify(|start| *start = (*start).min(x)).or_insert(x);
+                }
+                None => {}
+            }
+
+            let (min_y, max_y) = (*rows.iter().min().unwrap(), *rows.iter().max().unwrap());
+            push_wire((x, min_y), (x, max_y))?;
+        }
+    }
+
+    for (net, end) in trunk_ends {
+        let (driver, pin) = drivers[&net];
+        let start = pin_location(driver, pin);
+        push_wire(start, (end, start.1))?;
+    }
+
+    for (net, start) in feedback_starts {
+        let y = feedback_rows[&net];
+        push_wire((start, y), (feedback_ends[&net], y))?;
+    }
+
+    let components = cells.into_iter().zip(locations)
+        .map(|(cell, location)| Ok(SavedComponent { location: to_location(location)?, component: cell.component }))
+        .collect::<Result<_, LayoutError>>()?;
+
+    Ok((components, wires))
+}
+
+/// Longest path from a cell without drivers, ignoring edges which close loops.
+fn get_levels(cells: &[Cell]) -> Vec<usize> {
+    let drivers: HashMap<NetId, usize> = cells.iter().enumerate()
+        .flat_map(|(cell_idx, cell)| cell.outputs.iter().map(move |(_, net)| (*net, cell_idx)))
+        .collect();
+
+    fn visit(cell_idx: usize, cells: &[Cell], drivers: &HashMap<NetId, usize>,
+             levels: &mut Vec<Option<usize>>, visiting: &mut Vec<bool>) -> usize {
+        if let Some(level) = levels[cell_idx] {
+            return level;
+        }
+
+        visiting[cell_idx] = true;
+        let mut level = 0;
+        for (_, net) in cells[cell_idx].inputs.iter() {
+            if let Some(&driver) = drivers.get(net) {
+                if !visiting[driver] {
+                    level = level.max(visit(driver, cells, drivers, levels, visiting) + 1);
+                }
+            }
+        }
+        visiting[cell_idx] = false;
+
+        levels[cell_idx] = Some(level);
+        level
+    }
+
+    let mut levels: Vec<Option<usize>> = vec![None; cells.len()];
+    let mut visiting = vec![false; cells.len()];
+    for cell_idx in 0..cells.len() {
+        visit(cell_idx, cells, &drivers, &mut levels, &mut visiting);
+    }
+
+    levels.into_iter().map(|level| level.unwrap()).collect()
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::core::analysis::{AnalysisError, DEFAULT_MAX_INPUT_BITS, tabulate_definition};
+use crate::core::canvas::location::Location;
+use crate::core::expression::{BinaryOp, Expr, UnaryOp};
+use crate::core::simulation::component::Component;
+use crate::core::simulation::components::behavioural::definition::{Definition, DefinitionError};
+use crate::core::simulation::components::logic::and_gate::AndGate;
+use crate::core::simulation::components::logic::not_gate::NotGate;
+use crate::core::simulation::components::logic::or_gate::OrGate;
+use crate::core::simulation::components::subcircuit::input_pin::InputPin;
+use crate::core::simulation::components::subcircuit::output_pin::OutputPin;
+use crate::core::simulation::pin::Direction;
+use crate::core::synthesis::layout::{Cell, layout, LayoutError, NetId};
+use crate::serde::project::{ProjectFile, SavedCircuit, SavedCircuitBounds, SavedCircuitPin};
+
+pub mod layout;
+
+/// Distance between ports on the subcircuit outline.
+const PORT_SPACING: i16 = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SynthesisError {
+    Definition(DefinitionError),
+    Analysis(AnalysisError),
+    WidePort { name: String },
+    NoInputs,
+    Layout(LayoutError),
+}
+
+impl Display for SynthesisError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SynthesisError::Definition(error) => write!(f, "{}", error),
+            SynthesisError::Analysis(error) => write!(f, "{}", error),
+            SynthesisError::WidePort { name } => {
+                write!(f, "Port {} is wider than one bit, only single bit ports can be synthesized", name)
+            }
+            SynthesisError::NoInputs => {
+                write!(f, "Definition has no inputs to build constant outputs from")
+            }
+            SynthesisError::Layout(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for SynthesisError {}
+
+impl From<DefinitionError> for SynthesisError {
+    fn from(value: DefinitionError) -> Self {
+        SynthesisError::Definition(value)
+    }
+}
+
+impl From<AnalysisError> for SynthesisError {
+    fn from(value: AnalysisError) -> Self {
+        SynthesisError::Analysis(value)
+    }
+}
+
+impl From<LayoutError> for SynthesisError {
+    fn from(value: LayoutError) -> Self {
+        SynthesisError::Layout(value)
+    }
+}
+
+/// Gate netlist with one net per distinct subexpression.
+#[derive(Default)]
+struct Netlist {
+    cells: Vec<Cell>,
+    nets: HashMap<String, NetId>,
+    net_count: usize,
+}
+
+impl Netlist {
+    fn add_net(&mut self) -> NetId {
+        self.net_count += 1;
+        self.net_count - 1
+    }
+
+    fn add_cell(&mut self, component: Component, inputs: Vec<NetId>) -> NetId {
+        let output = self.add_net();
+        let input_count = inputs.len();
+
+        self.cells.push(Cell {
+            component,
+            inputs: inputs.into_iter().enumerate().collect(),
+            outputs: vec![(input_count, output)],
+        });
+
+        output
+    }
+
+    /// Net carrying the value of a sum of products, built from two input gates.
+    fn build(&mut self, expression: &Expr, first_input: Option<&str>) -> Result<NetId, SynthesisError> {
+        let key = expression.to_string();
+        if let Some(net) = self.nets.get(&key) {
+            return Ok(*net);
+        }
+
+        let net = match expression {
+            Expr::Constant(value) => {
+                // Constants are made as `a & a'` or `a | a'` from the first input
+                let input = Expr::variable(first_input.ok_or(SynthesisError::NoInputs)?);
+                let op = if *value == 0 { BinaryOp::And } else { BinaryOp::Or };
+
+                self.build(&Expr::binary(op, input.clone(), Expr::complement(input)), first_input)?
+            }
+            Expr::Unary(UnaryOp::Not, operand) => {
+                let operand = self.build(operand, first_input)?;
+                self.add_cell(NotGate::from_bit_width(1), vec![operand])
+            }
+            Expr::Binary(op @ (BinaryOp::And | BinaryOp::Or), lhs, rhs) => {
+                let lhs = self.build(lhs, first_input)?;
+                let rhs = self.build(rhs, first_input)?;
+
+                let gate = match op {
+                    BinaryOp::And => AndGate::from_bit_width(1),
+                    _ => OrGate::from_bit_width(1),
+                };
+
+                self.add_cell(gate, vec![lhs, rhs])
+            }
+            _ => unreachable!("minimized expressions only contain constants, variables, NOT, AND and OR"),
+        };
+
+        self.nets.insert(key, net);
+        Ok(net)
+    }
+}
+
+/// Builds a circuit computing a behavioural definition from minimized AND,
+/// OR and NOT gates. The project contains a single circuit called `name`
+/// whose ports follow the definition.
+pub fn synthesize(source: &str, name: &str) -> Result<ProjectFile, SynthesisError> {
+    let definition = Definition::parse(source)?;
+
+    if let Some(port) = definition.get_inputs().iter().chain(definition.get_outputs().iter())
+        .find(|port| port.bit_width != 1) {
+        return Err(SynthesisError::WidePort { name: port.name.clone() });
+    }
+
+    let table = tabulate_definition(&definition, DEFAULT_MAX_INPUT_BITS)?;
+    let first_input = table.inputs.first().map(|port| port.name.as_str());
+
+    let mut netlist = Netlist::default();
+
+    for port in table.inputs.iter() {
+        let net = netlist.add_cell(InputPin::create(&port.name), vec![]);
+        netlist.nets.insert(port.name.clone(), net);
+    }
+
+    for (port, (_, expression)) in table.outputs.iter().zip(table.get_expressions()) {
+        let net = netlist.build(&expression, first_input)?;
+
+        netlist.cells.push(Cell {
+            component: OutputPin::create(&port.name),
+            inputs: vec![(0, net)],
+            outputs: vec![],
+        });
+    }
+
+    let (components, wires) = layout(netlist.cells)?;
+
+    let input_names: Vec<&str> = table.inputs.iter().map(|port| port.name.as_str()).collect();
+    let output_names: Vec<&str> = table.outputs.iter().map(|port| port.name.as_str()).collect();
+    let (pins, bounds) = port_pins(&input_names, &output_names)?;
+
+    Ok(ProjectFile {
+        top_circuit: name.to_string(),
+        circuits: HashMap::from([
+            (name.to_string(), SavedCircuit { components, wires, bounds, pins })
+        ]),
+    })
+}
+
+/// Single bit subcircuit ports, inputs on the left edge of the outline and
+/// outputs on the right, each side from the top in the given order.
+pub(crate) fn port_pins(inputs: &[&str], outputs: &[&str]) -> Result<(Vec<SavedCircuitPin>, SavedCircuitBounds), LayoutError> {
+    let ports = inputs.len().max(outputs.len());
+    if ports as i64 * PORT_SPACING as i64 > i16::MAX as i64 {
+        return Err(LayoutError);
+    }
+
+    let pins = inputs.iter().enumerate()
+        .map(|(idx, name)| (Location::new(-3 * PORT_SPACING, PORT_SPACING * idx as i16), name, Direction::Input))
+        .chain(outputs.iter().enumerate()
+            .map(|(idx, name)| (Location::new(0, PORT_SPACING * idx as i16), name, Direction::Output)))
+        .map(|(location, name, direction)| SavedCircuitPin {
+            location,
+            label: name.to_string(),
+            bit_width: 1,
+            direction,
+        })
+        .collect();
+
+    let bounds = SavedCircuitBounds {
+        start: Location::new(-3 * PORT_SPACING, -PORT_SPACING),
+        end: Location::new(0, PORT_SPACING * ports as i16),
+    };
+
+    Ok((pins, bounds))
+}
@@ -2,6 +2,9 @@ use std::path::PathBuf;
 use eframe::Frame;
 use egui::{Context, Shape};
 
+use crate::core::compiler::drc::{check_project, Diagnostic};
+use crate::core::compiler::error::CompileError;
+use crate::core::compiler::project::compile_project;
 use crate::editor::canvas::canvas_size;
 use crate::editor::canvas::grid::ShapeExt;
 use crate::editor::project::EditorProject;
@@ -20,6 +23,7 @@ pub struct State {
 pub struct CirquilEditor {
     state: State,
     tooling: Tree,
+    diagnostics: Option<Result<Vec<Diagnostic>, CompileError>>,
 }
 
 impl Default for CirquilEditor {
@@ -32,6 +36,7 @@ impl Default for CirquilEditor {
         Self {
             state,
             tooling,
+            diagnostics: None,
         }
     }
 }
@@ -65,9 +70,9 @@ impl eframe::App for CirquilEditor {
                                 .pick_file()
                                 .map(parse_logisim);
                             
-                            if let Some(Ok(logisim_project)) = option_logisim_project {
+                            if let Some(Ok(Ok(project_file))) = option_logisim_project.map(|result| result.map(convert_logisim_project)) {
                                 self.state.path = None;
-                                self.state.project = From::from(convert_logisim_project(logisim_project));
+                                self.state.project = From::from(project_file);
                                 self.tooling.populate_circuits(self.state.project.known_circuits());
                             }
                         };
@@ -87,10 +92,47 @@ impl eframe::App for CirquilEditor {
                         self.tooling.populate_circuits(self.state.project.known_circuits());
                         todo!();
                     };
+
+                    if ui.button("Check Design").clicked() {
+                        let project_file = ProjectFile::from(&self.state.project);
+                        self.diagnostics = Some(
+                            compile_project(project_file.clone())
+                                .map(|(_, circuits)| check_project(&project_file, &circuits))
+                        );
+
+                        ui.close_menu();
+                    };
                 });
             })
         });
 
+        if let Some(diagnostics) = &self.diagnostics {
+            let mut open = true;
+
+            egui::Window::new("Design Check")
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    match diagnostics {
+                        Ok(diagnostics) => {
+                            if diagnostics.is_empty() {
+                                ui.label("No problems found");
+                            }
+
+                            for diagnostic in diagnostics.iter() {
+                                ui.label(diagnostic.to_string());
+                            }
+                        }
+                        Err(error) => {
+                            ui.label(error.to_string());
+                        }
+                    }
+                });
+
+            if !open {
+                self.diagnostics = None;
+            }
+        }
+
         let sidebar = egui::SidePanel::left("sidebar")
             .resizable(true)
             .min_width(150.0);
@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
-use egui::{Painter, Pos2, Rect, Stroke, Vec2};
+use egui::{Context, Painter, Pos2, Rect, Stroke, Vec2};
 
+use crate::core::canvas::location::Location;
 use crate::core::simulation::component::{Component, ComponentPins};
-use crate::serde::project::{ProjectFile, SavedCircuit, SavedComponent};
+use crate::serde::project::{ProjectFile, SavedCircuit, SavedCircuitBounds, SavedCircuitPin, SavedComponent, SavedWire};
 
 #[derive(Debug)]
 pub struct EditorComponent {
@@ -20,11 +21,22 @@ impl From<SavedComponent> for EditorComponent {
     }
 }
 
+impl From<&EditorComponent> for SavedComponent {
+    fn from(value: &EditorComponent) -> Self {
+        Self {
+            location: Location::from(value.position),
+            component: value.agg.clone(),
+        }
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct EditorCircuit {
     pub components: Vec<EditorComponent>,
     pub wires: Vec<(Pos2, Pos2)>,
     pub pins: ComponentPins,
+    saved_pins: Vec<SavedCircuitPin>,
+    saved_bounds: Option<SavedCircuitBounds>,
     cached_max: Vec2,
 }
 
@@ -36,12 +48,31 @@ impl From<SavedCircuit> for EditorCircuit {
                 egui::pos2(wire.start.x as f32, wire.start.y as f32),
                 egui::pos2(wire.end.x as f32, wire.end.y as f32),
             )}).collect(),
-            pins: ComponentPins::new(value.pins.into_iter().map(From::from).collect()),
+            pins: ComponentPins::new(value.pins.iter().cloned().map(From::from).collect()),
+            saved_pins: value.pins,
+            saved_bounds: Some(value.bounds),
             cached_max: Default::default(),
         }
     }
 }
 
+impl From<&EditorCircuit> for SavedCircuit {
+    fn from(value: &EditorCircuit) -> Self {
+        Self {
+            components: value.components.iter().map(From::from).collect(),
+            wires: value.wires.iter().map(|(start, end)| SavedWire {
+                start: Location::from(*start),
+                end: Location::from(*end),
+            }).collect(),
+            bounds: value.saved_bounds.clone().unwrap_or(SavedCircuitBounds {
+                start: Location::new(0, 0),
+                end: Location::new(0, 0),
+            }),
+            pins: value.saved_pins.clone(),
+        }
+    }
+}
+
 impl EditorCircuit {
     pub fn add_wire(&mut self, start: Pos2, end: Pos2) {
         self.wires.push((start, end));
@@ -51,6 +82,20 @@ impl EditorCircuit {
         self.components.push(EditorComponent { agg: component, position })
     }
     
+    /// Turns the topmost component under `position` clockwise.
+    pub fn rotate_component_at(&mut self, position: Pos2, ctx: &Context) {
+        let component = self.components.iter_mut().rev().find(|component| {
+            component.agg.as_shapes(ctx).iter()
+                .map(|shape| shape.visual_bounding_rect().translate(component.position))
+                .any(|rect| rect.expand(2.0).contains(position))
+        });
+
+        if let Some(component) = component {
+            let facing = component.agg.get_facing().rotated_clockwise();
+            component.agg.set_facing(facing);
+        }
+    }
+
     pub fn show(&mut self, painter: &Painter, viewport: Rect, offset: Vec2) {
         let mut max_x = 0.0f32;
         let mut max_y = 0.0f32;
@@ -122,6 +167,15 @@ impl From<ProjectFile> for EditorProject {
     }
 }
 
+impl From<&EditorProject> for ProjectFile {
+    fn from(value: &EditorProject) -> Self {
+        Self {
+            top_circuit: value.top.clone(),
+            circuits: value.circuits.iter().map(|(k, v)| (k.clone(), From::from(v))).collect(),
+        }
+    }
+}
+
 impl EditorProject {
     pub fn pick(&mut self, id: &CircuitId) {
         if self.circuits.contains_key(id) {
@@ -15,6 +15,29 @@ impl Action for PointerCursor {
     }
 }
 
+#[derive(Default)]
+pub struct RotateCursor {
+
+}
+
+impl Action for RotateCursor {
+    fn act(&mut self, state: &mut State, response: &Response, _painter: &Painter, _viewport: Rect) {
+        if !response.clicked() {
+            return;
+        }
+
+        let Some(pointer) = response.interact_pointer_pos() else {
+            return;
+        };
+
+        let Some(circuit) = state.project.picked_circuit() else {
+            return eprintln!("Failed to retrieve the picked circuit!");
+        };
+
+        circuit.rotate_component_at(pointer - response.rect.min.to_vec2(), &response.ctx);
+    }
+}
+
 #[derive(Default)]
 pub struct WireCursor {
     dragged_from: Option<Pos2>,
@@ -31,8 +54,8 @@ impl Action for WireCursor {
         }
 
         let offset = response.rect.min.to_vec2();
-        if !response.clicked() && self.dragged_from.is_some() {
-            let start = self.dragged_from.unwrap() + offset;
+        if let (false, Some(dragged_from)) = (response.clicked(), self.dragged_from) {
+            let start = dragged_from + offset;
             painter.line_segment(
                 [start, grid_normalize_end(pointer, start)],
                 Stroke::new(2.0, response.ctx.style().visuals.weak_text_color()),
@@ -43,8 +66,8 @@ impl Action for WireCursor {
             return;
         }
 
-        let end = if self.dragged_from.is_some() {
-            let start = self.dragged_from.unwrap() + offset;
+        let end = if let Some(dragged_from) = self.dragged_from {
+            let start = dragged_from + offset;
             let end = grid_normalize_end(pointer, start);
 
             if start == end {
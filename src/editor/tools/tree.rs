@@ -1,16 +1,11 @@
 use std::iter::Map;
 use egui::{Response, Ui};
 use egui::collapsing_header::CollapsingState;
-use crate::core::simulation::components::clock_generator::ClockGenerator;
-use crate::core::simulation::components::input::button::InputButton;
-use crate::core::simulation::components::logic::and_gate::AndGate;
-use crate::core::simulation::components::logic::not_gate::NotGate;
-use crate::core::simulation::components::logic::or_gate::OrGate;
-use crate::core::simulation::components::tunnel::Tunnel;
+use crate::core::registry::get_descriptors;
 use crate::editor::project::CircuitId;
 use crate::editor::tools::circuit::Circuit;
 use crate::editor::tools::component::ComponentFactory;
-use crate::editor::tools::cursors::{PointerCursor, WireCursor};
+use crate::editor::tools::cursors::{PointerCursor, RotateCursor, WireCursor};
 use super::tool::{Tool, ToolIdx};
 
 #[derive(Debug)]
@@ -37,6 +32,11 @@ impl Group {
     }
 }
 
+/// Component tools follow the built-in cursors, circuits come after every
+/// registered component.
+const COMPONENT_TOOLS: ToolIdx = 3;
+const CIRCUIT_TOOLS: ToolIdx = 10_000;
+
 #[derive(Debug)]
 pub struct Tree {
     picked: ToolIdx,
@@ -45,40 +45,33 @@ pub struct Tree {
 
 impl Default for Tree {
     fn default() -> Self {
-        let groups = vec![
+        let mut groups = vec![
             Group::new("Circuits", vec![
                 
             ]),
             Group::new("Tools", vec![
                 Tool::new(0, Box::new(PointerCursor::default()), "Pointer"),
                 Tool::new(1, Box::new(WireCursor::default()), "Wire"),
-            ]),
-            Group::new("Wiring", vec![
-                Tool::new(2, Box::new(ComponentFactory::new(Box::new(|| {
-                    Tunnel::from_name_width("tunnel", 8)
-                }))), "Tunnel"),
-                Tool::new(3, Box::new(ComponentFactory::new(Box::new(|| {
-                    ClockGenerator::create()
-                }))), "Clock Generator"),
-            ]),
-            Group::new("Gates", vec![
-                Tool::new(4, Box::new(ComponentFactory::new(Box::new(|| {
-                    NotGate::from_bit_width(8)
-                }))), "NOT Gate"),
-                Tool::new(5, Box::new(ComponentFactory::new(Box::new(|| {
-                    AndGate::from_bit_width(8)
-                }))), "AND Gate"),
-                Tool::new(6, Box::new(ComponentFactory::new(Box::new(|| {
-                    OrGate::from_bit_width(8)
-                }))), "OR Gate"),
-            ]),
-            Group::new("Input and Output", vec![
-                Tool::new(7, Box::new(ComponentFactory::new(Box::new(|| {
-                    InputButton::create()
-                }))), "Button"),
+                Tool::new(2, Box::new(RotateCursor::default()), "Rotate"),
             ]),
         ];
 
+        let descriptors = get_descriptors().into_iter()
+            .filter_map(|descriptor| Some((descriptor.category?, descriptor)));
+
+        for (idx, (category, descriptor)) in descriptors.enumerate() {
+            let tool = Tool::new(
+                COMPONENT_TOOLS + idx,
+                Box::new(ComponentFactory::new(Box::new(descriptor.create))),
+                descriptor.name,
+            );
+
+            match groups.iter_mut().find(|group| group.name == category) {
+                Some(group) => group.tools.push(tool),
+                None => groups.push(Group::new(category, vec![tool])),
+            }
+        }
+
         Tree {
             picked: 0,
             groups,
@@ -89,7 +82,7 @@ impl Default for Tree {
 impl Tree {
     pub fn populate_circuits(&mut self, circuits: impl IntoIterator<Item = CircuitId>) {
         self.groups[0].tools = circuits.into_iter().enumerate().map(|(idx, id)| {
-            Tool::new(100 + idx, Box::new(Circuit::new(id.clone())), id)
+            Tool::new(CIRCUIT_TOOLS + idx, Box::new(Circuit::new(id.clone())), id)
         }).collect();
     }
     
@@ -1,6 +1,7 @@
 use egui::{Context, Pos2, Rect, Shape, Vec2};
 
 use crate::core::simulation::component::{Component, ComponentModel};
+use crate::gui::facing::{rotate_rect, rotate_shape};
 
 pub trait Poke {
     fn mouse_pressed(&self, _position: Pos2) {}
@@ -24,6 +25,7 @@ impl Component {
         match &self.model {
             ComponentModel::ClockGenerator(c) => { c.mouse_pressed(position) }
             ComponentModel::InputButton(c) => { c.mouse_pressed(position) }
+            ComponentModel::Extension(c) => { c.0.mouse_pressed(position) }
             _ => {}
         }
     }
@@ -31,6 +33,7 @@ impl Component {
         match &self.model {
             ComponentModel::ClockGenerator(c) => { c.mouse_released(position) }
             ComponentModel::InputButton(c) => { c.mouse_released(position) }
+            ComponentModel::Extension(c) => { c.0.mouse_released(position) }
             _ => {}
         }
     }
@@ -38,6 +41,7 @@ impl Component {
         match &self.model {
             ComponentModel::ClockGenerator(c) => { c.mouse_clicked(position) }
             ComponentModel::InputButton(c) => { c.mouse_clicked(position) }
+            ComponentModel::Extension(c) => { c.0.mouse_clicked(position) }
             _ => {}
         }
     }
@@ -45,6 +49,7 @@ impl Component {
         match &self.model {
             ComponentModel::ClockGenerator(c) => { c.mouse_dragged(delta) }
             ComponentModel::InputButton(c) => { c.mouse_dragged(delta) }
+            ComponentModel::Extension(c) => { c.0.mouse_dragged(delta) }
             _ => {}
         }
     }
@@ -53,11 +58,12 @@ impl Component {
         match &self.model {
             ComponentModel::ClockGenerator(c) => { c.key_typed() }
             ComponentModel::InputButton(c) => { c.key_typed() }
+            ComponentModel::Extension(c) => { c.0.key_typed() }
             _ => {}
         }
     }
     pub fn as_shapes(&self, context: &Context) -> Vec<Shape> {
-        match &self.model {
+        let shapes = match &self.model {
             ComponentModel::ClockGenerator(c) => { c.as_shapes(context) }
             ComponentModel::AndGate(c) => { c.as_shapes(context) }
             ComponentModel::OrGate(c) => { c.as_shapes(context) }
@@ -68,10 +74,15 @@ impl Component {
             ComponentModel::InputPin(c) => { c.as_shapes(context) }
             ComponentModel::OutputPin(c) => { c.as_shapes(context) }
             ComponentModel::Subcircuit(c) => { c.as_shapes(context) }
-        }
+
+            ComponentModel::Extension(c) => { c.0.as_shapes(context) }
+        };
+
+        let facing = self.get_facing();
+        shapes.into_iter().map(|shape| rotate_shape(shape, facing)).collect()
     }
     pub fn get_bounds(&self) -> Rect {
-        match &self.model {
+        let bounds = match &self.model {
             ComponentModel::ClockGenerator(c) => { c.get_bounds() }
             ComponentModel::AndGate(c) => { c.get_bounds() }
             ComponentModel::OrGate(c) => { c.get_bounds() }
@@ -82,6 +93,10 @@ impl Component {
             ComponentModel::InputPin(_) => { Rect::ZERO }
             ComponentModel::OutputPin(_) => { Rect::ZERO }
             ComponentModel::Subcircuit(_) => { Rect::ZERO }
-        }
+
+            ComponentModel::Extension(c) => { c.0.get_bounds() }
+        };
+
+        rotate_rect(bounds, self.get_facing())
     }
 }
\ No newline at end of file
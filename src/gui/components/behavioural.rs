@@ -0,0 +1,57 @@
+use eframe::emath::{Align2, Pos2, Rect};
+use eframe::epaint::{Color32, FontId, Rounding, Shape, Stroke};
+use egui::Context;
+
+use crate::core::simulation::components::behavioural::{Behavioural, BODY_WIDTH, PORT_SPACING};
+use crate::core::simulation::components::behavioural::definition::Port;
+use crate::gui::component::{AsShapes, Bounds, Poke};
+
+const LABEL_PAD: f32 = 4.0;
+
+impl AsShapes for Behavioural {
+    fn as_shapes(&self, context: &Context) -> Vec<Shape> {
+        port_box_shapes(context, self.definition.get_inputs(), self.definition.get_outputs())
+    }
+}
+
+impl Bounds for Behavioural {
+    fn get_bounds(&self) -> Rect {
+        port_box_bounds(self.definition.get_inputs(), self.definition.get_outputs())
+    }
+}
+
+impl Poke for Behavioural {}
+
+/// Box labelled with its input ports on the left and outputs on the right,
+/// shared by components laid out like [`Behavioural`].
+pub fn port_box_shapes(context: &Context, inputs: &[Port], outputs: &[Port]) -> Vec<Shape> {
+    let bounds = port_box_bounds(inputs, outputs);
+
+    let mut shapes = vec![
+        Shape::rect_filled(bounds, Rounding::ZERO, Color32::WHITE),
+        Shape::rect_stroke(bounds, Rounding::ZERO, Stroke::new(2.0, Color32::BLACK)),
+    ];
+
+    let inputs = inputs.iter().enumerate()
+        .map(|(idx, port)| (port, Pos2::new(-BODY_WIDTH as f32 + LABEL_PAD, (PORT_SPACING * idx as i16) as f32), Align2::LEFT_CENTER));
+    let outputs = outputs.iter().enumerate()
+        .map(|(idx, port)| (port, Pos2::new(-LABEL_PAD, (PORT_SPACING * idx as i16) as f32), Align2::RIGHT_CENTER));
+
+    context.fonts(|fonts| {
+        for (port, position, align) in inputs.chain(outputs) {
+            shapes.push(Shape::text(fonts, position, align, &port.name, FontId::monospace(9.0), Color32::BLACK));
+        }
+    });
+
+    shapes
+}
+
+pub fn port_box_bounds(inputs: &[Port], outputs: &[Port]) -> Rect {
+    let rows = inputs.len().max(outputs.len()).max(1);
+    let half = PORT_SPACING as f32 / 2.0;
+
+    Rect::from_min_max(
+        Pos2::new(-BODY_WIDTH as f32, -half),
+        Pos2::new(0.0, (PORT_SPACING as usize * rows) as f32 - half),
+    )
+}
@@ -0,0 +1,21 @@
+use eframe::emath::Rect;
+use eframe::epaint::Shape;
+use egui::Context;
+
+use crate::core::simulation::components::external::External;
+use crate::gui::component::{AsShapes, Bounds, Poke};
+use crate::gui::components::behavioural::{port_box_bounds, port_box_shapes};
+
+impl AsShapes for External {
+    fn as_shapes(&self, context: &Context) -> Vec<Shape> {
+        port_box_shapes(context, self.get_inputs(), self.get_outputs())
+    }
+}
+
+impl Bounds for External {
+    fn get_bounds(&self) -> Rect {
+        port_box_bounds(self.get_inputs(), self.get_outputs())
+    }
+}
+
+impl Poke for External {}
@@ -2,4 +2,6 @@ pub mod clock_generator;
 pub mod logic;
 pub mod input;
 pub mod tunnel;
-pub mod subcircuit;
\ No newline at end of file
+pub mod subcircuit;
+pub mod behavioural;
+pub mod external;
@@ -0,0 +1,70 @@
+use egui::{Pos2, Rect, Shape, Vec2};
+
+use crate::core::canvas::facing::Facing;
+
+pub fn rotate_pos(position: Pos2, facing: Facing) -> Pos2 {
+    match facing {
+        Facing::East => position,
+        Facing::South => Pos2::new(-position.y, position.x),
+        Facing::West => Pos2::new(-position.x, -position.y),
+        Facing::North => Pos2::new(position.y, -position.x),
+    }
+}
+
+pub fn rotate_rect(rect: Rect, facing: Facing) -> Rect {
+    if rect == Rect::NOTHING || rect == Rect::ZERO {
+        return rect;
+    }
+
+    Rect::from_two_pos(rotate_pos(rect.min, facing), rotate_pos(rect.max, facing))
+}
+
+/// Rotates a shape drawn for an east-facing component around the origin.
+/// Text keeps reading left to right, only its position is rotated.
+pub fn rotate_shape(shape: Shape, facing: Facing) -> Shape {
+    if facing == Facing::East {
+        return shape;
+    }
+
+    let rotate = |position: Pos2| rotate_pos(position, facing);
+
+    match shape {
+        Shape::Vec(shapes) => {
+            Shape::Vec(shapes.into_iter().map(|shape| rotate_shape(shape, facing)).collect())
+        }
+        Shape::Circle(mut circle) => {
+            circle.center = rotate(circle.center);
+            Shape::Circle(circle)
+        }
+        Shape::LineSegment { points, stroke } => {
+            Shape::LineSegment { points: points.map(rotate), stroke }
+        }
+        Shape::Path(mut path) => {
+            path.points = path.points.into_iter().map(rotate).collect();
+            Shape::Path(path)
+        }
+        Shape::Rect(mut rect) => {
+            rect.rect = rotate_rect(rect.rect, facing);
+            Shape::Rect(rect)
+        }
+        Shape::Text(mut text) => {
+            let size: Vec2 = text.galley.rect.size();
+            let center = text.pos + text.galley.rect.center().to_vec2();
+            text.pos = rotate(center) - size / 2.0 - text.galley.rect.min.to_vec2();
+            Shape::Text(text)
+        }
+        Shape::Mesh(mut mesh) => {
+            mesh.vertices.iter_mut().for_each(|vertex| vertex.pos = rotate(vertex.pos));
+            Shape::Mesh(mesh)
+        }
+        Shape::QuadraticBezier(mut bezier) => {
+            bezier.points = bezier.points.map(rotate);
+            Shape::QuadraticBezier(bezier)
+        }
+        Shape::CubicBezier(mut bezier) => {
+            bezier.points = bezier.points.map(rotate);
+            Shape::CubicBezier(bezier)
+        }
+        shape => shape,
+    }
+}
@@ -3,5 +3,7 @@ pub mod constants;
 pub mod components;
 pub mod location;
 pub mod component;
+pub mod facing;
 pub mod value;
 pub mod probe;
+pub mod svg;
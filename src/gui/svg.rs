@@ -0,0 +1,237 @@
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use egui::{Color32, Context, FontFamily, Pos2, RawInput, Rect, Shape, Stroke, Vec2};
+
+use crate::core::compiler::project::InstantiatedCircuits;
+use crate::core::simulation::circuit::CircuitIdx;
+use crate::core::simulation::probe::CanvasProbe;
+use crate::gui::component::AsShapes;
+use crate::gui::value::get_value_color;
+
+/// Free space around the drawing.
+const MARGIN: f32 = 20.0;
+
+#[derive(Debug, Clone, Default)]
+pub struct SvgOptions {
+    /// Colors wires and pins by their current value like the player,
+    /// otherwise wires are black and pins are not marked.
+    pub values: bool,
+}
+
+/// Draws an instantiated circuit as an SVG document: component shapes,
+/// wires, tunnel labels and the markers of probes placed in this circuit.
+///
+/// Components draw themselves with the same shapes as in the player, laid
+/// out by an egui context which never opens a window.
+pub fn render_svg(circuits: &InstantiatedCircuits, circuit_idx: CircuitIdx, probes: &[CanvasProbe], options: &SvgOptions) -> String {
+    let context = Context::default();
+    let mut shapes: Vec<Shape> = Vec::new();
+
+    let _ = context.run(RawInput::default(), |context| {
+        shapes = circuit_shapes(context, circuits, circuit_idx, probes, options);
+    });
+
+    let bounds = shapes.iter()
+        .map(Shape::visual_bounding_rect)
+        .filter(|rect| rect.is_positive())
+        .fold(Rect::NOTHING, |bounds, rect| bounds.union(rect));
+    let bounds = match bounds.is_positive() {
+        true => bounds.expand(MARGIN),
+        false => Rect::from_min_size(Pos2::ZERO, Vec2::splat(2.0 * MARGIN)),
+    };
+
+    let mut svg = String::new();
+    writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" width="{}" height="{}">"#,
+             number(bounds.min.x), number(bounds.min.y),
+             number(bounds.width()), number(bounds.height()),
+             number(bounds.width()), number(bounds.height())).unwrap();
+    writeln!(svg, r#"<rect x="{}" y="{}" width="{}" height="{}" fill="white"/>"#,
+             number(bounds.min.x), number(bounds.min.y),
+             number(bounds.width()), number(bounds.height())).unwrap();
+
+    for shape in shapes.iter() {
+        write_shape(&mut svg, shape);
+    }
+
+    writeln!(svg, "</svg>").unwrap();
+    svg
+}
+
+pub fn save_svg<P>(path: P, circuits: &InstantiatedCircuits, circuit_idx: CircuitIdx, probes: &[CanvasProbe], options: &SvgOptions) -> Result<(), Box<dyn Error>>
+    where
+        P: AsRef<Path>,
+{
+    fs::write(path, render_svg(circuits, circuit_idx, probes, options))?;
+    Ok(())
+}
+
+/// Shapes in canvas coordinates, in the order the player paints them.
+fn circuit_shapes(context: &Context, circuits: &InstantiatedCircuits, circuit_idx: CircuitIdx, probes: &[CanvasProbe], options: &SvgOptions) -> Vec<Shape> {
+    let (circuit, canvas_idx) = &circuits.instantiated_circuits[circuit_idx];
+    let canvas = &circuits.canvas_circuits[*canvas_idx];
+
+    let mut shapes: Vec<Shape> = Vec::new();
+
+    for canvas_wire in canvas.wires.iter() {
+        let wire = circuit.get_wire(canvas_wire.wire);
+
+        let color = match (options.values, wire.connected_components.first()) {
+            (true, Some(&(component_idx, pin_idx))) => {
+                let bit_width = circuit.get_component(component_idx).get_pins()[pin_idx].bit_width;
+                get_value_color(wire.value.get(), bit_width)
+            }
+            (true, None) => get_value_color(wire.value.get(), 1),
+            (false, _) => Color32::BLACK,
+        };
+
+        for (start, end) in canvas_wire.segments.iter() {
+            shapes.push(Shape::line_segment([Pos2::from(*start), Pos2::from(*end)], Stroke::new(2.0, color)));
+        }
+
+        for node in canvas_wire.nodes.iter() {
+            shapes.push(Shape::circle_filled(Pos2::from(*node), 3.5, color));
+        }
+    }
+
+    for canvas_component in canvas.components.iter() {
+        let component = circuit.get_component(canvas_component.component);
+        let offset = Vec2::from(canvas_component.loc);
+
+        let mut component_shapes = component.as_shapes(context);
+        if options.values {
+            for pin in component.get_pins() {
+                let color = get_value_color(pin.value.get(), pin.bit_width);
+                component_shapes.push(Shape::circle_filled(Pos2::from(pin.location), 2.0, color));
+            }
+        }
+
+        for mut shape in component_shapes {
+            shape.translate(offset);
+            shapes.push(shape);
+        }
+    }
+
+    for CanvasProbe { location, probe } in probes.iter().filter(|canvas_probe| canvas_probe.probe.circuit == circuit_idx) {
+        for mut shape in probe.as_shapes(context) {
+            shape.translate(Vec2::from(*location));
+            shapes.push(shape);
+        }
+    }
+
+    shapes
+}
+
+fn number(value: f32) -> String {
+    format!("{}", (value * 100.0).round() / 100.0)
+}
+
+fn color(color: Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn fill(fill: Color32) -> String {
+    match fill.a() {
+        0 => r#"fill="none""#.to_string(),
+        255 => format!(r#"fill="{}""#, color(fill)),
+        alpha => format!(r#"fill="{}" fill-opacity="{}""#, color(fill), number(alpha as f32 / 255.0)),
+    }
+}
+
+fn stroke(stroke: Stroke) -> String {
+    if stroke.is_empty() {
+        return r#"stroke="none""#.to_string();
+    }
+
+    let mut attributes = format!(r#"stroke="{}" stroke-width="{}""#, color(stroke.color), number(stroke.width));
+    if stroke.color.a() != 255 {
+        write!(attributes, r#" stroke-opacity="{}""#, number(stroke.color.a() as f32 / 255.0)).unwrap();
+    }
+
+    attributes
+}
+
+fn points(points: &[Pos2]) -> String {
+    points.iter()
+        .map(|point| format!("{},{}", number(point.x), number(point.y)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn write_shape(svg: &mut String, shape: &Shape) {
+    match shape {
+        Shape::Vec(shapes) => shapes.iter().for_each(|shape| write_shape(svg, shape)),
+        Shape::Circle(circle) => {
+            writeln!(svg, r#"<circle cx="{}" cy="{}" r="{}" {} {}/>"#,
+                     number(circle.center.x), number(circle.center.y), number(circle.radius),
+                     fill(circle.fill), stroke(circle.stroke)).unwrap();
+        }
+        Shape::LineSegment { points: [start, end], stroke: line_stroke } => {
+            writeln!(svg, r#"<line x1="{}" y1="{}" x2="{}" y2="{}" {} stroke-linecap="round"/>"#,
+                     number(start.x), number(start.y), number(end.x), number(end.y), stroke(*line_stroke)).unwrap();
+        }
+        Shape::Path(path) => {
+            let element = if path.closed { "polygon" } else { "polyline" };
+            writeln!(svg, r#"<{} points="{}" {} {} stroke-linejoin="round"/>"#,
+                     element, points(&path.points), fill(path.fill), stroke(path.stroke)).unwrap();
+        }
+        Shape::Rect(rect) => {
+            writeln!(svg, r#"<rect x="{}" y="{}" width="{}" height="{}" rx="{}" {} {}/>"#,
+                     number(rect.rect.min.x), number(rect.rect.min.y),
+                     number(rect.rect.width()), number(rect.rect.height()),
+                     number(rect.rounding.nw), fill(rect.fill), stroke(rect.stroke)).unwrap();
+        }
+        Shape::Text(text) => {
+            let Some(section) = text.galley.job.sections.first() else { return; };
+
+            let family = match section.format.font_id.family {
+                FontFamily::Monospace => "monospace",
+                _ => "sans-serif",
+            };
+            let text_color = text.override_text_color.unwrap_or(match section.format.color {
+                Color32::PLACEHOLDER => text.fallback_color,
+                color => color,
+            });
+
+            for row in text.galley.rows.iter() {
+                let line: String = row.glyphs.iter().map(|glyph| glyph.chr).collect();
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                writeln!(svg, r#"<text x="{}" y="{}" font-family="{}" font-size="{}" dominant-baseline="central" {}>{}</text>"#,
+                         number(text.pos.x + row.rect.min.x), number(text.pos.y + row.rect.center().y),
+                         family, number(section.format.font_id.size), fill(text_color), escape(&line)).unwrap();
+            }
+        }
+        Shape::Mesh(mesh) => {
+            for triangle in mesh.indices.chunks_exact(3) {
+                let vertices: Vec<Pos2> = triangle.iter().map(|&idx| mesh.vertices[idx as usize].pos).collect();
+                writeln!(svg, r#"<polygon points="{}" {}/>"#,
+                         points(&vertices), fill(mesh.vertices[triangle[0] as usize].color)).unwrap();
+            }
+        }
+        Shape::QuadraticBezier(bezier) => {
+            let [start, control, end] = bezier.points;
+            writeln!(svg, r#"<path d="M {} {} Q {} {} {} {}{}" {} {}/>"#,
+                     number(start.x), number(start.y), number(control.x), number(control.y),
+                     number(end.x), number(end.y), if bezier.closed { " Z" } else { "" },
+                     fill(bezier.fill), stroke(bezier.stroke)).unwrap();
+        }
+        Shape::CubicBezier(bezier) => {
+            let [start, first, second, end] = bezier.points;
+            writeln!(svg, r#"<path d="M {} {} C {} {} {} {} {} {}{}" {} {}/>"#,
+                     number(start.x), number(start.y), number(first.x), number(first.y),
+                     number(second.x), number(second.y), number(end.x), number(end.y),
+                     if bezier.closed { " Z" } else { "" },
+                     fill(bezier.fill), stroke(bezier.stroke)).unwrap();
+        }
+        Shape::Noop | Shape::Callback(_) => {}
+    }
+}
@@ -0,0 +1,46 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::core::compiler::drc::{check_project, Severity};
+use crate::core::compiler::project::compile_project;
+use crate::serde::project::ProjectFile;
+
+#[derive(Debug)]
+pub struct CheckArgs {
+    pub circuit_path: PathBuf,
+}
+
+/// Prints design check diagnostics and returns the number of errors found.
+pub fn run_check(args: CheckArgs) -> Result<usize, Box<dyn Error>> {
+    let project_file = ProjectFile::load(&args.circuit_path)?;
+
+    let (_, circuits) = compile_project(project_file.clone())?;
+
+    let diagnostics = check_project(&project_file, &circuits);
+
+    for diagnostic in diagnostics.iter() {
+        let severity = match diagnostic.severity {
+            Severity::Warning => "WARNING".yellow(),
+            Severity::Error => "ERROR".red(),
+        };
+
+        println!("{}: {} ({}, {}): {}",
+                 severity, diagnostic.circuit,
+                 diagnostic.location.x, diagnostic.location.y,
+                 diagnostic.message());
+    }
+
+    let errors = diagnostics.iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .count();
+
+    if diagnostics.is_empty() {
+        println!("{}", "No problems found".green());
+    } else {
+        println!("{} errors, {} warnings", errors, diagnostics.len() - errors);
+    }
+
+    Ok(errors)
+}
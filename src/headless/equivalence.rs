@@ -0,0 +1,41 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::core::analysis::equivalence::{check_equivalence, Equivalence, EquivalenceOptions};
+use crate::headless::files::load_circuit;
+
+#[derive(Debug)]
+pub struct EquivalenceArgs {
+    pub first_path: PathBuf,
+    pub second_path: PathBuf,
+    pub options: EquivalenceOptions,
+}
+
+/// Compares top circuits of two projects and returns whether they are equivalent.
+pub fn run_equivalence(args: EquivalenceArgs) -> Result<bool, Box<dyn Error>> {
+    let (first_idx, first_circuits) = load_circuit(args.first_path)?;
+    let (second_idx, second_circuits) = load_circuit(args.second_path)?;
+
+    let result = check_equivalence(
+        &first_circuits.instantiated_circuits[first_idx].0,
+        &second_circuits.instantiated_circuits[second_idx].0,
+        &args.options,
+    )?;
+
+    match result {
+        Equivalence::Equivalent { vectors, exhaustive: true } => {
+            println!("{} (all {} input vectors checked)", "Circuits are equivalent".green(), vectors);
+            Ok(true)
+        }
+        Equivalence::Equivalent { vectors, exhaustive: false } => {
+            println!("{} in {} random input vectors", "No difference found".green(), vectors);
+            Ok(true)
+        }
+        Equivalence::Different(counterexample) => {
+            println!("{}: {}", "Circuits differ".red(), counterexample);
+            Ok(false)
+        }
+    }
+}
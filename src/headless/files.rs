@@ -9,7 +9,7 @@ use crate::core::simulation::probe::CanvasProbe;
 use crate::core::simulation::workbench::from_workbench_file;
 use crate::serde::fs::deserialize_from_file;
 use crate::serde::project::ProjectFile;
-use crate::serde::workbench::WorkbenchFile;
+use crate::serde::workbench::{SavedAssertion, WorkbenchFile};
 
 pub fn load_circuit<P>(path: P) -> Result<(CircuitIdx, InstantiatedCircuits), Box<dyn Error>>
     where
@@ -17,32 +17,40 @@ pub fn load_circuit<P>(path: P) -> Result<(CircuitIdx, InstantiatedCircuits), Bo
 {
     let project_file = ProjectFile::load(path)?;
 
-    let (top_circuit, compiled_circuits) = compile_project(project_file);
+    let (top_circuit, compiled_circuits) = compile_project(project_file)?;
 
-    compiled_circuits.instantiated_circuits.iter().for_each(
-        |(circuit, _)| circuit.propagate_all()
-    );
+    compiled_circuits.reset();
 
     Ok((top_circuit, compiled_circuits))
 }
 
-pub fn load_probes<P>(path: P, compiled_circuits: &InstantiatedCircuits) -> Result<Vec<CanvasProbe>, Box<dyn Error>>
+pub struct LoadedWorkbench {
+    /// Probes which could be attached to the circuit.
+    pub probes: Vec<CanvasProbe>,
+    pub assertions: Vec<SavedAssertion>,
+    /// Errors of the probes which could not be attached.
+    pub warnings: Vec<String>,
+}
+
+pub fn load_workbench<P>(path: P, compiled_circuits: &InstantiatedCircuits) -> Result<LoadedWorkbench, Box<dyn Error>>
     where
         P: AsRef<Path>
 {
     let workbench_file: WorkbenchFile = deserialize_from_file(path)?;
 
-    let (probes, _, _) = from_workbench_file(workbench_file, compiled_circuits);
+    let (probes, _, _, assertions) = from_workbench_file(workbench_file, compiled_circuits);
 
     let verified_probes: Vec<CanvasProbe> = probes.iter()
         .filter_map(|probe| probe.clone().ok())
         .collect();
 
-    probes.into_iter()
+    let warnings: Vec<String> = probes.into_iter()
         .filter(|probe| probe.is_err())
         .map(|probe| probe.unwrap_err())
-        .for_each(|error| println!("{}: {error}", "WARNING".yellow()));
+        .collect();
 
+    warnings.iter()
+        .for_each(|error| println!("{}: {error}", "WARNING".yellow()));
 
-    Ok(verified_probes)
+    Ok(LoadedWorkbench { probes: verified_probes, assertions, warnings })
 }
\ No newline at end of file
@@ -0,0 +1,27 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::serde::yosys::load_yosys;
+
+#[derive(Debug)]
+pub struct ImportArgs {
+    pub netlist_path: PathBuf,
+    pub output_path: PathBuf,
+}
+
+/// Imports a Yosys JSON netlist and saves it as a project.
+pub fn run_import(args: ImportArgs) -> Result<(), Box<dyn Error>> {
+    let project_file = load_yosys(&args.netlist_path)?;
+
+    project_file.save(&args.output_path)?;
+
+    println!("{} {} ({} circuits, top circuit {})",
+             "Successfully saved imported project:".green(),
+             args.output_path.display(),
+             project_file.circuits.len(),
+             project_file.top_circuit);
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use std::error::Error;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+use crate::headless::session::Session;
+
+#[derive(Debug)]
+pub struct InteractiveArgs {
+    pub circuit_path: PathBuf,
+    pub workbench_path: Option<PathBuf>,
+}
+
+/// Reads commands line by line until `quit` or the end of `input` and writes
+/// one response per command, errors are prefixed with `ERROR:`.
+pub fn run_interactive<R, W>(args: InteractiveArgs, input: R, mut output: W) -> Result<(), Box<dyn Error>>
+    where
+        R: BufRead,
+        W: Write,
+{
+    let mut session = Session::load(args.circuit_path, args.workbench_path)?;
+
+    for line in input.lines() {
+        let line = line?;
+        let command = line.trim();
+
+        if command.is_empty() || command.starts_with('#') {
+            continue;
+        }
+
+        if command == "quit" || command == "exit" {
+            break;
+        }
+
+        match session.execute(command) {
+            Ok(response) if response.is_empty() => {}
+            Ok(response) => writeln!(output, "{}", response)?,
+            Err(error) => writeln!(output, "ERROR: {}", error)?,
+        }
+
+        output.flush()?;
+    }
+
+    Ok(())
+}
@@ -1,19 +1,36 @@
+use std::collections::BTreeMap;
 use std::error::Error;
-use std::path::PathBuf;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use colored::Colorize;
 
+use crate::core::simulation::assertion::from_saved_assertions;
+use crate::core::simulation::condition::Condition;
 use crate::core::simulation::probe::CanvasProbe;
+use crate::core::simulation::stimulus::Stimulus;
 use crate::core::simulation::trace::Trace;
 use crate::core::simulation::value::Value;
-use crate::headless::files::{load_circuit, load_probes};
+use crate::headless::files::{load_circuit, load_workbench, LoadedWorkbench};
 use crate::player::replay::ReplayManager;
 use crate::serde::csv::{save_csv, TimeSeries, TimeSeriesRecord};
 use crate::serde::fs::serialize_to_file;
 use crate::serde::replay::ReplayFile;
+use crate::serde::stimulus::StimulusFile;
+use crate::serde::summary::{RunSummary, StopReason};
+use crate::serde::vcd::{save_vcd, VcdSignal};
 
-mod files;
+pub mod files;
+pub mod check;
+pub mod equivalence;
+pub mod synthesize;
+pub mod vectors;
+pub mod session;
+pub mod interactive;
+pub mod remote;
+pub mod verilog;
+pub mod import;
+pub mod render;
 
 #[derive(Debug)]
 pub struct HeadlessArgs {
@@ -22,13 +39,42 @@ pub struct HeadlessArgs {
     pub cycles: usize,
     pub replay_path: Option<PathBuf>,
     pub trace_path: Option<PathBuf>,
+    pub vcd_path: Option<PathBuf>,
+    pub stimulus_path: Option<PathBuf>,
+    /// Stop as soon as this condition over probe names holds.
+    pub until: Option<String>,
+    pub timeout: Option<Duration>,
+    pub summary_path: Option<PathBuf>,
 }
 
-pub fn run_player_headless(args: HeadlessArgs) -> Result<(), Box<dyn Error>> {
+/// Parses the `--timeout` argument, a non-negative number of seconds.
+pub fn parse_timeout(source: &str) -> Result<Duration, String> {
+    let seconds: f64 = source.parse().map_err(|_| format!("{} is not a number of seconds", source))?;
+
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|_| format!("timeout must be a finite non-negative number of seconds, found {}", source))
+}
+
+pub fn run_player_headless(args: HeadlessArgs) -> Result<RunSummary, Box<dyn Error>> {
     let (top_circuit_idx, circuits) = load_circuit(args.circuit_path)?;
     let (top_circuit, _) = circuits.instantiated_circuits.get(top_circuit_idx).unwrap();
 
-    let probes = load_probes(args.workbench_path, &circuits)?;
+    let LoadedWorkbench { probes, assertions: saved_assertions, mut warnings } = load_workbench(args.workbench_path, &circuits)?;
+
+    let (mut assertions, errors) = from_saved_assertions(saved_assertions, &probes, &circuits);
+    if let Some(error) = errors.into_iter().next() {
+        return Err(error.into());
+    }
+
+    let until = match &args.until {
+        Some(source) => Some(Condition::parse(source, &probes, &circuits)?),
+        None => None,
+    };
+
+    let stimulus = match &args.stimulus_path {
+        Some(path) => Stimulus::from_file(StimulusFile::load(path)?, top_circuit_idx, &circuits)?,
+        None => Stimulus::default(),
+    };
 
     let mut replay_manager = ReplayManager::default();
     let mut trace = Trace::default();
@@ -39,11 +85,25 @@ pub fn run_player_headless(args: HeadlessArgs) -> Result<(), Box<dyn Error>> {
 
     let timer = Instant::now();
 
-    for _ in 0..args.cycles {
+    let mut cycles = 0;
+    let mut failed_assertion = None;
+    let mut stop_reason = match until {
+        Some(_) => StopReason::ConditionNotMet,
+        None => StopReason::Cycles,
+    };
+
+    for cycle in 0..args.cycles {
+        if args.timeout.is_some_and(|timeout| timer.elapsed() >= timeout) {
+            stop_reason = StopReason::Timeout;
+            break;
+        }
+
+        stimulus.apply(cycle, &circuits);
+
         top_circuit.tick();
         top_circuit.propagate_ticked();
 
-        if args.trace_path.is_some() {
+        if args.trace_path.is_some() || args.vcd_path.is_some() {
             let mut trace_sample = vec![];
 
             for (CanvasProbe { probe, .. }, trace_idx) in probes.iter() {
@@ -63,39 +123,72 @@ pub fn run_player_headless(args: HeadlessArgs) -> Result<(), Box<dyn Error>> {
                     .collect()
             );
         }
+
+        cycles += 1;
+
+        if let Some(failure) = assertions.iter_mut().find_map(|assertion| assertion.check(cycle, &circuits)) {
+            failed_assertion = Some(failure);
+            stop_reason = StopReason::AssertionFailed;
+            break;
+        }
+
+        if until.as_ref().is_some_and(|condition| condition.holds(&circuits)) {
+            stop_reason = StopReason::Condition;
+            break;
+        }
     }
 
-    println!("{} {} {} {:?}", "Successfully simulated".green(), args.cycles, "cycles in".green(), timer.elapsed());
+    let elapsed = timer.elapsed();
+
+    match stop_reason {
+        StopReason::Cycles => {
+            println!("{} {} {} {:?}", "Successfully simulated".green(), cycles, "cycles in".green(), elapsed);
+        }
+        StopReason::Condition => {
+            println!("{} {} {} {:?}", "Condition met after".green(), cycles, "cycles in".green(), elapsed);
+        }
+        StopReason::ConditionNotMet => {
+            println!("{}: condition not met in {} cycles ({:?})", "FAILED".red(), cycles, elapsed);
+        }
+        StopReason::Timeout => {
+            println!("{}: timed out after {} cycles ({:?})", "FAILED".red(), cycles, elapsed);
+        }
+        StopReason::AssertionFailed => {
+            println!("{}: {}", "ERROR".red(), failed_assertion.as_ref().unwrap().describe());
+        }
+    }
 
-    if args.trace_path.is_some() {
-        let mut records = vec![];
+    let contentions = circuits.get_contentions();
+    if !contentions.is_empty() {
+        let occurrences: u64 = contentions.iter()
+            .map(|report| report.contention.occurrences)
+            .sum();
 
-        for i in 0..trace.recorded_samples {
-            let record: TimeSeriesRecord = probes.iter()
-                .map(|(_, idx)| *idx)
-                .map(|idx| {
-                    if let Some(value) = trace.traces[idx][i as usize] {
-                        value.get_defined_value()
-                    } else {
-                        Value::default().get_defined_value()
-                    }
-                })
-                .collect();
+        println!("{}: {} bus contention events on {} wires", "WARNING".yellow(), occurrences, contentions.len());
 
-            records.push(record);
+        for report in contentions.iter() {
+            println!("  {}", report.describe(&circuits));
+            warnings.push(format!("Bus contention: {}", report.describe(&circuits)));
         }
+    }
 
-        let time_series = TimeSeries {
-            names: probes.iter().map(|(probe, _)| probe.probe.name.clone()).collect(),
-            records,
-        };
+    if let Some(trace_path) = &args.trace_path {
+        save_trace_csv(trace_path, &probes, &trace);
 
-        save_csv(args.trace_path.as_ref().unwrap(), time_series);
+        println!("{} {}", "Successfully saved trace file:".green(), trace_path.display());
+    }
+
+    if let Some(vcd_path) = &args.vcd_path {
+        let signals: Vec<VcdSignal> = probes.iter()
+            .map(|(CanvasProbe { probe, .. }, trace_idx)| probe.to_vcd_signal(&probe.name, *trace_idx, &circuits))
+            .collect();
+
+        save_vcd(vcd_path, &signals, &trace)?;
 
-        println!("{} {}", "Successfully saved trace file:".green(), &args.trace_path.as_ref().unwrap().display());
+        println!("{} {}", "Successfully saved VCD file:".green(), vcd_path.display());
     }
 
-    if args.replay_path.is_some() {
+    if let Some(replay_path) = &args.replay_path {
         let replay_file = ReplayFile {
             top_circuit: top_circuit_idx,
             states: replay_manager.replay.clone(),
@@ -105,10 +198,62 @@ pub fn run_player_headless(args: HeadlessArgs) -> Result<(), Box<dyn Error>> {
             parents: circuits.parents.clone(),
         };
 
-        serialize_to_file(&replay_file, args.replay_path.as_ref().unwrap())?;
+        serialize_to_file(&replay_file, replay_path)?;
+
+        println!("{} {}", "Successfully saved replay file:".green(), replay_path.display());
+    }
+
+    let summary = RunSummary {
+        cycles,
+        stop_reason,
+        elapsed_seconds: elapsed.as_secs_f64(),
+        probes: probes.iter()
+            .map(|(CanvasProbe { probe, .. }, _)| {
+                let (circuit, _) = &circuits.instantiated_circuits[probe.circuit];
+                let value = circuit.wires[probe.wire].value.get();
+
+                (probe.name.clone(), value.to_bit_string(probe.get_bit_width(&circuits)))
+            })
+            .collect::<BTreeMap<String, String>>(),
+        warnings,
+        failed_assertion,
+    };
+
+    if let Some(summary_path) = &args.summary_path {
+        serialize_to_file(&summary, summary_path)?;
+
+        println!("{} {}", "Successfully saved summary file:".green(), summary_path.display());
+    }
+
+    Ok(summary)
+}
+
+/// Saves probe values recorded in `trace` as CSV, one column per probe.
+pub fn save_trace_csv<P>(path: P, probes: &[(CanvasProbe, usize)], trace: &Trace)
+    where
+        P: AsRef<Path>
+{
+    let mut records = vec![];
 
-        println!("{} {}", "Successfully saved replay file:".green(), &args.replay_path.as_ref().unwrap().display());
+    for i in 0..trace.recorded_samples {
+        let record: TimeSeriesRecord = probes.iter()
+            .map(|(_, idx)| *idx)
+            .map(|idx| {
+                if let Some(value) = trace.traces[idx][i as usize] {
+                    value.get_defined_value()
+                } else {
+                    Value::default().get_defined_value()
+                }
+            })
+            .collect();
+
+        records.push(record);
     }
 
-    Ok(())
+    let time_series = TimeSeries {
+        names: probes.iter().map(|(probe, _)| probe.probe.name.clone()).collect(),
+        records,
+    };
+
+    save_csv(path, time_series);
 }
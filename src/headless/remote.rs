@@ -0,0 +1,28 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::headless::session::Session;
+use crate::remote::RemoteHandler;
+use crate::remote::server::RemoteServer;
+
+#[derive(Debug)]
+pub struct RemoteArgs {
+    pub circuit_path: PathBuf,
+    pub workbench_path: Option<PathBuf>,
+    /// Loopback address to listen on, like `127.0.0.1:7070`.
+    pub address: String,
+}
+
+/// Serves the remote control protocol until a client asks for shutdown.
+pub fn run_remote(args: RemoteArgs) -> Result<(), Box<dyn Error>> {
+    let mut session = Session::load(args.circuit_path, args.workbench_path)?;
+    let server = RemoteServer::bind(&args.address)?;
+
+    println!("{} {}", "Listening on".green(), server.address);
+
+    RemoteHandler::default().serve(&mut session, &server);
+
+    Ok(())
+}
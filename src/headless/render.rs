@@ -0,0 +1,45 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::core::simulation::probe::CanvasProbe;
+use crate::gui::svg::{save_svg, SvgOptions};
+use crate::headless::session::Session;
+
+#[derive(Debug)]
+pub struct RenderArgs {
+    pub circuit_path: PathBuf,
+    pub output_path: PathBuf,
+    pub workbench_path: Option<PathBuf>,
+    /// Instance path like `main/adder`, with subcircuit components named by
+    /// label or UUID, the top circuit by default.
+    pub instance: Option<String>,
+    pub cycles: usize,
+    pub values: bool,
+}
+
+/// Draws a circuit as SVG, after simulating `cycles` cycles when values or
+/// probes of a workbench are shown.
+pub fn run_render(args: RenderArgs) -> Result<(), Box<dyn Error>> {
+    let mut session = Session::load(args.circuit_path, args.workbench_path)?;
+    session.step(args.cycles);
+
+    let circuit_idx = match &args.instance {
+        Some(instance) => session.circuits.find_instance(instance)
+            .ok_or(format!("Project has no circuit instance {}", instance))?,
+        None => session.top_circuit,
+    };
+
+    let probes: Vec<CanvasProbe> = session.probes.iter().map(|(probe, _)| probe.clone()).collect();
+    let options = SvgOptions { values: args.values };
+
+    save_svg(&args.output_path, &session.circuits, circuit_idx, &probes, &options)?;
+
+    println!("{} {} ({})",
+             "Successfully saved circuit image:".green(),
+             args.output_path.display(),
+             session.circuits.get_instance_labels(circuit_idx).join("/"));
+
+    Ok(())
+}
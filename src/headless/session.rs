@@ -0,0 +1,283 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use crate::core::compiler::project::InstantiatedCircuits;
+use crate::core::simulation::circuit::CircuitIdx;
+use crate::core::simulation::component::ComponentModel;
+use crate::core::simulation::condition::Condition;
+use crate::core::simulation::probe::CanvasProbe;
+use crate::core::simulation::stimulus::{find_inputs, poke_input};
+use crate::core::simulation::trace::Trace;
+use crate::core::simulation::value::{parse_number, Value};
+use crate::headless::files::{load_circuit, load_workbench, LoadedWorkbench};
+use crate::headless::save_trace_csv;
+use crate::serde::vcd::{save_vcd, VcdSignal};
+
+/// Cycles `run-until` simulates before giving up.
+pub const MAX_RUN_UNTIL_CYCLES: usize = 1_000_000;
+
+/// Loaded circuit driven step by step, with every cycle recorded for the
+/// workbench probes.
+pub struct Session {
+    pub top_circuit: CircuitIdx,
+    pub circuits: InstantiatedCircuits,
+    /// Probes with the trace row they are recorded to.
+    pub probes: Vec<(CanvasProbe, usize)>,
+    pub trace: Trace,
+    pub cycle: usize,
+}
+
+impl Session {
+    pub fn new(top_circuit: CircuitIdx, circuits: InstantiatedCircuits, probes: Vec<CanvasProbe>) -> Session {
+        let mut trace = Trace::default();
+
+        let probes = probes.into_iter()
+            .map(|probe| (probe, trace.add_row()))
+            .collect();
+
+        Session { top_circuit, circuits, probes, trace, cycle: 0 }
+    }
+
+    pub fn load<P>(circuit_path: P, workbench_path: Option<P>) -> Result<Session, Box<dyn Error>>
+        where
+            P: AsRef<Path>
+    {
+        let (top_circuit, circuits) = load_circuit(circuit_path)?;
+
+        let probes = match workbench_path {
+            Some(path) => {
+                let LoadedWorkbench { probes, .. } = load_workbench(path, &circuits)?;
+                probes
+            }
+            None => vec![],
+        };
+
+        Ok(Session::new(top_circuit, circuits, probes))
+    }
+
+    /// Ticks the top circuit `cycles` times.
+    pub fn step(&mut self, cycles: usize) {
+        let (top_circuit, _) = &self.circuits.instantiated_circuits[self.top_circuit];
+
+        for _ in 0..cycles {
+            top_circuit.tick();
+            top_circuit.propagate_ticked();
+
+            let sample = self.probes.iter()
+                .map(|(CanvasProbe { probe, .. }, trace_idx)| {
+                    let (circuit, _) = &self.circuits.instantiated_circuits[probe.circuit];
+                    (*trace_idx, circuit.wires[probe.wire].value.get())
+                })
+                .collect();
+
+            self.trace.add_sample(sample);
+            self.cycle += 1;
+        }
+    }
+
+    /// Steps until `condition` over probe names holds, at most `max_cycles`
+    /// times. Returns whether the condition was met.
+    pub fn run_until(&mut self, condition: &str, max_cycles: usize) -> Result<bool, String> {
+        let canvas_probes: Vec<CanvasProbe> = self.probes.iter().map(|(probe, _)| probe.clone()).collect();
+        let condition = Condition::parse(condition, &canvas_probes, &self.circuits)?;
+
+        for _ in 0..max_cycles {
+            self.step(1);
+
+            if condition.holds(&self.circuits) {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Value and bit width of a probe or a labelled component, see [`read_signal`].
+    pub fn peek(&self, name: &str) -> Result<(Value, u8), String> {
+        read_signal(name, self.probes.iter().map(|(probe, _)| probe), &self.circuits)
+    }
+
+    /// Sets input pins or buttons by label or UUID like stimulus files do.
+    pub fn poke(&mut self, name: &str, value: u32) -> Result<(), String> {
+        for (circuit_idx, component_idx) in find_inputs(name, self.top_circuit, &self.circuits)? {
+            poke_input(circuit_idx, component_idx, value, &self.circuits);
+        }
+
+        Ok(())
+    }
+
+    pub fn list(&self) -> Vec<(String, String)> {
+        list_signals(self.top_circuit, self.probes.iter().map(|(probe, _)| probe), &self.circuits)
+    }
+
+    /// Propagates every circuit without ticking the clock.
+    pub fn propagate(&mut self) {
+        self.circuits.propagate_all();
+    }
+
+    /// Returns the circuit to its power-on state and clears the trace.
+    pub fn reset(&mut self) {
+        self.circuits.reset();
+        self.trace.clear_traces();
+        self.cycle = 0;
+    }
+
+    /// Saves the recorded trace as VCD by the extension, or as CSV.
+    pub fn save_trace<P>(&self, path: P) -> Result<(), Box<dyn Error>>
+        where
+            P: AsRef<Path>
+    {
+        let is_vcd = path.as_ref().extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("vcd"));
+
+        if is_vcd {
+            let signals: Vec<VcdSignal> = self.probes.iter()
+                .map(|(CanvasProbe { probe, .. }, trace_idx)| probe.to_vcd_signal(&probe.name, *trace_idx, &self.circuits))
+                .collect();
+
+            save_vcd(path, &signals, &self.trace)?;
+        } else {
+            save_trace_csv(path, &self.probes, &self.trace);
+        }
+
+        Ok(())
+    }
+
+    /// Runs one shell command and returns its output.
+    pub fn execute(&mut self, line: &str) -> Result<String, String> {
+        let (command, arguments) = line.trim().split_once(char::is_whitespace)
+            .map(|(command, arguments)| (command, arguments.trim()))
+            .unwrap_or((line.trim(), ""));
+
+        match command {
+            "step" => {
+                let cycles = match arguments {
+                    "" => 1,
+                    cycles => cycles.parse().map_err(|_| format!("Invalid number of cycles {}", cycles))?,
+                };
+
+                self.step(cycles);
+                Ok(format!("cycle {}", self.cycle))
+            }
+            "peek" => {
+                let (value, bit_width) = self.peek(arguments)?;
+                Ok(format!("{} = {}", arguments, value.to_bit_string(bit_width)))
+            }
+            "poke" => {
+                let Some((name, value)) = arguments.split_once(char::is_whitespace) else {
+                    return Err("Usage: poke <input> <value>".to_string());
+                };
+
+                let value = parse_number(value.trim()).ok_or(format!("Invalid value {}", value.trim()))?;
+                self.poke(name, value)?;
+                Ok(format!("{} = {}", name, value))
+            }
+            "list" => {
+                let lines: Vec<String> = self.list().into_iter()
+                    .map(|(name, value)| format!("{} = {}", name, value))
+                    .collect();
+
+                Ok(lines.join("\n"))
+            }
+            "reset" => {
+                self.reset();
+                Ok(format!("cycle {}", self.cycle))
+            }
+            "save-trace" => {
+                if arguments.is_empty() {
+                    return Err("Usage: save-trace <path>".to_string());
+                }
+
+                self.save_trace(PathBuf::from(arguments)).map_err(|error| error.to_string())?;
+                Ok(format!("saved {}", arguments))
+            }
+            "run-until" => {
+                if arguments.is_empty() {
+                    return Err("Usage: run-until <condition>".to_string());
+                }
+
+                let start = self.cycle;
+                match self.run_until(arguments, MAX_RUN_UNTIL_CYCLES)? {
+                    true => Ok(format!("cycle {}", self.cycle)),
+                    false => Err(format!("Condition not met in {} cycles", self.cycle - start)),
+                }
+            }
+            "help" => Ok(HELP.to_string()),
+            command => Err(format!("Unknown command {}, try help", command)),
+        }
+    }
+}
+
+/// Value and bit width of a probe, or of the component labelled by the
+/// last segment of an instance path like `adder/sum`, where `adder` is the
+/// label or UUID of a subcircuit component in the top circuit.
+pub fn read_signal<'a, I>(name: &str, probes: I, circuits: &InstantiatedCircuits) -> Result<(Value, u8), String>
+    where
+        I: IntoIterator<Item = &'a CanvasProbe>
+{
+    if let Some(CanvasProbe { probe, .. }) = probes.into_iter().find(|CanvasProbe { probe, .. }| probe.name == name) {
+        let (circuit, _) = &circuits.instantiated_circuits[probe.circuit];
+        return Ok((circuit.wires[probe.wire].value.get(), probe.get_bit_width(circuits)));
+    }
+
+    let (instance, label) = match name.rsplit_once('/') {
+        Some((instance, label)) => (instance.split('/').collect(), label),
+        None => (vec![], name),
+    };
+
+    let circuit_idx = (0..circuits.instantiated_circuits.len())
+        .find(|&idx| circuits.get_instance_labels(idx)[1..] == instance[..])
+        .ok_or(format!("There is no probe or subcircuit path {}", name))?;
+
+    let (circuit, _) = &circuits.instantiated_circuits[circuit_idx];
+    let component = circuit.components.iter()
+        .filter(|component| !matches!(component.model, ComponentModel::Subcircuit(_)))
+        .find(|component| component.get_properties().get_string("label").as_deref() == Some(label))
+        .ok_or(format!("There is no component labelled {} in {}", label, circuits.get_instance_labels(circuit_idx).join("/")))?;
+
+    let pin = component.get_pins().first()
+        .ok_or(format!("Component {} has no pins", name))?;
+
+    Ok((pin.value.get(), pin.bit_width))
+}
+
+/// Probes and labelled input and output pins of the top circuit with
+/// their current values.
+pub fn list_signals<'a, I>(top_circuit: CircuitIdx, probes: I, circuits: &InstantiatedCircuits) -> Vec<(String, String)>
+    where
+        I: IntoIterator<Item = &'a CanvasProbe>
+{
+    let probes = probes.into_iter()
+        .map(|CanvasProbe { probe, .. }| {
+            let (circuit, _) = &circuits.instantiated_circuits[probe.circuit];
+            let value = circuit.wires[probe.wire].value.get();
+            (format!("probe {}", probe.name), value.to_bit_string(probe.get_bit_width(circuits)))
+        });
+
+    let (top_circuit, _) = &circuits.instantiated_circuits[top_circuit];
+    let pins = top_circuit.components.iter()
+        .filter_map(|component| {
+            let kind = match component.model {
+                ComponentModel::InputPin(_) => "input",
+                ComponentModel::OutputPin(_) => "output",
+                _ => return None,
+            };
+
+            let label = component.get_properties().get_string("label")?;
+            let pin = &component.get_pins()[0];
+
+            Some((format!("{} {}", kind, label), pin.value.get().to_bit_string(pin.bit_width)))
+        });
+
+    probes.chain(pins).collect()
+}
+
+const HELP: &str = "\
+step [N]              tick the clock N times
+peek <probe|path>     print a probe or a labelled component like adder/sum
+poke <input> <value>  set an input pin by label or UUID
+list                  print probes and top circuit pins
+reset                 return to the power-on state
+save-trace <path>     save probe values as VCD or CSV
+run-until <condition> step until a condition over probes holds
+quit                  exit";
@@ -0,0 +1,32 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::core::synthesis::synthesize;
+
+#[derive(Debug)]
+pub struct SynthesizeArgs {
+    pub definition_path: PathBuf,
+    pub output_path: PathBuf,
+    pub name: String,
+}
+
+/// Builds a circuit from a behavioural definition file and saves it as a project.
+pub fn run_synthesize(args: SynthesizeArgs) -> Result<(), Box<dyn Error>> {
+    let source = fs::read_to_string(&args.definition_path)?;
+
+    let project_file = synthesize(&source, &args.name)?;
+    let circuit = &project_file.circuits[&args.name];
+
+    project_file.save(&args.output_path)?;
+
+    println!("{} {} ({} components, {} wires)",
+             "Successfully saved synthesized circuit:".green(),
+             args.output_path.display(),
+             circuit.components.len(),
+             circuit.wires.len());
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::core::analysis::vectors::run_test_vectors;
+use crate::headless::files::load_circuit;
+use crate::serde::vectors::TestVectors;
+
+#[derive(Debug)]
+pub struct VectorArgs {
+    pub circuit_path: PathBuf,
+    pub vectors_path: PathBuf,
+}
+
+/// Runs test vectors on the top circuit and returns the number of failed lines.
+pub fn run_vectors(args: VectorArgs) -> Result<usize, Box<dyn Error>> {
+    let (top_circuit_idx, circuits) = load_circuit(args.circuit_path)?;
+    let (top_circuit, _) = circuits.instantiated_circuits.get(top_circuit_idx).unwrap();
+
+    let vectors = TestVectors::load(&args.vectors_path)?;
+    let results = run_test_vectors(top_circuit, &vectors)?;
+
+    for result in results.iter() {
+        if result.passed() {
+            println!("{} line {}", "PASS".green(), result.line);
+            continue;
+        }
+
+        let diffs: Vec<String> = result.failures.iter()
+            .map(|failure| format!("{} expected {}, got {}", failure.name, failure.expected, failure.actual))
+            .collect();
+
+        println!("{} line {}: {}", "FAIL".red(), result.line, diffs.join(", "));
+    }
+
+    let failed = results.iter().filter(|result| !result.passed()).count();
+
+    if failed == 0 {
+        println!("{} {} {}", "All".green(), results.len(), "test vectors passed".green());
+    } else {
+        println!("{} of {} test vectors failed", failed, results.len());
+    }
+
+    Ok(failed)
+}
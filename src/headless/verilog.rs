@@ -0,0 +1,27 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::serde::project::ProjectFile;
+use crate::serde::verilog::save_verilog;
+
+#[derive(Debug)]
+pub struct ExportArgs {
+    pub circuit_path: PathBuf,
+    pub output_path: PathBuf,
+}
+
+/// Exports every circuit of a project as a structural Verilog module.
+pub fn run_export(args: ExportArgs) -> Result<(), Box<dyn Error>> {
+    let project_file = ProjectFile::load(&args.circuit_path)?;
+
+    save_verilog(&args.output_path, &project_file)?;
+
+    println!("{} {} ({} modules)",
+             "Successfully saved Verilog file:".green(),
+             args.output_path.display(),
+             project_file.circuits.len());
+
+    Ok(())
+}
@@ -7,4 +7,5 @@ pub mod logisim;
 pub mod editor;
 pub mod player;
 pub mod serde;
-pub mod headless;
\ No newline at end of file
+pub mod headless;
+pub mod remote;
\ No newline at end of file
@@ -1,10 +1,11 @@
 use crate::core::canvas::location::Location;
 use crate::core::simulation::pin::Direction;
+use crate::logisim::converter::error::ConvertError;
 use crate::logisim::parser::circuit::LogisimCircuit;
 use crate::logisim::parser::project::LogisimProject;
 use crate::serde::project::{ProjectFile, SavedCircuit, SavedCircuitBounds, SavedCircuitPin};
 
-pub fn collect_circuits(project_file: &mut ProjectFile, logisim_project: &LogisimProject) {
+pub fn collect_circuits(project_file: &mut ProjectFile, logisim_project: &LogisimProject) -> Result<(), ConvertError> {
     for LogisimCircuit { name, appear, components, .. } in logisim_project.circuits.iter() {
         let Some(circuit_appearance) = appear else {
             project_file.circuits.insert(
@@ -46,13 +47,17 @@ pub fn collect_circuits(project_file: &mut ProjectFile, logisim_project: &Logisi
                     (port.y + (port.height / 2)) as i16,
                 ) - anchor;
 
-                let pin_component = components.iter()
+                let (pin_component, label) = components.iter()
                     .find(|comp| comp.loc == port.pin_location)
-                    .unwrap();
+                    .and_then(|comp| Some((comp, comp.get_param("label")?)))
+                    .ok_or_else(|| ConvertError::MissingPortPin {
+                        circuit: name.clone(),
+                        location: port.pin_location.into(),
+                    })?;
 
-                SavedCircuitPin {
+                Ok(SavedCircuitPin {
                     location,
-                    label: pin_component.get_param("label").unwrap().to_string(),
+                    label: label.to_string(),
                     bit_width: 1,
                     direction: {
                         if let Some("true") = pin_component.get_param("output") {
@@ -61,9 +66,9 @@ pub fn collect_circuits(project_file: &mut ProjectFile, logisim_project: &Logisi
                             Direction::Input
                         }
                     },
-                }
+                })
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         project_file.circuits.insert(
             name.clone(),
@@ -75,4 +80,6 @@ pub fn collect_circuits(project_file: &mut ProjectFile, logisim_project: &Logisi
             },
         );
     }
-}
\ No newline at end of file
+
+    Ok(())
+}
@@ -1,41 +1,58 @@
-use crate::core::simulation::component::Component;
-use crate::core::simulation::components::clock_generator::ClockGenerator;
-use crate::core::simulation::components::input::button::InputButton;
-use crate::core::simulation::components::logic::and_gate::AndGate;
-use crate::core::simulation::components::logic::not_gate::NotGate;
-use crate::core::simulation::components::logic::or_gate::OrGate;
-use crate::core::simulation::components::subcircuit::input_pin::InputPin;
-use crate::core::simulation::components::subcircuit::output_pin::OutputPin;
-use crate::core::simulation::components::tunnel::Tunnel;
+use crate::core::canvas::facing::Facing;
+use crate::core::registry::find_logisim_mapping;
+use crate::core::simulation::component::{Component, ComponentModel};
+use crate::logisim::converter::error::ConvertError;
 use crate::logisim::parser::component::LogisimComponent;
 
-pub fn convert_logisim_component(logisim_component: &LogisimComponent) -> Component {
+pub fn convert_logisim_component(circuit: &str, logisim_component: &LogisimComponent) -> Result<Component, ConvertError> {
     debug_assert!(logisim_component.lib.is_some());
 
-    match (
-        logisim_component.lib.unwrap(),
-        logisim_component.name.as_str(),
-    ) {
-        (0, "Clock") => ClockGenerator::create(),
-        (5, "Button") => InputButton::create(),
-        (1, "OR Gate") => OrGate::from_bit_width(1),
-        (1, "AND Gate") => AndGate::from_bit_width(1),
-        (1, "NOT Gate") => NotGate::from_bit_width(1),
-        (0, "Tunnel") => Tunnel::from_name_width(logisim_component.get_param("label").unwrap(), 1),
-        (0, "Pin") => {
-            let label = logisim_component.get_param("label").unwrap();
-
-            if let Some("true") = logisim_component.get_param("output") {
-                OutputPin::create(label)
-            } else {
-                InputPin::create(label)
-            }
-        }
-
-        _ => panic!(
-            "Unknown component {} from lib {}",
-            logisim_component.name,
-            logisim_component.lib.unwrap()
-        ),
+    let mut component = create_component(circuit, logisim_component)?;
+    set_facing(&mut component, logisim_component);
+
+    Ok(component)
+}
+
+/// Copies Logisim `facing` as a rotation from the way Cirquil draws the component.
+pub fn set_facing(component: &mut Component, logisim_component: &LogisimComponent) {
+    let (default, drawn) = match &component.model {
+        ComponentModel::OutputPin(_) => (Facing::East, Facing::West),
+        ComponentModel::Tunnel(_) => (Facing::West, Facing::West),
+        _ => (Facing::East, Facing::East),
+    };
+
+    let facing = logisim_component.get_param("facing")
+        .and_then(|facing| facing.parse().ok())
+        .unwrap_or(default)
+        .relative_to(drawn);
+
+    if facing != Facing::East {
+        component.set_facing(facing);
+    }
+}
+
+/// Non-empty parameter `parameter` of a component in `circuit`.
+pub fn require_param<'a>(circuit: &str, logisim_component: &'a LogisimComponent, parameter: &str) -> Result<&'a str, ConvertError> {
+    logisim_component.get_param(parameter)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| ConvertError::MissingParameter {
+            circuit: circuit.to_string(),
+            name: logisim_component.name.clone(),
+            parameter: parameter.to_string(),
+            location: logisim_component.loc.into(),
+        })
+}
+
+fn create_component(circuit: &str, logisim_component: &LogisimComponent) -> Result<Component, ConvertError> {
+    let library = logisim_component.lib.unwrap();
+
+    match find_logisim_mapping(library, logisim_component.name.as_str()) {
+        Some(mapping) => (mapping.convert)(circuit, logisim_component),
+        None => Err(ConvertError::UnknownComponent {
+            circuit: circuit.to_string(),
+            name: logisim_component.name.clone(),
+            library,
+            location: logisim_component.loc.into(),
+        }),
     }
 }
@@ -0,0 +1,58 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::core::canvas::location::Location;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConvertError {
+    /// Component without a registered Logisim mapping.
+    UnknownComponent {
+        circuit: String,
+        name: String,
+        library: u32,
+        location: Location,
+    },
+    UnknownSubcircuit {
+        circuit: String,
+        name: String,
+        location: Location,
+    },
+    /// Component without a parameter its conversion needs, like the label
+    /// of a tunnel.
+    MissingParameter {
+        circuit: String,
+        name: String,
+        parameter: String,
+        location: Location,
+    },
+    /// Port of a custom appearance without a labelled pin at its location.
+    MissingPortPin {
+        circuit: String,
+        location: Location,
+    },
+}
+
+impl Display for ConvertError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConvertError::UnknownComponent { circuit, name, library, location } => {
+                write!(f, "Circuit {}: component \"{}\" from library {} at ({}, {}) is not supported",
+                       circuit, name, library, location.x, location.y)
+            }
+            ConvertError::UnknownSubcircuit { circuit, name, location } => {
+                write!(f, "Circuit {}: subcircuit at ({}, {}) refers to unknown circuit \"{}\"",
+                       circuit, location.x, location.y, name)
+            }
+            ConvertError::MissingParameter { circuit, name, parameter, location } => {
+                write!(f, "Circuit {}: component \"{}\" at ({}, {}) has no {}",
+                       circuit, name, location.x, location.y, parameter)
+            }
+            ConvertError::MissingPortPin { circuit, location } => {
+                write!(f, "Circuit {}: appearance port at ({}, {}) has no labelled pin",
+                       circuit, location.x, location.y)
+            }
+        }
+    }
+}
+
+impl Error for ConvertError {}
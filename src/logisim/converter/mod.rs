@@ -3,20 +3,22 @@ use std::collections::HashMap;
 use crate::core::canvas::location::Location;
 use crate::core::simulation::components::subcircuit::Subcircuit;
 use crate::logisim::converter::circuit::collect_circuits;
-use crate::logisim::converter::component::convert_logisim_component;
+use crate::logisim::converter::component::{convert_logisim_component, set_facing};
+use crate::logisim::converter::error::ConvertError;
 use crate::logisim::parser::project::LogisimProject;
 use crate::serde::project::{ProjectFile, SavedComponent, SavedWire};
 
 pub mod component;
 pub mod circuit;
+pub mod error;
 
-pub fn convert_logisim_project(logisim_project: LogisimProject) -> ProjectFile {
+pub fn convert_logisim_project(logisim_project: LogisimProject) -> Result<ProjectFile, ConvertError> {
     let mut project_file = ProjectFile {
         top_circuit: logisim_project.top_circuit.name.clone(),
         circuits: HashMap::new(),
     };
 
-    collect_circuits(&mut project_file, &logisim_project);
+    collect_circuits(&mut project_file, &logisim_project)?;
 
     for circuit in logisim_project.circuits {
         let name = circuit.name;
@@ -35,19 +37,28 @@ pub fn convert_logisim_project(logisim_project: LogisimProject) -> ProjectFile {
             .into_iter()
             .map(|x| {
                 let component = if x.lib.is_some() {
-                    convert_logisim_component(&x)
+                    convert_logisim_component(&name, &x)?
                 } else {
-                    let subcircuit = project_file.circuits.get(x.name.as_str()).unwrap();
+                    let Some(subcircuit) = project_file.circuits.get(x.name.as_str()) else {
+                        return Err(ConvertError::UnknownSubcircuit {
+                            circuit: name.clone(),
+                            name: x.name.clone(),
+                            location: x.loc.into(),
+                        });
+                    };
 
-                    Subcircuit::from_saved_circuit(subcircuit, x.name.as_str())
+                    let mut component = Subcircuit::from_saved_circuit(subcircuit, x.name.as_str());
+                    set_facing(&mut component, &x);
+
+                    component
                 };
 
-                SavedComponent {
+                Ok(SavedComponent {
                     location: Location::from(x.loc),
                     component,
-                }
+                })
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         let project_circuit = project_file.circuits.get_mut(name.as_str()).unwrap();
 
@@ -55,5 +66,5 @@ pub fn convert_logisim_project(logisim_project: LogisimProject) -> ProjectFile {
         project_circuit.wires = wires;
     }
 
-    project_file
+    Ok(project_file)
 }
\ No newline at end of file
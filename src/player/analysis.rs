@@ -0,0 +1,94 @@
+use std::fs;
+use std::path::PathBuf;
+
+use egui::{Grid, RichText, ScrollArea, Ui};
+
+use crate::core::analysis::{analyze_circuit, AnalysisError, DEFAULT_MAX_INPUT_BITS, TruthTable};
+use crate::core::compiler::project::InstantiatedCircuits;
+use crate::core::expression::Expr;
+use crate::core::simulation::circuit::CircuitIdx;
+
+pub struct Analysis {
+    pub circuit: CircuitIdx,
+    pub name: String,
+    pub result: Result<TruthTable, AnalysisError>,
+    expressions: Vec<(String, Expr)>,
+}
+
+impl Analysis {
+    pub fn new(circuits: &InstantiatedCircuits, circuit: CircuitIdx) -> Self {
+        let result = analyze_circuit(&circuits.instantiated_circuits[circuit].0, DEFAULT_MAX_INPUT_BITS);
+        let expressions = result.as_ref()
+            .map(|table| table.get_expressions())
+            .unwrap_or_default();
+
+        Analysis { circuit, name: circuits.get_instance_name(circuit), result, expressions }
+    }
+}
+
+pub fn draw_analysis(ui: &mut Ui, analysis: &Analysis) {
+    ui.heading(analysis.name.as_str());
+
+    let table = match &analysis.result {
+        Ok(table) => table,
+        Err(error) => {
+            ui.label(error.to_string());
+            return;
+        }
+    };
+
+    ui.horizontal(|ui| {
+        if ui.button("Export CSV").clicked() {
+            if let Some(path) = show_save_analysis_file_dialog("CSV file", "csv") {
+                save_export(path, table.to_csv());
+            }
+        }
+
+        if ui.button("Export Markdown").clicked() {
+            if let Some(path) = show_save_analysis_file_dialog("Markdown file", "md") {
+                save_export(path, table.to_markdown());
+            }
+        }
+    });
+
+    ui.separator();
+
+    for (name, expression) in analysis.expressions.iter() {
+        ui.label(RichText::new(format!("{} = {}", name, expression)).monospace());
+    }
+
+    ui.separator();
+
+    ScrollArea::vertical().id_source("analysis_scroll").show(ui, |ui| {
+        Grid::new("analysis_table").striped(true).show(ui, |ui| {
+            for port in table.inputs.iter().chain(table.outputs.iter()) {
+                ui.label(RichText::new(port.name.as_str()).monospace().strong());
+            }
+            ui.end_row();
+
+            for row in 0..table.rows.len() {
+                let inputs = table.get_row_inputs(row).into_iter().zip(table.inputs.iter())
+                    .map(|(value, port)| format!("{:0width$b}", value, width = port.bit_width as usize));
+                let outputs = table.rows[row].iter().zip(table.outputs.iter())
+                    .map(|(value, port)| value.to_bit_string(port.bit_width));
+
+                for cell in inputs.chain(outputs) {
+                    ui.label(RichText::new(cell).monospace());
+                }
+                ui.end_row();
+            }
+        });
+    });
+}
+
+fn save_export(path: PathBuf, contents: String) {
+    if let Err(error) = fs::write(&path, contents) {
+        eprintln!("Failed to export analysis to {}: {}", path.display(), error);
+    }
+}
+
+fn show_save_analysis_file_dialog(name: &str, extension: &str) -> Option<PathBuf> {
+    rfd::FileDialog::new()
+        .add_filter(name, vec![extension].as_slice())
+        .save_file()
+}
@@ -6,32 +6,42 @@ use std::time::{Duration, Instant};
 
 use eframe::epaint::Shape;
 use eframe::Frame;
-use egui::{Button, containers, Context, Pos2, ScrollArea, Sense, Separator, Slider, Stroke, Ui, Vec2, Vec2b};
+use egui::{Button, Color32, containers, Context, Painter, Pos2, ScrollArea, Sense, Separator, Slider, Stroke, Ui, Vec2, Vec2b};
 use egui::collapsing_header::CollapsingState;
 use egui_extras::{Size, StripBuilder};
 
 use crate::core::canvas::circuit::CanvasCircuit;
+use crate::core::compiler::drc::Diagnostic;
 use crate::core::compiler::project::{InstantiatedCircuits, SimulationTreeNode};
+use crate::core::simulation::assertion::{Assertion, AssertionFailure};
 use crate::core::simulation::circuit::{Circuit, CircuitIdx};
 use crate::core::simulation::probe::{CanvasProbe, Probe};
+use crate::core::simulation::wire::WireIdx;
 use crate::gui::component::AsShapes;
 use crate::gui::constants::GRID_STEP;
 use crate::gui::grid;
 use crate::gui::value::get_value_color;
+use crate::player::analysis::{Analysis, draw_analysis};
 use crate::player::circuit::{CircuitManager, PlaybackType};
 use crate::player::clock::{ClockState, SimulationTicker};
 use crate::player::file::OpenedFile;
 use crate::player::instrument::Instrument;
+use crate::player::assertions::draw_assertions;
 use crate::player::osc::{draw_osc, Oscilloscope};
 use crate::player::probe_location::place_new_probe;
 use crate::player::project::{show_load_logisim_file_dialog, show_load_project_file_dialog, show_save_project_file_dialog};
 use crate::player::replay::{ReplayManager, show_load_replay_file_dialogue, show_save_replay_file_dialogue};
+use crate::player::warnings::{draw_warnings, Highlight};
 use crate::player::workbench::{show_load_workbench_file_dialogue, show_save_workbench_file_dialogue};
+use crate::remote::RemoteHandler;
+use crate::remote::server::RemoteServer;
 
 const _GRID_SQUARE: Vec2 = Vec2::new(GRID_STEP, GRID_STEP);
 
 const BUTTON_SIZE: Vec2 = Vec2::new(40.0, 40.0);
 
+const REMOTE_POLL_PERIOD: Duration = Duration::from_millis(20);
+
 pub struct CirquilPlayerApp {
     pub circuit_manager: CircuitManager,
     pub current_circuit: CircuitIdx,
@@ -47,8 +57,20 @@ pub struct CirquilPlayerApp {
     pub current_instrument: Instrument,
     pub osc: Oscilloscope,
     pub failed_probe_errors: Option<Vec<String>>,
+    pub project_error: Option<String>,
     pub replay_manager: ReplayManager,
     pub target_replay_frame: usize,
+    pub warnings_visible: bool,
+    pub highlighted: Option<(CircuitIdx, Highlight)>,
+    pub diagnostics: Vec<Diagnostic>,
+    pub analysis: Option<Analysis>,
+    pub analysis_visible: bool,
+    pub assertions: Vec<Assertion>,
+    pub assertion_failures: Vec<AssertionFailure>,
+    pub assertions_visible: bool,
+    /// Cycles simulated since the last reset.
+    pub cycle: usize,
+    pub remote: Option<(RemoteServer, RemoteHandler)>,
 }
 
 impl CirquilPlayerApp {
@@ -93,8 +115,19 @@ impl CirquilPlayerApp {
             current_instrument: Instrument::None,
             osc: Oscilloscope::default(),
             failed_probe_errors: None,
+            project_error: None,
             replay_manager: ReplayManager::default(),
             target_replay_frame: 0,
+            warnings_visible: false,
+            highlighted: None,
+            diagnostics: vec![],
+            analysis: None,
+            analysis_visible: false,
+            assertions: vec![],
+            assertion_failures: vec![],
+            assertions_visible: false,
+            cycle: 0,
+            remote: None,
         }
     }
 }
@@ -116,13 +149,25 @@ impl eframe::App for CirquilPlayerApp {
         }
 
         if let Some(path) = self.project_file.check_load() {
-            self.load_project(path).unwrap();
+            if let Err(error) = self.load_project(path) {
+                self.project_error = Some(error.to_string());
+            }
         }
 
         if let Some(path) = self.workbench_file.check_load() {
             self.failed_probe_errors = self.load_workbench(path);
         }
 
+        // Shutdown requests stop remote control by dropping the server, the
+        // player keeps running
+        if let Some((server, mut handler)) = self.remote.take() {
+            if !handler.poll(self, &server) {
+                self.remote = Some((server, handler));
+            }
+
+            ctx.request_repaint_after(REMOTE_POLL_PERIOD);
+        }
+
         let (top_circuit, _) = self.circuit_manager.get_circuits().instantiated_circuits.get(self.top_circuit).unwrap();
 
         if self.simulation_ticker.check_tick_needed() {
@@ -136,6 +181,7 @@ impl eframe::App for CirquilPlayerApp {
 
             if self.circuit_manager.playback_type.is_simulation() {
                 self.tick(top_circuit);
+                self.check_assertions();
             }
 
             if let PlaybackType::Replay(_, frame) = &self.circuit_manager.playback_type {
@@ -160,7 +206,7 @@ impl eframe::App for CirquilPlayerApp {
                 .resizable(false)
                 .collapsible(false)
                 .show(ctx, |ui| {
-                    ui.heading("Some probes loaded with errors: ");
+                    ui.heading("Some probes or assertions loaded with errors: ");
 
                     for error in failed_probe_errors {
                         ui.label((*error).as_str());
@@ -178,6 +224,30 @@ impl eframe::App for CirquilPlayerApp {
             }
         }
 
+        if let Some(project_error) = &self.project_error {
+            let mut should_clear_error = false;
+
+            egui::Window::new("Project Errors")
+                .min_width(500.0)
+                .resizable(false)
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    ui.heading("Project could not be loaded: ");
+
+                    ui.label(project_error.as_str());
+
+                    ui.separator();
+
+                    if ui.button("Ok").clicked() {
+                        should_clear_error = true;
+                    }
+                });
+
+            if should_clear_error {
+                self.project_error = None;
+            }
+        }
+
         egui::TopBottomPanel::top("menu_panel").exact_height(20.0).show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
                 ui.menu_button("File", |ui| {
@@ -189,6 +259,14 @@ impl eframe::App for CirquilPlayerApp {
                         ui.close_menu()
                     }
 
+                    if ui.add_enabled(self.project_file.current_file.is_some(), Button::new("Reload project")).clicked() {
+                        if let Some(path) = self.project_file.current_file.clone() {
+                            self.project_file.request_open(path);
+                        }
+
+                        ui.close_menu();
+                    }
+
                     if ui.button("Convert .circ to .cirq").clicked() {
                         if let Some(logisim_path) = show_load_logisim_file_dialog() {
                             if let Some(cirq_path) = show_save_project_file_dialog() {
@@ -253,8 +331,9 @@ impl eframe::App for CirquilPlayerApp {
 
                     ui.add(Separator::default().vertical());
 
-                    if ui.add(Button::new("Reset circuit").min_size(BUTTON_SIZE)).clicked() && self.project_file.current_file.is_some() {
-                        self.project_file.request_open(self.project_file.current_file.clone().unwrap());
+                    if ui.add_enabled(self.circuit_manager.playback_type.is_simulation(), Button::new("Reset circuit").min_size(BUTTON_SIZE)).clicked() {
+                        self.circuit_manager.get_circuits().reset();
+                        self.reset_assertions();
                     }
 
                     ui.add(Separator::default().vertical());
@@ -306,6 +385,32 @@ impl eframe::App for CirquilPlayerApp {
                     if ui.add(Button::new("Osc").min_size(BUTTON_SIZE)).clicked() {
                         self.osc_visible = !self.osc_visible;
                     }
+
+                    let warnings_count = self.circuit_manager.get_circuits().get_contentions().len() + self.diagnostics.len();
+                    let warnings_label = if warnings_count == 0 {
+                        "Warnings".to_string()
+                    } else {
+                        format!("Warnings ({})", warnings_count)
+                    };
+
+                    if ui.add(Button::new(warnings_label).min_size(BUTTON_SIZE).selected(self.warnings_visible)).clicked() {
+                        self.warnings_visible = !self.warnings_visible;
+                    }
+
+                    let assertions_label = if self.assertion_failures.is_empty() {
+                        "Assertions".to_string()
+                    } else {
+                        format!("Assertions ({})", self.assertion_failures.len())
+                    };
+
+                    if ui.add(Button::new(assertions_label).min_size(BUTTON_SIZE).selected(self.assertions_visible)).clicked() {
+                        self.assertions_visible = !self.assertions_visible;
+                    }
+
+                    if ui.add_enabled(self.circuit_manager.playback_type.is_simulation(), Button::new("Analyze").min_size(BUTTON_SIZE)).clicked() {
+                        self.analysis = Some(Analysis::new(self.circuit_manager.get_circuits(), self.current_circuit));
+                        self.analysis_visible = true;
+                    }
                 })
             })
         });
@@ -358,10 +463,37 @@ impl eframe::App for CirquilPlayerApp {
                 .min_size(Vec2::new(600.0, 300.0))
                 .max_size(Vec2::new(1400.0, 600.0))
                 .open(&mut self.osc_visible)
-                .show(ctx, |ui| draw_osc(ui, &mut self.osc, self.probes.as_slice()));
+                .show(ctx, |ui| draw_osc(ui, &mut self.osc, self.probes.as_slice(), self.circuit_manager.get_circuits()));
+
+            if let Some(analysis) = &self.analysis {
+                egui::Window::new("Combinational Analysis")
+                    .min_width(300.0)
+                    .open(&mut self.analysis_visible)
+                    .show(ctx, |ui| draw_analysis(ui, analysis));
+            }
+
+            egui::Window::new("Assertions")
+                .min_width(400.0)
+                .open(&mut self.assertions_visible)
+                .show(ctx, |ui| draw_assertions(ui, &self.assertions, &mut self.assertion_failures));
+
+            let mut located = None;
+            egui::Window::new("Warnings")
+                .min_width(400.0)
+                .open(&mut self.warnings_visible)
+                .show(ctx, |ui| located = draw_warnings(ui, self.circuit_manager.get_circuits(), &self.diagnostics));
+
+            if let Some((circuit, highlight)) = located {
+                self.current_circuit = circuit;
+                self.highlighted = Some((circuit, highlight));
+            }
+
+            let highlighted = self.highlighted
+                .filter(|(circuit, _)| *circuit == self.current_circuit)
+                .map(|(_, highlight)| highlight);
 
             ScrollArea::both().id_source("canvas_scroll").show(ui, |ui| {
-                containers::Frame::canvas(ui.style()).show(ui, |ui| draw_canvas(ui, ctx, self.current_circuit, self.circuit_manager.get_circuits(), &mut self.probes, &mut self.probe_max_id, &self.current_instrument));
+                containers::Frame::canvas(ui.style()).show(ui, |ui| draw_canvas(ui, ctx, self.current_circuit, self.circuit_manager.get_circuits(), &mut self.probes, &mut self.probe_max_id, &self.current_instrument, highlighted));
             });
         });
     }
@@ -420,6 +552,26 @@ fn traverse_simulation_tree(ui: &mut Ui, node: &SimulationTreeNode, circuits: &I
     clicked_circuit
 }
 
+fn draw_wire_highlight(painter: &Painter, coords: Vec2, canvas: &CanvasCircuit, circuit: &Circuit, wire_idx: WireIdx) {
+    let stroke = Stroke::new(8.0, Color32::from_rgba_unmultiplied(255, 140, 0, 160));
+
+    if let Some(canvas_wire) = canvas.wires.iter().find(|w| w.wire == wire_idx) {
+        for (s, e) in canvas_wire.segments.iter() {
+            painter.line_segment([Pos2::from(*s) + coords, Pos2::from(*e) + coords], stroke);
+        }
+    }
+
+    for (component_idx, pin_idx) in circuit.get_wire(wire_idx).connected_components.iter() {
+        let Some(canvas_component) = canvas.components.iter().find(|c| c.component == *component_idx) else { continue; };
+        let pin = &circuit.get_component(*component_idx).get_pins()[*pin_idx];
+
+        painter.circle_stroke(
+            Pos2::from(canvas_component.loc + pin.location) + coords, 6.0,
+            stroke,
+        );
+    }
+}
+
 fn calculate_canvas_bounds(canvas: &CanvasCircuit) -> Vec2 {
     let max_component_x = canvas.components.iter()
         .max_by(|a, b| a.loc.x.cmp(&b.loc.x))
@@ -436,7 +588,8 @@ fn calculate_canvas_bounds(canvas: &CanvasCircuit) -> Vec2 {
     Vec2::new(max_coord as f32, max_coord as f32)
 }
 
-fn draw_canvas(ui: &mut Ui, ctx: &Context, current_circuit: CircuitIdx, instantiated_circuits: &InstantiatedCircuits, probes: &mut Vec<CanvasProbe>, probe_id: &mut usize, current_instrument: &Instrument) {
+#[allow(clippy::too_many_arguments)]
+fn draw_canvas(ui: &mut Ui, ctx: &Context, current_circuit: CircuitIdx, instantiated_circuits: &InstantiatedCircuits, probes: &mut Vec<CanvasProbe>, probe_id: &mut usize, current_instrument: &Instrument, highlighted: Option<Highlight>) {
     let (circuit, canvas_idx) = instantiated_circuits.instantiated_circuits.get(current_circuit).unwrap();
     let canvas = instantiated_circuits.canvas_circuits.get(*canvas_idx).unwrap();
 
@@ -448,6 +601,19 @@ fn draw_canvas(ui: &mut Ui, ctx: &Context, current_circuit: CircuitIdx, instanti
     grid::draw(&response.rect, &painter);
     let coords = response.rect.min.to_vec2();
 
+    match highlighted {
+        Some(Highlight::Wire(wire_idx)) => {
+            draw_wire_highlight(&painter, coords, canvas, circuit, wire_idx);
+        }
+        Some(Highlight::Location(location)) => {
+            painter.circle_stroke(
+                Pos2::from(location) + coords, 10.0,
+                Stroke::new(4.0, Color32::from_rgba_unmultiplied(255, 140, 0, 160)),
+            );
+        }
+        None => {}
+    }
+
     for canvas_wire in canvas.wires.iter() {
         let wire = circuit.get_wire(canvas_wire.wire);
 
@@ -0,0 +1,50 @@
+use egui::{Color32, RichText, ScrollArea, Ui};
+
+use crate::core::simulation::assertion::{Assertion, AssertionFailure};
+use crate::player::CirquilPlayerApp;
+
+impl CirquilPlayerApp {
+    /// Checks workbench assertions after a simulated cycle.
+    pub fn check_assertions(&mut self) {
+        let circuits = self.circuit_manager.get_circuits();
+
+        for assertion in self.assertions.iter_mut() {
+            if let Some(failure) = assertion.check(self.cycle, circuits) {
+                self.assertion_failures.push(failure);
+            }
+        }
+
+        self.cycle += 1;
+    }
+
+    pub fn reset_assertions(&mut self) {
+        self.assertions.iter_mut().for_each(|assertion| assertion.reset());
+        self.cycle = 0;
+    }
+}
+
+pub fn draw_assertions(ui: &mut Ui, assertions: &[Assertion], failures: &mut Vec<AssertionFailure>) {
+    ui.horizontal(|ui| {
+        ui.label(format!("{} assertions, {} failures", assertions.len(), failures.len()));
+
+        if ui.button("Clear").clicked() {
+            failures.clear();
+        }
+    });
+
+    ui.separator();
+
+    for assertion in assertions.iter() {
+        ui.label(RichText::new(format!("{}: {}", assertion.saved.name, assertion.saved.condition)).monospace());
+    }
+
+    ui.separator();
+
+    ScrollArea::vertical().id_source("assertions_scroll").show(ui, |ui| {
+        for failure in failures.iter() {
+            ui.label(RichText::new(format!("[cycle {}] {} failed: {}", failure.cycle, failure.name, failure.condition))
+                .monospace()
+                .color(Color32::RED));
+        }
+    });
+}
@@ -72,7 +72,7 @@ impl CircuitManager {
         Self {
             circuits: InstantiatedCircuits {
                 canvas_circuits: replay_file.canvas_circuits.clone(),
-                instantiated_circuits: Self::wrap_in_rc(replay_file.states.get(0).unwrap()),
+                instantiated_circuits: Self::wrap_in_rc(replay_file.states.first().unwrap()),
                 simulation_tree: replay_file.simulation_tree.clone(),
                 by_uuid: replay_file.by_uuid.clone(),
                 parents: replay_file.parents.clone(),
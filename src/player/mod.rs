@@ -3,6 +3,9 @@ use std::path::PathBuf;
 use eframe::Error;
 use egui::{Style, Visuals};
 
+use crate::remote::RemoteHandler;
+use crate::remote::server::RemoteServer;
+
 pub use app::CirquilPlayerApp;
 
 pub mod app;
@@ -16,8 +19,14 @@ pub mod replay;
 mod circuit;
 pub mod probe_location;
 mod csv;
+mod vcd;
+mod warnings;
+mod analysis;
+mod assertions;
+mod remote;
 
-pub fn run_player_gui(initial_project_file: Option<PathBuf>, initial_workbench_file: Option<PathBuf>) -> Result<(), Error> {
+/// Answers remote control requests of `remote` between frames when given.
+pub fn run_player_gui(initial_project_file: Option<PathBuf>, initial_workbench_file: Option<PathBuf>, remote: Option<RemoteServer>) -> Result<(), Error> {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default().with_inner_size([1000.0, 600.0]),
         ..Default::default()
@@ -33,7 +42,10 @@ pub fn run_player_gui(initial_project_file: Option<PathBuf>, initial_workbench_f
             };
             cc.egui_ctx.set_style(style);
 
-            Box::new(CirquilPlayerApp::from_file_options(initial_project_file, initial_workbench_file))
+            let mut app = CirquilPlayerApp::from_file_options(initial_project_file, initial_workbench_file);
+            app.remote = remote.map(|server| (server, RemoteHandler::default()));
+
+            Box::new(app)
         }),
     )
 }
@@ -9,6 +9,7 @@ use crate::core::simulation::trace::Trace;
 use crate::core::simulation::value::Value;
 use crate::gui::value::get_value_color;
 use crate::player::csv::{save_csv_from_oscilloscope, show_save_csv_file_dialog};
+use crate::player::vcd::{save_vcd_from_oscilloscope, show_save_vcd_file_dialog};
 
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub enum TriggerType {
@@ -89,7 +90,7 @@ impl Oscilloscope {
     }
 }
 
-pub fn draw_osc(ui: &mut Ui, osc: &mut Oscilloscope, probes: &[CanvasProbe]) {
+pub fn draw_osc(ui: &mut Ui, osc: &mut Oscilloscope, probes: &[CanvasProbe], circuits: &InstantiatedCircuits) {
     egui::menu::bar(ui, |ui| {
         ui.menu_button("File", |ui| {
             if ui.button("Save CSV").clicked() {
@@ -100,6 +101,14 @@ pub fn draw_osc(ui: &mut Ui, osc: &mut Oscilloscope, probes: &[CanvasProbe]) {
                 ui.close_menu();
             }
 
+            if ui.button("Save VCD").clicked() {
+                if let Some(path) = show_save_vcd_file_dialog() {
+                    save_vcd_from_oscilloscope(path, osc, probes, circuits);
+                }
+
+                ui.close_menu();
+            }
+
             ui.separator();
 
             if ui.button("Clear traces").clicked() {
@@ -3,8 +3,11 @@ use std::fmt::{Debug, Display, Formatter};
 use std::io::Error as StdIoError;
 use std::path::{Path, PathBuf};
 
+use crate::core::compiler::drc::check_project;
+use crate::core::compiler::error::CompileError;
 use crate::core::compiler::project::compile_project;
 use crate::logisim::converter::convert_logisim_project;
+use crate::logisim::converter::error::ConvertError;
 use crate::logisim::parser::parse_logisim;
 use crate::player::circuit::CircuitManager;
 use crate::player::CirquilPlayerApp;
@@ -15,7 +18,8 @@ use crate::serde::project::ProjectFile;
 pub enum LoadErrorKind {
     UnknownFileType,
     IoError(StdIoError),
-    ConvertError,
+    ConvertError(ConvertError),
+    CompileError(CompileError),
     UnknownError,
 }
 
@@ -26,7 +30,17 @@ pub struct ProjectLoadError {
 
 impl Display for ProjectLoadError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str(format!("Can't load project {:?}", self.kind).as_str())
+        match &self.kind {
+            LoadErrorKind::ConvertError(error) => {
+                f.write_str(format!("Can't convert Logisim project: {}", error).as_str())
+            }
+            LoadErrorKind::CompileError(error) => {
+                f.write_str(format!("Can't compile project: {}", error).as_str())
+            }
+            kind => {
+                f.write_str(format!("Can't load project {:?}", kind).as_str())
+            }
+        }
     }
 }
 
@@ -38,6 +52,22 @@ impl From<LoadErrorKind> for ProjectLoadError {
     }
 }
 
+impl From<CompileError> for ProjectLoadError {
+    fn from(value: CompileError) -> Self {
+        ProjectLoadError {
+            kind: LoadErrorKind::CompileError(value)
+        }
+    }
+}
+
+impl From<ConvertError> for ProjectLoadError {
+    fn from(value: ConvertError) -> Self {
+        ProjectLoadError {
+            kind: LoadErrorKind::ConvertError(value)
+        }
+    }
+}
+
 impl From<StdIoError> for ProjectLoadError {
     fn from(value: StdIoError) -> Self {
         ProjectLoadError {
@@ -61,7 +91,7 @@ fn load_from_file<P>(path: P) -> Result<ProjectFile, ProjectLoadError>
                     let logisim_project = parse_logisim(path)
                         .map_err(|_| ProjectLoadError::from(LoadErrorKind::UnknownError))?;
 
-                    Ok(convert_logisim_project(logisim_project))
+                    Ok(convert_logisim_project(logisim_project)?)
                 }
                 Some(_) => { Err(ProjectLoadError::from(LoadErrorKind::UnknownFileType)) }
                 None => { Err(ProjectLoadError::from(LoadErrorKind::UnknownError)) }
@@ -77,17 +107,21 @@ impl CirquilPlayerApp {
     {
         let project_file = load_from_file(path)?;
 
-        let (top_circuit, compiled_circuits) = compile_project(project_file);
+        let (top_circuit, compiled_circuits) = compile_project(project_file.clone())?;
+
+        compiled_circuits.reset();
 
-        compiled_circuits.instantiated_circuits.iter().for_each(
-            |(circuit, _)| circuit.propagate_all()
-        );
+        self.diagnostics = check_project(&project_file, &compiled_circuits);
 
         self.circuit_manager = CircuitManager::create_simulation(compiled_circuits);
         self.top_circuit = top_circuit;
         self.current_circuit = top_circuit;
         self.probes = vec![];
+        self.assertions = vec![];
+        self.assertion_failures = vec![];
+        self.cycle = 0;
         self.probe_max_id = 0;
+        self.highlighted = None;
 
         self.osc = Oscilloscope::default();
 
@@ -0,0 +1,55 @@
+use crate::core::simulation::stimulus::{find_inputs, poke_input};
+use crate::core::simulation::value::Value;
+use crate::headless::session::{list_signals, read_signal};
+use crate::player::CirquilPlayerApp;
+use crate::remote::RemoteTarget;
+
+impl RemoteTarget for CirquilPlayerApp {
+    fn tick(&mut self) -> Result<(), String> {
+        if !self.circuit_manager.playback_type.is_simulation() {
+            return Err("Player is showing a replay".to_string());
+        }
+
+        let (top_circuit, _) = &self.circuit_manager.get_circuits().instantiated_circuits[self.top_circuit];
+        CirquilPlayerApp::tick(self, top_circuit);
+
+        self.check_assertions();
+        self.osc.collect_probe_values(self.probes.as_slice(), self.circuit_manager.get_circuits());
+
+        Ok(())
+    }
+
+    fn propagate(&mut self) {
+        self.circuit_manager.get_circuits().propagate_all();
+    }
+
+    fn reset(&mut self) {
+        self.circuit_manager.get_circuits().reset();
+        self.reset_assertions();
+    }
+
+    fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    fn read(&self, name: &str) -> Result<(Value, u8), String> {
+        read_signal(name, self.probes.iter(), self.circuit_manager.get_circuits())
+    }
+
+    fn write(&mut self, name: &str, value: u32) -> Result<(), String> {
+        if !self.circuit_manager.playback_type.is_simulation() {
+            return Err("Player is showing a replay".to_string());
+        }
+
+        let circuits = self.circuit_manager.get_circuits();
+        for (circuit_idx, component_idx) in find_inputs(name, self.top_circuit, circuits)? {
+            poke_input(circuit_idx, component_idx, value, circuits);
+        }
+
+        Ok(())
+    }
+
+    fn list(&self) -> Vec<(String, String)> {
+        list_signals(self.top_circuit, self.probes.iter(), self.circuit_manager.get_circuits())
+    }
+}
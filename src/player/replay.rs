@@ -50,6 +50,8 @@ impl CirquilPlayerApp {
         self.circuit_manager = CircuitManager::create_replay(replay_file);
         self.probes = vec![];
         self.probe_max_id = 0;
+        self.highlighted = None;
+        self.diagnostics = vec![];
 
         self.osc = Oscilloscope::default();
     }
@@ -0,0 +1,29 @@
+use std::path::{Path, PathBuf};
+
+use crate::core::compiler::project::InstantiatedCircuits;
+use crate::core::simulation::probe::CanvasProbe;
+use crate::player::osc::Oscilloscope;
+use crate::serde::vcd::{save_vcd, VcdSignal};
+
+pub fn save_vcd_from_oscilloscope<P>(path: P, osc: &Oscilloscope, probes: &[CanvasProbe], circuits: &InstantiatedCircuits)
+    where P: AsRef<Path>
+{
+    let signals: Vec<VcdSignal> = osc.rows.iter()
+        .map(|row| {
+            match probes.iter().find(|CanvasProbe { probe, .. }| probe.name == row.source) {
+                Some(CanvasProbe { probe, .. }) => probe.to_vcd_signal(&row.name, row.trace_idx, circuits),
+                None => VcdSignal { name: row.name.clone(), scope: vec![], bit_width: 1, trace_idx: row.trace_idx },
+            }
+        })
+        .collect();
+
+    if let Err(error) = save_vcd(&path, &signals, &osc.trace) {
+        eprintln!("Failed to save VCD file {}: {}", path.as_ref().display(), error);
+    }
+}
+
+pub fn show_save_vcd_file_dialog() -> Option<PathBuf> {
+    rfd::FileDialog::new()
+        .add_filter("VCD file", vec!["vcd"].as_slice())
+        .save_file()
+}
@@ -0,0 +1,65 @@
+use egui::{Color32, RichText, ScrollArea, Ui};
+
+use crate::core::canvas::location::Location;
+use crate::core::compiler::drc::{Diagnostic, Severity};
+use crate::core::compiler::project::InstantiatedCircuits;
+use crate::core::simulation::circuit::CircuitIdx;
+use crate::core::simulation::wire::WireIdx;
+
+#[derive(Debug, Copy, Clone)]
+pub enum Highlight {
+    Wire(WireIdx),
+    Location(Location),
+}
+
+pub fn draw_warnings(ui: &mut Ui, circuits: &InstantiatedCircuits, diagnostics: &[Diagnostic]) -> Option<(CircuitIdx, Highlight)> {
+    let mut located = None;
+    let contentions = circuits.get_contentions();
+
+    ui.horizontal(|ui| {
+        ui.label(format!("{} bus contentions", contentions.len()));
+
+        if ui.button("Clear").clicked() {
+            circuits.clear_contentions();
+        }
+    });
+
+    ui.separator();
+
+    ScrollArea::vertical().id_source("warnings_scroll").show(ui, |ui| {
+        for report in contentions.iter() {
+            let text = format!("[x{}] {}",
+                               report.contention.occurrences,
+                               report.describe(circuits));
+
+            if ui.selectable_label(false, RichText::new(text).monospace()).clicked() {
+                located = Some((report.circuit, Highlight::Wire(report.contention.wire)));
+            }
+        }
+
+        if !diagnostics.is_empty() {
+            ui.separator();
+            ui.label(format!("{} design check diagnostics", diagnostics.len()));
+
+            for diagnostic in diagnostics.iter() {
+                let color = match diagnostic.severity {
+                    Severity::Warning => Color32::from_rgb(160, 110, 0),
+                    Severity::Error => Color32::DARK_RED,
+                };
+
+                let text = RichText::new(diagnostic.to_string()).monospace().color(color);
+
+                if ui.selectable_label(false, text).clicked() {
+                    let instance = circuits.instantiated_circuits.iter()
+                        .position(|(_, idx)| circuits.canvas_circuits[*idx].name == diagnostic.circuit);
+
+                    if let Some(instance) = instance {
+                        located = Some((instance, Highlight::Location(diagnostic.location)));
+                    }
+                }
+            }
+        }
+    });
+
+    located
+}
@@ -1,10 +1,11 @@
 use std::path::{Path, PathBuf};
 
+use crate::core::simulation::assertion::from_saved_assertions;
 use crate::core::simulation::probe::CanvasProbe;
 use crate::core::simulation::workbench::{from_workbench_file, to_workbench_file};
 use crate::player::CirquilPlayerApp;
 use crate::serde::fs::{deserialize_from_file, serialize_to_file};
-use crate::serde::workbench::WorkbenchFile;
+use crate::serde::workbench::{SavedAssertion, WorkbenchFile};
 
 impl CirquilPlayerApp {
     pub fn load_workbench<P>(&mut self, path: P) -> Option<Vec<String>>
@@ -13,7 +14,7 @@ impl CirquilPlayerApp {
     {
         let workbench_file: WorkbenchFile = deserialize_from_file(path).unwrap();
 
-        let (probes, osc, last_probe_id) = from_workbench_file(workbench_file, self.circuit_manager.get_circuits());
+        let (probes, osc, last_probe_id, saved_assertions) = from_workbench_file(workbench_file, self.circuit_manager.get_circuits());
         self.osc = osc;
         self.probe_max_id = last_probe_id;
 
@@ -21,12 +22,17 @@ impl CirquilPlayerApp {
             .filter_map(|probe| probe.clone().ok())
             .collect();
 
-        let failed_probe_errors: Vec<String> = probes.into_iter()
+        let mut failed_probe_errors: Vec<String> = probes.into_iter()
             .filter(|probe| probe.is_err())
             .map(|probe| probe.unwrap_err())
             .collect();
 
+        let (assertions, assertion_errors) = from_saved_assertions(saved_assertions, &verified_probes, self.circuit_manager.get_circuits());
+        failed_probe_errors.extend(assertion_errors);
+
         self.probes = verified_probes;
+        self.assertions = assertions;
+        self.assertion_failures.clear();
 
         if failed_probe_errors.is_empty() {
             None
@@ -39,8 +45,13 @@ impl CirquilPlayerApp {
         where
             P: AsRef<Path>,
     {
+        let saved_assertions: Vec<SavedAssertion> = self.assertions.iter()
+            .map(|assertion| assertion.saved.clone())
+            .collect();
+
         let workbench_file = to_workbench_file(&self.probes, &self.osc,
-                                               self.circuit_manager.get_circuits(), self.probe_max_id);
+                                               self.circuit_manager.get_circuits(), self.probe_max_id,
+                                               &saved_assertions);
         serialize_to_file(&workbench_file, path).unwrap();
     }
 }
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+use crate::serde::remote::{Request, Response};
+
+#[derive(Debug)]
+pub enum RemoteError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// Error response of the simulation.
+    Server(String),
+    UnexpectedResponse(Response),
+    Disconnected,
+}
+
+impl Display for RemoteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RemoteError::Io(error) => write!(f, "{}", error),
+            RemoteError::Json(error) => write!(f, "Invalid response: {}", error),
+            RemoteError::Server(message) => write!(f, "{}", message),
+            RemoteError::UnexpectedResponse(response) => write!(f, "Unexpected response {:?}", response),
+            RemoteError::Disconnected => write!(f, "Simulation closed the connection"),
+        }
+    }
+}
+
+impl Error for RemoteError {}
+
+impl From<io::Error> for RemoteError {
+    fn from(value: io::Error) -> Self {
+        RemoteError::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for RemoteError {
+    fn from(value: serde_json::Error) -> Self {
+        RemoteError::Json(value)
+    }
+}
+
+/// Probe values streamed after a tick.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sample {
+    pub cycle: usize,
+    pub values: BTreeMap<String, String>,
+}
+
+/// Blocking client of a simulation started with `--remote`.
+pub struct RemoteClient {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    samples: Vec<Sample>,
+}
+
+impl RemoteClient {
+    pub fn connect<A: ToSocketAddrs>(address: A) -> Result<RemoteClient, RemoteError> {
+        let writer = TcpStream::connect(address)?;
+        let reader = BufReader::new(writer.try_clone()?);
+
+        Ok(RemoteClient { reader, writer, samples: vec![] })
+    }
+
+    /// Sends a request and returns its final response, samples streamed
+    /// before it are kept for [`RemoteClient::take_samples`].
+    pub fn call(&mut self, request: &Request) -> Result<Response, RemoteError> {
+        writeln!(self.writer, "{}", serde_json::to_string(request)?)?;
+        self.writer.flush()?;
+
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err(RemoteError::Disconnected);
+            }
+
+            match serde_json::from_str(&line)? {
+                Response::Sample { cycle, values } => self.samples.push(Sample { cycle, values }),
+                Response::Error { message } => return Err(RemoteError::Server(message)),
+                response => return Ok(response),
+            }
+        }
+    }
+
+    fn call_ok(&mut self, request: &Request) -> Result<usize, RemoteError> {
+        match self.call(request)? {
+            Response::Ok { cycle } => Ok(cycle),
+            response => Err(RemoteError::UnexpectedResponse(response)),
+        }
+    }
+
+    /// Returns the cycle reached.
+    pub fn tick(&mut self, cycles: usize) -> Result<usize, RemoteError> {
+        self.call_ok(&Request::Tick { cycles })
+    }
+
+    pub fn propagate(&mut self) -> Result<(), RemoteError> {
+        self.call_ok(&Request::Propagate).map(|_| ())
+    }
+
+    /// Returns the value as a bit string.
+    pub fn read(&mut self, name: &str) -> Result<String, RemoteError> {
+        match self.call(&Request::Read { name: name.to_string() })? {
+            Response::Value { bits, .. } => Ok(bits),
+            response => Err(RemoteError::UnexpectedResponse(response)),
+        }
+    }
+
+    pub fn write(&mut self, name: &str, value: u32) -> Result<(), RemoteError> {
+        self.call_ok(&Request::Write { name: name.to_string(), value }).map(|_| ())
+    }
+
+    pub fn reset(&mut self) -> Result<(), RemoteError> {
+        self.call_ok(&Request::Reset).map(|_| ())
+    }
+
+    pub fn list(&mut self) -> Result<Vec<(String, String)>, RemoteError> {
+        match self.call(&Request::List)? {
+            Response::Signals { signals } => Ok(signals),
+            response => Err(RemoteError::UnexpectedResponse(response)),
+        }
+    }
+
+    pub fn subscribe(&mut self, probes: &[&str]) -> Result<(), RemoteError> {
+        let probes = probes.iter().map(|probe| probe.to_string()).collect();
+        self.call_ok(&Request::Subscribe { probes }).map(|_| ())
+    }
+
+    pub fn unsubscribe(&mut self) -> Result<(), RemoteError> {
+        self.call_ok(&Request::Unsubscribe).map(|_| ())
+    }
+
+    /// Returns samples received since the last call.
+    pub fn take_samples(&mut self) -> Vec<Sample> {
+        std::mem::take(&mut self.samples)
+    }
+
+    pub fn shutdown(mut self) -> Result<(), RemoteError> {
+        self.call_ok(&Request::Shutdown).map(|_| ())
+    }
+}
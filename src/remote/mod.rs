@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+
+use crate::core::simulation::value::Value;
+use crate::headless::session::Session;
+use crate::remote::server::RemoteServer;
+use crate::serde::remote::{Request, Response};
+
+pub mod client;
+pub mod server;
+
+/// Simulation which can be driven over the remote control protocol.
+pub trait RemoteTarget {
+    /// Ticks the clock once.
+    fn tick(&mut self) -> Result<(), String>;
+    fn propagate(&mut self);
+    fn reset(&mut self);
+    /// Cycles simulated since the last reset.
+    fn cycle(&self) -> usize;
+    fn read(&self, name: &str) -> Result<(Value, u8), String>;
+    fn write(&mut self, name: &str, value: u32) -> Result<(), String>;
+    fn list(&self) -> Vec<(String, String)>;
+}
+
+impl RemoteTarget for Session {
+    fn tick(&mut self) -> Result<(), String> {
+        self.step(1);
+        Ok(())
+    }
+
+    fn propagate(&mut self) {
+        Session::propagate(self);
+    }
+
+    fn reset(&mut self) {
+        Session::reset(self);
+    }
+
+    fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    fn read(&self, name: &str) -> Result<(Value, u8), String> {
+        self.peek(name)
+    }
+
+    fn write(&mut self, name: &str, value: u32) -> Result<(), String> {
+        self.poke(name, value)
+    }
+
+    fn list(&self) -> Vec<(String, String)> {
+        Session::list(self)
+    }
+}
+
+/// Answers requests of one client at a time, a new connection drops the
+/// subscription of the previous one.
+#[derive(Debug, Default)]
+pub struct RemoteHandler {
+    connection: usize,
+    subscribed: Vec<String>,
+}
+
+impl RemoteHandler {
+    pub fn handle<T: RemoteTarget>(&mut self, target: &mut T, connection: usize, request: Request) -> Vec<Response> {
+        if connection != self.connection {
+            self.connection = connection;
+            self.subscribed.clear();
+        }
+
+        let result = match request {
+            Request::Tick { cycles } => return self.tick(target, cycles),
+            Request::Propagate => {
+                target.propagate();
+                Ok(())
+            }
+            Request::Read { name } => {
+                return vec![match target.read(&name) {
+                    Ok((value, bit_width)) => {
+                        let width = !u32::MAX.checked_shl(bit_width as u32).unwrap_or(0);
+                        let defined = (value.get_raw_mask() & width == 0).then_some(value.get_raw_value() & width);
+
+                        Response::Value { name, bits: value.to_bit_string(bit_width), value: defined }
+                    }
+                    Err(message) => Response::Error { message },
+                }];
+            }
+            Request::Write { name, value } => target.write(&name, value),
+            Request::Reset => {
+                target.reset();
+                Ok(())
+            }
+            Request::List => return vec![Response::Signals { signals: target.list() }],
+            Request::Subscribe { probes } => {
+                match probes.iter().find_map(|name| target.read(name).err()) {
+                    Some(message) => Err(message),
+                    None => {
+                        self.subscribed = probes;
+                        Ok(())
+                    }
+                }
+            }
+            Request::Unsubscribe => {
+                self.subscribed.clear();
+                Ok(())
+            }
+            Request::Shutdown => Ok(()),
+        };
+
+        vec![match result {
+            Ok(()) => Response::Ok { cycle: target.cycle() },
+            Err(message) => Response::Error { message },
+        }]
+    }
+
+    fn tick<T: RemoteTarget>(&mut self, target: &mut T, cycles: usize) -> Vec<Response> {
+        let mut responses = Vec::new();
+
+        for _ in 0..cycles {
+            if let Err(message) = target.tick() {
+                responses.push(Response::Error { message });
+                return responses;
+            }
+
+            if !self.subscribed.is_empty() {
+                let values: BTreeMap<String, String> = self.subscribed.iter()
+                    .filter_map(|name| {
+                        let (value, bit_width) = target.read(name).ok()?;
+                        Some((name.clone(), value.to_bit_string(bit_width)))
+                    })
+                    .collect();
+
+                responses.push(Response::Sample { cycle: target.cycle(), values });
+            }
+        }
+
+        responses.push(Response::Ok { cycle: target.cycle() });
+        responses
+    }
+
+    /// Answers requests until a client asks for shutdown.
+    pub fn serve<T: RemoteTarget>(&mut self, target: &mut T, server: &RemoteServer) {
+        while let Ok(call) = server.calls.recv() {
+            let shutdown = call.request == Request::Shutdown;
+            let responses = self.handle(target, call.connection, call.request.clone());
+            let written = call.reply(responses);
+
+            // Let the client read the response before the process exits
+            if shutdown {
+                let _ = written.recv();
+                break;
+            }
+        }
+    }
+
+    /// Answers pending requests without blocking, returns whether a client
+    /// asked for shutdown.
+    pub fn poll<T: RemoteTarget>(&mut self, target: &mut T, server: &RemoteServer) -> bool {
+        while let Ok(call) = server.calls.try_recv() {
+            let shutdown = call.request == Request::Shutdown;
+            let responses = self.handle(target, call.connection, call.request.clone());
+            let written = call.reply(responses);
+
+            // The server is dropped next, which closes the connection
+            if shutdown {
+                let _ = written.recv();
+                return true;
+            }
+        }
+
+        false
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::io;
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::serde::remote::{Request, Response};
+
+/// Request of a client waiting for its responses.
+pub struct RemoteCall {
+    /// Sequence number of the connection the request came from.
+    pub connection: usize,
+    pub request: Request,
+    reply: Sender<(Vec<Response>, Sender<()>)>,
+}
+
+impl RemoteCall {
+    /// Returns a receiver notified once the responses were written.
+    pub fn reply(self, responses: Vec<Response>) -> Receiver<()> {
+        let (written, receiver) = channel();
+
+        // The client may have disconnected in the meantime
+        let _ = self.reply.send((responses, written));
+        receiver
+    }
+}
+
+/// How often the accept thread checks whether the server was dropped.
+const ACCEPT_POLL_PERIOD: Duration = Duration::from_millis(50);
+
+/// Open client connections by sequence number.
+type Connections = Arc<Mutex<HashMap<usize, TcpStream>>>;
+
+/// Accepts clients on a background thread and queues their requests, the
+/// simulation answers them from its own thread.
+///
+/// The protocol has no authentication, so only loopback addresses are
+/// accepted. Dropping the server closes the listener and every connection.
+pub struct RemoteServer {
+    pub address: SocketAddr,
+    pub(crate) calls: Receiver<RemoteCall>,
+    stopped: Arc<AtomicBool>,
+    connections: Connections,
+    accept_thread: Option<JoinHandle<()>>,
+}
+
+impl RemoteServer {
+    pub fn bind<A: ToSocketAddrs>(address: A) -> io::Result<RemoteServer> {
+        RemoteServer::from_listener(TcpListener::bind(address)?)
+    }
+
+    pub fn from_listener(listener: TcpListener) -> io::Result<RemoteServer> {
+        let address = listener.local_addr()?;
+        if !address.ip().is_loopback() {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("Remote control is not authenticated, {} is not a loopback address like 127.0.0.1", address.ip()),
+            ));
+        }
+
+        listener.set_nonblocking(true)?;
+
+        let (sender, calls) = channel();
+        let stopped = Arc::new(AtomicBool::new(false));
+        let connections: Connections = Arc::default();
+
+        let accept_thread = {
+            let stopped = stopped.clone();
+            let connections = connections.clone();
+
+            thread::spawn(move || accept_connections(listener, sender, &stopped, connections))
+        };
+
+        Ok(RemoteServer { address, calls, stopped, connections, accept_thread: Some(accept_thread) })
+    }
+}
+
+impl Drop for RemoteServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+
+        // The listener is closed once the accept thread returns
+        if let Some(accept_thread) = self.accept_thread.take() {
+            let _ = accept_thread.join();
+        }
+
+        for stream in self.connections.lock().unwrap().values() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+fn accept_connections(listener: TcpListener, calls: Sender<RemoteCall>, stopped: &AtomicBool, connections: Connections) {
+    let mut connection = 0;
+
+    while !stopped.load(Ordering::Relaxed) {
+        let stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(error) if error.kind() == ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_POLL_PERIOD);
+                continue;
+            }
+            Err(_) => continue,
+        };
+
+        // Accepted streams may inherit non-blocking mode from the listener
+        let Ok(tracked) = stream.set_nonblocking(false).and_then(|_| stream.try_clone()) else { continue; };
+
+        connection += 1;
+        connections.lock().unwrap().insert(connection, tracked);
+
+        let calls = calls.clone();
+        let connections = connections.clone();
+
+        thread::spawn(move || {
+            let _ = handle_connection(connection, stream, calls);
+            connections.lock().unwrap().remove(&connection);
+        });
+    }
+}
+
+fn handle_connection(connection: usize, stream: TcpStream, calls: Sender<RemoteCall>) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (responses, written) = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => {
+                let (reply, responses) = channel();
+
+                // Stop once the simulation is gone
+                if calls.send(RemoteCall { connection, request, reply }).is_err() {
+                    break;
+                }
+
+                match responses.recv() {
+                    Ok((responses, written)) => (responses, Some(written)),
+                    Err(_) => break,
+                }
+            }
+            Err(error) => (vec![Response::Error { message: format!("Invalid request: {}", error) }], None),
+        };
+
+        for response in responses {
+            writeln!(writer, "{}", serde_json::to_string(&response)?)?;
+        }
+
+        writer.flush()?;
+
+        if let Some(written) = written {
+            let _ = written.send(());
+        }
+    }
+
+    Ok(())
+}
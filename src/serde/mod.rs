@@ -3,3 +3,10 @@ pub mod project;
 pub mod workbench;
 pub mod replay;
 pub mod csv;
+pub mod vectors;
+pub mod stimulus;
+pub mod vcd;
+pub mod summary;
+pub mod remote;
+pub mod verilog;
+pub mod yosys;
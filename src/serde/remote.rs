@@ -0,0 +1,42 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+fn one() -> usize { 1 }
+
+/// Remote control request, sent as one JSON object per line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum Request {
+    Tick {
+        #[serde(default = "one")]
+        cycles: usize,
+    },
+    /// Propagates every circuit without ticking the clock.
+    Propagate,
+    /// Reads a probe or a labelled component by path like `adder/sum`.
+    Read { name: String },
+    /// Sets an input pin or a button by label or UUID.
+    Write { name: String, value: u32 },
+    Reset,
+    /// Lists probes and top circuit pins.
+    List,
+    /// Streams a sample of these probes after every tick.
+    Subscribe { probes: Vec<String> },
+    Unsubscribe,
+    /// Stops a headless server, only closes the socket of the player.
+    Shutdown,
+}
+
+/// Response to a request, answers to ticks are preceded by a sample for every
+/// cycle while subscribed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    Ok { cycle: usize },
+    /// Value as a bit string, `value` is set when every bit is defined.
+    Value { name: String, bits: String, value: Option<u32> },
+    Signals { signals: Vec<(String, String)> },
+    Sample { cycle: usize, values: BTreeMap<String, String> },
+    Error { message: String },
+}
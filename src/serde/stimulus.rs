@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::simulation::value::parse_number;
+
+/// Values poked into inputs before a cycle, keyed by cycle and then by
+/// component label or UUID.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StimulusFile {
+    pub cycles: BTreeMap<usize, BTreeMap<String, u32>>,
+}
+
+impl StimulusFile {
+    /// Parses a CSV timeline, the first column is the cycle and the header
+    /// names the inputs of the other columns. Empty cells leave inputs as they are.
+    pub fn parse_csv(text: &str) -> Result<Self, Box<dyn Error>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(text.as_bytes());
+
+        let targets: Vec<String> = reader.headers()?.iter()
+            .skip(1)
+            .map(|target| target.to_string())
+            .collect();
+
+        let mut stimulus = StimulusFile::default();
+        for record in reader.records() {
+            let record = record?;
+            let line = record.position().map(|position| position.line()).unwrap_or(0);
+
+            let cycle = record.get(0)
+                .and_then(|cycle| cycle.parse().ok())
+                .ok_or(format!("Stimulus line {}: invalid cycle", line))?;
+
+            let values = stimulus.cycles.entry(cycle).or_default();
+            for (target, field) in targets.iter().zip(record.iter().skip(1)) {
+                if field.is_empty() {
+                    continue;
+                }
+
+                let value = parse_number(field)
+                    .ok_or(format!("Stimulus line {}: invalid value {} for {}", line, field, target))?;
+                values.insert(target.clone(), value);
+            }
+        }
+
+        Ok(stimulus)
+    }
+
+    /// Loads CSV files by their extension and anything else as JSON.
+    pub fn load<P>(path: P) -> Result<Self, Box<dyn Error>>
+        where
+            P: AsRef<Path>,
+    {
+        let text = fs::read_to_string(&path)?;
+
+        let is_csv = path.as_ref().extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+
+        if is_csv {
+            Self::parse_csv(&text)
+        } else {
+            Ok(serde_json::from_str(&text)?)
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::simulation::assertion::AssertionFailure;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    /// All requested cycles were simulated.
+    Cycles,
+    /// The `until` condition held.
+    Condition,
+    /// The `until` condition never held within the requested cycles.
+    ConditionNotMet,
+    Timeout,
+    /// A workbench assertion failed.
+    AssertionFailed,
+}
+
+/// Outcome of a headless run, saved for scripts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunSummary {
+    pub cycles: usize,
+    pub stop_reason: StopReason,
+    pub elapsed_seconds: f64,
+    /// Final value of every probe as a bit string.
+    pub probes: BTreeMap<String, String>,
+    pub warnings: Vec<String>,
+    pub failed_assertion: Option<AssertionFailure>,
+}
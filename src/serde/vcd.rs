@@ -0,0 +1,134 @@
+use std::fs::File;
+use std::io::{BufWriter, Error, Write};
+use std::path::Path;
+
+use crate::core::simulation::trace::Trace;
+use crate::core::simulation::value::Value;
+
+/// Traced signal, `scope` lists the enclosing modules from the top.
+#[derive(Debug, Clone)]
+pub struct VcdSignal {
+    pub name: String,
+    pub scope: Vec<String>,
+    pub bit_width: u8,
+    pub trace_idx: usize,
+}
+
+/// Short identifier made of printable characters, as VCD expects.
+fn get_identifier(mut idx: usize) -> String {
+    let mut identifier = String::new();
+
+    loop {
+        identifier.push((b'!' + (idx % 94) as u8) as char);
+        idx /= 94;
+
+        if idx == 0 {
+            return identifier;
+        }
+
+        idx -= 1;
+    }
+}
+
+/// VCD names cannot contain whitespace.
+fn escape_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_whitespace() { '_' } else { c })
+        .collect()
+}
+
+/// Undriven bits are written as `z` and errors as `x`. Samples missing from
+/// the trace are unknown.
+fn format_value(value: Option<Value>, bit_width: u8) -> String {
+    match value {
+        Some(value) => value.to_bit_string(bit_width)
+            .chars()
+            .map(|bit| match bit {
+                'x' => 'z',
+                'E' => 'x',
+                bit => bit,
+            })
+            .collect(),
+        None => "x".repeat(bit_width as usize),
+    }
+}
+
+/// Writes traces as a Value Change Dump, one time step per sample.
+pub fn write_vcd<W>(writer: &mut W, signals: &[VcdSignal], trace: &Trace) -> Result<(), Error>
+    where W: Write
+{
+    writeln!(writer, "$version cirquil {} $end", env!("CARGO_PKG_VERSION"))?;
+    writeln!(writer, "$timescale 1 ns $end")?;
+
+    // Group signals by scope, reopening only the scopes which differ
+    let mut order: Vec<usize> = (0..signals.len()).collect();
+    order.sort_by(|a, b| signals[*a].scope.cmp(&signals[*b].scope));
+
+    let mut current: Vec<String> = Vec::new();
+    for &idx in order.iter() {
+        let signal = &signals[idx];
+        let common = current.iter().zip(signal.scope.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        for _ in common..current.len() {
+            writeln!(writer, "$upscope $end")?;
+        }
+        for scope in signal.scope[common..].iter() {
+            writeln!(writer, "$scope module {} $end", escape_name(scope))?;
+        }
+        current = signal.scope.clone();
+
+        writeln!(writer, "$var wire {} {} {} $end", signal.bit_width, get_identifier(idx), escape_name(&signal.name))?;
+    }
+    for _ in 0..current.len() {
+        writeln!(writer, "$upscope $end")?;
+    }
+
+    writeln!(writer, "$enddefinitions $end")?;
+
+    let mut last: Vec<Option<String>> = vec![None; signals.len()];
+
+    for sample in 0..trace.recorded_samples as usize {
+        let mut changes = Vec::new();
+
+        for (idx, signal) in signals.iter().enumerate() {
+            let value = trace.traces[signal.trace_idx].get(sample).copied().flatten();
+            let value = format_value(value, signal.bit_width);
+
+            if last[idx].as_ref() != Some(&value) {
+                changes.push(match signal.bit_width {
+                    1 => format!("{}{}", value, get_identifier(idx)),
+                    _ => format!("b{} {}", value, get_identifier(idx)),
+                });
+                last[idx] = Some(value);
+            }
+        }
+
+        if sample == 0 {
+            writeln!(writer, "#0\n$dumpvars")?;
+            for change in changes {
+                writeln!(writer, "{}", change)?;
+            }
+            writeln!(writer, "$end")?;
+        } else if !changes.is_empty() {
+            writeln!(writer, "#{}", sample)?;
+            for change in changes {
+                writeln!(writer, "{}", change)?;
+            }
+        }
+    }
+
+    writeln!(writer, "#{}", trace.recorded_samples)?;
+
+    Ok(())
+}
+
+pub fn save_vcd<P>(path: P, signals: &[VcdSignal], trace: &Trace) -> Result<(), Error>
+    where P: AsRef<Path>
+{
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    write_vcd(&mut writer, signals, trace)?;
+    writer.flush()
+}
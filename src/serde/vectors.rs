@@ -0,0 +1,220 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+use crate::core::simulation::value::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorError {
+    /// One-based line of the test vector file, zero for errors not tied to a line.
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for VectorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for VectorError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorColumn {
+    pub name: String,
+    /// Width written in the header as `name[width]`.
+    pub bit_width: Option<u8>,
+}
+
+/// Value of one column, bits cleared in `care` are don't-cares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorValue {
+    pub bits: u32,
+    pub care: u32,
+}
+
+impl VectorValue {
+    /// Don't-care bits are driven as unknown.
+    pub fn to_value(&self, bit_width: u8) -> Value {
+        let undefined = !self.care & !u32::MAX.checked_shl(bit_width as u32).unwrap_or(0);
+        Value::new(self.bits & self.care, Value::create(0, bit_width).get_raw_mask() | undefined)
+    }
+
+    /// Whether every bit which is cared for has the expected defined state.
+    pub fn matches(&self, value: Value, bit_width: u8) -> bool {
+        let care = self.care & !u32::MAX.checked_shl(bit_width as u32).unwrap_or(0);
+
+        value.get_raw_mask() & care == 0 && (value.get_raw_value() ^ self.bits) & care == 0
+    }
+
+    /// Formats lowest `bits` bits like [`Value::to_bit_string`], don't-cares as `x`.
+    pub fn to_bit_string(&self, bits: u8) -> String {
+        (0..bits.min(32)).rev()
+            .map(|i| match ((self.care >> i) & 1, (self.bits >> i) & 1) {
+                (0, _) => 'x',
+                (_, 0) => '0',
+                _ => '1',
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorRow {
+    pub line: usize,
+    pub values: Vec<VectorValue>,
+}
+
+/// Values for circuit pins, one row per step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestVectors {
+    pub columns: Vec<VectorColumn>,
+    pub rows: Vec<VectorRow>,
+}
+
+impl TestVectors {
+    /// Parses the Logisim test vector format: a header with pin names, then
+    /// whitespace separated values, `#` starts a comment.
+    pub fn parse(text: &str) -> Result<Self, VectorError> {
+        let lines: Vec<(usize, Vec<&str>)> = text.lines().enumerate()
+            .map(|(idx, line)| (idx + 1, line.split('#').next().unwrap().split_whitespace().collect()))
+            .filter(|(_, fields): &(usize, Vec<&str>)| !fields.is_empty())
+            .collect();
+
+        Self::from_fields(lines)
+    }
+
+    /// Parses comma separated values with a header row.
+    pub fn parse_csv(text: &str) -> Result<Self, VectorError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .trim(csv::Trim::All)
+            .from_reader(text.as_bytes());
+
+        let mut lines = Vec::new();
+        for record in reader.records() {
+            let record = record.map_err(|error| VectorError {
+                line: error.position().map(|position| position.line() as usize).unwrap_or(0),
+                message: error.to_string(),
+            })?;
+
+            let line = record.position().map(|position| position.line() as usize).unwrap_or(0);
+            lines.push((line, record.iter().map(|field| field.to_string()).collect::<Vec<String>>()));
+        }
+
+        Self::from_fields(lines.iter()
+            .map(|(line, fields)| (*line, fields.iter().map(|field| field.as_str()).collect()))
+            .filter(|(_, fields): &(usize, Vec<&str>)| fields.iter().any(|field| !field.is_empty()))
+            .collect())
+    }
+
+    /// Loads CSV files by their extension and anything else as Logisim vectors.
+    pub fn load<P>(path: P) -> Result<Self, Box<dyn Error>>
+        where
+            P: AsRef<Path>,
+    {
+        let text = fs::read_to_string(&path)?;
+
+        let is_csv = path.as_ref().extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+
+        if is_csv {
+            Ok(Self::parse_csv(&text)?)
+        } else {
+            Ok(Self::parse(&text)?)
+        }
+    }
+
+    fn from_fields(lines: Vec<(usize, Vec<&str>)>) -> Result<Self, VectorError> {
+        let Some((_, header)) = lines.first() else {
+            return Err(VectorError { line: 1, message: "test vector file is empty".to_string() });
+        };
+
+        let columns = header.iter()
+            .map(|field| parse_column(field).ok_or_else(|| VectorError {
+                line: lines[0].0,
+                message: format!("invalid column {}", field),
+            }))
+            .collect::<Result<Vec<VectorColumn>, VectorError>>()?;
+
+        let rows = lines[1..].iter()
+            .map(|(line, fields)| {
+                if fields.len() != columns.len() {
+                    return Err(VectorError {
+                        line: *line,
+                        message: format!("expected {} values, found {}", columns.len(), fields.len()),
+                    });
+                }
+
+                let values = fields.iter()
+                    .map(|field| parse_value(field).ok_or_else(|| VectorError {
+                        line: *line,
+                        message: format!("invalid value {}", field),
+                    }))
+                    .collect::<Result<Vec<VectorValue>, VectorError>>()?;
+
+                Ok(VectorRow { line: *line, values })
+            })
+            .collect::<Result<Vec<VectorRow>, VectorError>>()?;
+
+        Ok(TestVectors { columns, rows })
+    }
+}
+
+fn parse_column(field: &str) -> Option<VectorColumn> {
+    let Some((name, width)) = field.split_once('[') else {
+        return Some(VectorColumn { name: field.to_string(), bit_width: None });
+    };
+
+    let bit_width = width.strip_suffix(']')?.parse().ok()?;
+    Some(VectorColumn { name: name.to_string(), bit_width: Some(bit_width) })
+}
+
+/// Binary by default, `0x` hexadecimal, `0o` octal and `0d` decimal. Binary,
+/// octal and hexadecimal digits can be `x` for don't-care bits.
+fn parse_value(field: &str) -> Option<VectorValue> {
+    let lowercase = field.to_ascii_lowercase();
+
+    let (digits, digit_bits) = match lowercase.get(..2) {
+        Some("0x") if lowercase.len() > 2 => (&lowercase[2..], 4),
+        Some("0o") => (&lowercase[2..], 3),
+        Some("0b") => (&lowercase[2..], 1),
+        Some("0d") => {
+            let bits = lowercase[2..].parse().ok()?;
+            return Some(VectorValue { bits, care: u32::MAX });
+        }
+        _ => (lowercase.as_str(), 1),
+    };
+
+    if digits.is_empty() {
+        return None;
+    }
+
+    // A single `x` leaves the whole value unspecified
+    if digits == "x" {
+        return Some(VectorValue { bits: 0, care: 0 });
+    }
+
+    let mut bits: u64 = 0;
+    let mut care: u64 = 0;
+    for digit in digits.chars() {
+        let digit_mask = (1 << digit_bits) - 1;
+        bits <<= digit_bits;
+        care <<= digit_bits;
+
+        if digit == 'x' {
+            continue;
+        }
+
+        let digit = digit.to_digit(1 << digit_bits)? as u64;
+        bits |= digit;
+        care |= digit_mask;
+    }
+
+    // Bits above the written digits have to be zero
+    let written = (digits.len() as u32 * digit_bits).min(32);
+    let care = care as u32 | u32::MAX.checked_shl(written).unwrap_or(0);
+
+    Some(VectorValue { bits: bits as u32, care })
+}
@@ -0,0 +1,255 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write};
+use std::fs;
+use std::path::Path;
+
+use crate::core::compiler::net::extract_nets;
+use crate::core::simulation::component::{ComponentIdx, ComponentModel};
+use crate::core::simulation::components::subcircuit::Subcircuit;
+use crate::core::simulation::pin::{Direction, PinIdx};
+use crate::serde::project::{ProjectFile, SavedCircuit};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerilogError {
+    /// Component without a synthesizable equivalent, like a clock generator.
+    Unsupported { circuit: String, component: String },
+    MissingCircuit { circuit: String, name: String },
+    MissingTopCircuit { name: String },
+    /// Input or output pin without a label, which has no port name.
+    UnlabeledPort { circuit: String },
+    DuplicatePort { circuit: String, name: String },
+}
+
+impl Display for VerilogError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerilogError::Unsupported { circuit, component } => {
+                write!(f, "Circuit {}: {} cannot be exported to Verilog", circuit, component)
+            }
+            VerilogError::MissingCircuit { circuit, name } => {
+                write!(f, "Circuit {}: subcircuit {} does not exist", circuit, name)
+            }
+            VerilogError::MissingTopCircuit { name } => {
+                write!(f, "Top circuit {} does not exist", name)
+            }
+            VerilogError::UnlabeledPort { circuit } => {
+                write!(f, "Circuit {}: input and output pins need labels to become ports", circuit)
+            }
+            VerilogError::DuplicatePort { circuit, name } => {
+                write!(f, "Circuit {}: more than one port is labelled {}", circuit, name)
+            }
+        }
+    }
+}
+
+impl Error for VerilogError {}
+
+const KEYWORDS: &[&str] = &[
+    "always", "and", "assign", "begin", "buf", "case", "default", "else", "end", "endcase", "endmodule",
+    "for", "function", "if", "initial", "inout", "input", "integer", "module", "nand", "nor", "not", "or",
+    "output", "parameter", "reg", "supply0", "supply1", "task", "tri", "wire", "xnor", "xor",
+];
+
+/// Names which are not plain identifiers are written as escaped identifiers.
+fn identifier(name: &str) -> String {
+    let mut chars = name.chars();
+    let simple = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+    if simple && !KEYWORDS.contains(&name) {
+        name.to_string()
+    } else {
+        format!("\\{} ", name.replace(char::is_whitespace, "_"))
+    }
+}
+
+fn range(bit_width: u8) -> String {
+    match bit_width {
+        1 => String::new(),
+        bit_width => format!("[{}:0] ", bit_width - 1),
+    }
+}
+
+/// Port of a module, from the circuit pins or from a labelled pin component
+/// missing among them.
+struct ModulePort {
+    name: String,
+    bit_width: u8,
+    direction: Direction,
+}
+
+fn get_ports(name: &str, circuit: &SavedCircuit) -> Result<Vec<ModulePort>, VerilogError> {
+    let duplicate = |port: &str| VerilogError::DuplicatePort { circuit: name.to_string(), name: port.to_string() };
+
+    let mut ports: Vec<ModulePort> = Vec::new();
+    for pin in circuit.pins.iter() {
+        if ports.iter().any(|port| port.name == pin.label) {
+            return Err(duplicate(&pin.label));
+        }
+
+        ports.push(ModulePort { name: pin.label.clone(), bit_width: pin.bit_width, direction: pin.direction });
+    }
+
+    // Every pin component is a port, either one of the circuit pins or a new one
+    let mut labels: Vec<String> = Vec::new();
+    for saved in circuit.components.iter() {
+        let direction = match saved.component.model {
+            ComponentModel::InputPin(_) => Direction::Input,
+            ComponentModel::OutputPin(_) => Direction::Output,
+            _ => continue,
+        };
+
+        let label = saved.component.get_properties().get_string("label").unwrap_or_default();
+        if labels.contains(&label) {
+            return Err(duplicate(&label));
+        }
+
+        if !ports.iter().any(|port| port.name == label) {
+            let bit_width = saved.component.get_pins()[0].bit_width;
+            ports.push(ModulePort { name: label.clone(), bit_width, direction });
+        }
+
+        labels.push(label);
+    }
+
+    if ports.iter().any(|port| port.name.is_empty()) {
+        return Err(VerilogError::UnlabeledPort { circuit: name.to_string() });
+    }
+
+    Ok(ports)
+}
+
+/// Name derived from `name` which is not among `taken`, generated names get
+/// underscores in front until they do not clash with a port.
+fn fresh_name(name: String, taken: &[String]) -> String {
+    let mut name = name;
+    while taken.contains(&name) {
+        name.insert(0, '_');
+    }
+
+    name
+}
+
+/// Writes every circuit of the project as a structural Verilog module, the
+/// top circuit first.
+///
+/// Nets follow the compiler, so tunnels with the same name become one wire.
+/// Gates become continuous assignments and subcircuits module instances with
+/// ports connected by name. Clocks, buttons, behavioural and extension
+/// components have no structural equivalent and are reported as errors.
+pub fn export_verilog(project: &ProjectFile) -> Result<String, VerilogError> {
+    if !project.circuits.contains_key(&project.top_circuit) {
+        return Err(VerilogError::MissingTopCircuit { name: project.top_circuit.clone() });
+    }
+
+    let mut names: Vec<&String> = project.circuits.keys().collect();
+    names.sort_by_key(|name| (**name != project.top_circuit, name.as_str()));
+
+    let mut verilog = String::new();
+    for name in names {
+        if !verilog.is_empty() {
+            verilog.push('\n');
+        }
+
+        write_module(&mut verilog, name, &project.circuits[name], project)?;
+    }
+
+    Ok(verilog)
+}
+
+pub fn save_verilog<P>(path: P, project: &ProjectFile) -> Result<(), Box<dyn Error>>
+    where
+        P: AsRef<Path>,
+{
+    fs::write(path, export_verilog(project)?)?;
+    Ok(())
+}
+
+fn write_module(verilog: &mut String, name: &str, circuit: &SavedCircuit, project: &ProjectFile) -> Result<(), VerilogError> {
+    let unsupported = |component: &str| VerilogError::Unsupported { circuit: name.to_string(), component: component.to_string() };
+
+    let ports = get_ports(name, circuit)?;
+    let port_names: Vec<String> = ports.iter().map(|port| identifier(&port.name)).collect();
+
+    let mut nets: HashMap<(ComponentIdx, PinIdx), String> = HashMap::new();
+    let mut widths: Vec<(String, u8)> = Vec::new();
+
+    for (idx, net) in extract_nets(&circuit.wires, &circuit.components).iter().enumerate() {
+        let Some(&(component, pin)) = net.pins.first() else { continue; };
+
+        let net_name = fresh_name(format!("n{}", idx), &port_names);
+        widths.push((net_name.clone(), circuit.components[component].component.get_pins()[pin].bit_width));
+
+        for &pin in net.pins.iter() {
+            nets.insert(pin, net_name.clone());
+        }
+    }
+
+    // Pins touching nothing get wires of their own
+    for (component_idx, saved) in circuit.components.iter().enumerate() {
+        for (pin_idx, pin) in saved.component.get_pins().iter().enumerate() {
+            if let Entry::Vacant(entry) = nets.entry((component_idx, pin_idx)) {
+                let net_name = fresh_name(format!("u{}_{}", component_idx, pin_idx), &port_names);
+                widths.push((net_name.clone(), pin.bit_width));
+                entry.insert(net_name);
+            }
+        }
+    }
+
+    writeln!(verilog, "module {}({});", identifier(name), port_names.join(", ")).unwrap();
+
+    for (port, port_name) in ports.iter().zip(port_names.iter()) {
+        let direction = match port.direction {
+            Direction::Input => "input",
+            _ => "output",
+        };
+
+        writeln!(verilog, "  {} {}{};", direction, range(port.bit_width), port_name).unwrap();
+    }
+
+    for (net_name, bit_width) in widths.iter() {
+        writeln!(verilog, "  wire {}{};", range(*bit_width), net_name).unwrap();
+    }
+
+    for (component_idx, saved) in circuit.components.iter().enumerate() {
+        let net = |pin: PinIdx| nets[&(component_idx, pin)].as_str();
+        let component = &saved.component;
+
+        match &component.model {
+            ComponentModel::AndGate(_) => writeln!(verilog, "  assign {} = {} & {};", net(2), net(0), net(1)).unwrap(),
+            ComponentModel::OrGate(_) => writeln!(verilog, "  assign {} = {} | {};", net(2), net(0), net(1)).unwrap(),
+            ComponentModel::NotGate(_) => writeln!(verilog, "  assign {} = ~{};", net(1), net(0)).unwrap(),
+            ComponentModel::Tunnel(_) => {}
+            ComponentModel::InputPin(_) => {
+                if let Some(label) = component.get_properties().get_string("label") {
+                    writeln!(verilog, "  assign {} = {};", net(0), identifier(&label)).unwrap();
+                }
+            }
+            ComponentModel::OutputPin(_) => {
+                if let Some(label) = component.get_properties().get_string("label") {
+                    writeln!(verilog, "  assign {} = {};", identifier(&label), net(0)).unwrap();
+                }
+            }
+            ComponentModel::Subcircuit(Subcircuit::NotInstantiated(subcircuit)) => {
+                let Some(saved_subcircuit) = project.circuits.get(subcircuit) else {
+                    return Err(VerilogError::MissingCircuit { circuit: name.to_string(), name: subcircuit.clone() });
+                };
+
+                let connections: Vec<String> = saved_subcircuit.pins.iter().enumerate()
+                    .take(component.get_pins().len())
+                    .map(|(pin, port)| format!(".{}({})", identifier(&port.label), net(pin)))
+                    .collect();
+
+                let instance = fresh_name(format!("i{}", component_idx), &port_names);
+                writeln!(verilog, "  {} {}({});", identifier(subcircuit), instance, connections.join(", ")).unwrap();
+            }
+            model => return Err(unsupported(model.get_name())),
+        }
+    }
+
+    writeln!(verilog, "endmodule").unwrap();
+
+    Ok(())
+}
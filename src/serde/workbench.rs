@@ -33,9 +33,29 @@ pub struct OscilloscopeRow {
     pub source: String,
 }
 
+/// When an assertion is checked, clocks are probe names.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssertionTrigger {
+    #[default]
+    EveryCycle,
+    RisingEdge(String),
+    FallingEdge(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedAssertion {
+    pub name: String,
+    /// Expression over probe names which has to be non-zero.
+    pub condition: String,
+    #[serde(default)]
+    pub trigger: AssertionTrigger,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkbenchFile {
     pub probes: Vec<SavedProbe>,
     pub oscilloscope_config: OscilloscopeConfig,
     pub last_probe_id: usize,
+    #[serde(default)]
+    pub assertions: Vec<SavedAssertion>,
 }
@@ -0,0 +1,357 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::core::simulation::component::Component;
+use crate::core::simulation::components::behavioural::Behavioural;
+use crate::core::simulation::components::logic::and_gate::AndGate;
+use crate::core::simulation::components::logic::not_gate::NotGate;
+use crate::core::simulation::components::logic::or_gate::OrGate;
+use crate::core::simulation::components::subcircuit::input_pin::InputPin;
+use crate::core::simulation::components::subcircuit::output_pin::OutputPin;
+use crate::core::simulation::components::subcircuit::Subcircuit;
+use crate::core::simulation::pin::Direction;
+use crate::core::synthesis::layout::{Cell, layout, NetId};
+use crate::core::synthesis::port_pins;
+use crate::serde::project::{ProjectFile, SavedCircuit};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetlistError {
+    Json(String),
+    NoModules,
+    UnsupportedPort { module: String, port: String, direction: String },
+    UnsupportedCell { module: String, cell: String, cell_type: String },
+    /// Ports of a bitwise cell with different widths, which would need extension.
+    WidthMismatch { module: String, cell: String },
+    RecursiveModule { chain: Vec<String> },
+    /// Module with more cells or ports than fit on the canvas.
+    LayoutTooLarge { module: String },
+}
+
+impl Display for NetlistError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetlistError::Json(error) => write!(f, "Invalid Yosys JSON netlist: {}", error),
+            NetlistError::NoModules => write!(f, "Netlist contains no modules"),
+            NetlistError::UnsupportedPort { module, port, direction } => {
+                write!(f, "Module {}: port {} has unsupported direction {}", module, port, direction)
+            }
+            NetlistError::UnsupportedCell { module, cell, cell_type } => {
+                write!(f, "Module {}: cell {} of type {} cannot be imported", module, cell, cell_type)
+            }
+            NetlistError::WidthMismatch { module, cell } => {
+                write!(f, "Module {}: ports of cell {} have different widths", module, cell)
+            }
+            NetlistError::RecursiveModule { chain } => {
+                write!(f, "Module instantiates itself: {}", chain.join(" -> "))
+            }
+            NetlistError::LayoutTooLarge { module } => {
+                write!(f, "Module {}: too many cells or ports to lay out on the canvas", module)
+            }
+        }
+    }
+}
+
+impl Error for NetlistError {}
+
+#[derive(Debug, Deserialize)]
+struct YosysNetlist {
+    #[serde(default)]
+    modules: BTreeMap<String, YosysModule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YosysModule {
+    #[serde(default)]
+    attributes: BTreeMap<String, serde_json::Value>,
+    #[serde(default)]
+    ports: BTreeMap<String, YosysPort>,
+    #[serde(default)]
+    cells: BTreeMap<String, YosysCell>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YosysPort {
+    direction: String,
+    bits: Vec<YosysBit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YosysCell {
+    #[serde(rename = "type")]
+    cell_type: String,
+    #[serde(default)]
+    connections: BTreeMap<String, Vec<YosysBit>>,
+}
+
+/// Signal bit, either a net number or a constant `0`, `1`, `x` or `z`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum YosysBit {
+    Net(NetId),
+    Constant(String),
+}
+
+impl YosysModule {
+    fn is_top(&self) -> bool {
+        match self.attributes.get("top") {
+            Some(serde_json::Value::String(value)) => value.contains('1'),
+            Some(serde_json::Value::Number(value)) => value.as_u64() != Some(0),
+            _ => false,
+        }
+    }
+
+    /// Single bit port names in subcircuit pin order, `name_idx` for bits
+    /// of wide ports.
+    fn port_bits(&self, direction: &str) -> Vec<(String, &YosysBit)> {
+        self.ports.iter()
+            .filter(|(_, port)| port.direction == direction)
+            .flat_map(|(name, port)| {
+                port.bits.iter().enumerate().map(move |(idx, bit)| match port.bits.len() {
+                    1 => (name.clone(), bit),
+                    _ => (format!("{}_{}", name, idx), bit),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Single bit component for a bitwise cell type and its input ports in pin
+/// order, the output is always `Y`.
+fn bitwise_cell(cell_type: &str) -> Option<(&'static [&'static str], Component)> {
+    let expression = |inputs: &'static [&'static str], body: &str| {
+        let source = format!("input {}\noutput Y\nY = {}", inputs.join(", "), body);
+        (inputs, Behavioural::from_definition(&source).unwrap())
+    };
+
+    Some(match cell_type {
+        "$_AND_" | "$and" => (&["A", "B"], AndGate::from_bit_width(1)),
+        "$_OR_" | "$or" => (&["A", "B"], OrGate::from_bit_width(1)),
+        "$_NOT_" | "$not" => (&["A"], NotGate::from_bit_width(1)),
+        "$_BUF_" | "$pos" => expression(&["A"], "A"),
+        "$_XOR_" | "$xor" => expression(&["A", "B"], "A ^ B"),
+        "$_XNOR_" | "$xnor" => expression(&["A", "B"], "~(A ^ B)"),
+        "$_NAND_" => expression(&["A", "B"], "~(A & B)"),
+        "$_NOR_" => expression(&["A", "B"], "~(A | B)"),
+        "$_ANDNOT_" => expression(&["A", "B"], "A & ~B"),
+        "$_ORNOT_" => expression(&["A", "B"], "A | ~B"),
+        "$_MUX_" => expression(&["A", "B", "S"], "S & B | ~S & A"),
+        _ => return None,
+    })
+}
+
+/// Circuit being built for one module, Yosys net numbers are used as net
+/// ids and new nets are numbered after them.
+struct ModuleBuilder {
+    cells: Vec<Cell>,
+    next_net: NetId,
+    constants: HashMap<bool, NetId>,
+}
+
+impl ModuleBuilder {
+    fn add_net(&mut self) -> NetId {
+        self.next_net += 1;
+        self.next_net - 1
+    }
+
+    /// Constants get one driver per module, undefined bits a net without one.
+    fn net(&mut self, bit: &YosysBit) -> NetId {
+        let value = match bit {
+            YosysBit::Net(net) => return *net,
+            YosysBit::Constant(value) if value == "0" => false,
+            YosysBit::Constant(value) if value == "1" => true,
+            YosysBit::Constant(_) => return self.add_net(),
+        };
+
+        if let Some(net) = self.constants.get(&value) {
+            return *net;
+        }
+
+        let net = self.add_net();
+        let source = format!("output Y\nY = {}", value as u8);
+        self.cells.push(Cell {
+            component: Behavioural::from_definition(&source).unwrap(),
+            inputs: vec![],
+            outputs: vec![(0, net)],
+        });
+
+        self.constants.insert(value, net);
+        net
+    }
+}
+
+/// Builds a project from a Yosys JSON netlist, as written by `write_json`
+/// after `synth`.
+///
+/// Every module becomes a circuit with one single bit pin per port bit, ports
+/// sorted by name and wide ports split into `name_idx`. Gate cells become
+/// AND, OR and NOT gates, other bitwise and multiplexer cells behavioural
+/// components, and instances of modules from the netlist subcircuits. Cells
+/// are placed and wired automatically. The top circuit is the module with
+/// the `top` attribute, or else the first one by name which no other module
+/// instantiates.
+pub fn import_yosys(json: &str) -> Result<ProjectFile, NetlistError> {
+    let netlist: YosysNetlist = serde_json::from_str(json).map_err(|error| NetlistError::Json(error.to_string()))?;
+
+    let mut circuits: HashMap<String, SavedCircuit> = HashMap::new();
+    for name in netlist.modules.keys() {
+        import_module(name, &netlist, &mut circuits, &mut Vec::new())?;
+    }
+
+    let top_circuit = netlist.modules.iter()
+        .find(|(_, module)| module.is_top())
+        .or_else(|| netlist.modules.iter().find(|(name, _)| {
+            !netlist.modules.values().any(|module| module.cells.values().any(|cell| cell.cell_type == **name))
+        }))
+        .or_else(|| netlist.modules.iter().next())
+        .map(|(name, _)| name.clone())
+        .ok_or(NetlistError::NoModules)?;
+
+    Ok(ProjectFile { top_circuit, circuits })
+}
+
+pub fn load_yosys<P>(path: P) -> Result<ProjectFile, Box<dyn Error>>
+    where
+        P: AsRef<Path>,
+{
+    Ok(import_yosys(&fs::read_to_string(path)?)?)
+}
+
+/// Imports a module after every module it instantiates, `stack` holds the
+/// modules being imported to report recursion.
+fn import_module(name: &str,
+                 netlist: &YosysNetlist,
+                 circuits: &mut HashMap<String, SavedCircuit>,
+                 stack: &mut Vec<String>)
+                 -> Result<(), NetlistError> {
+    if circuits.contains_key(name) {
+        return Ok(());
+    }
+
+    if let Some(start) = stack.iter().position(|module| module == name) {
+        let mut chain = stack[start..].to_vec();
+        chain.push(name.to_string());
+        return Err(NetlistError::RecursiveModule { chain });
+    }
+
+    let module = &netlist.modules[name];
+
+    stack.push(name.to_string());
+    for cell in module.cells.values() {
+        if netlist.modules.contains_key(&cell.cell_type) {
+            import_module(&cell.cell_type, netlist, circuits, stack)?;
+        }
+    }
+    stack.pop();
+
+    if let Some((port, direction)) = module.ports.iter()
+        .map(|(port, definition)| (port, &definition.direction))
+        .find(|(_, direction)| *direction != "input" && *direction != "output") {
+        return Err(NetlistError::UnsupportedPort {
+            module: name.to_string(),
+            port: port.clone(),
+            direction: direction.clone(),
+        });
+    }
+
+    let max_net = module.ports.values().flat_map(|port| port.bits.iter())
+        .chain(module.cells.values().flat_map(|cell| cell.connections.values().flatten()))
+        .filter_map(|bit| match bit {
+            YosysBit::Net(net) => Some(*net),
+            YosysBit::Constant(_) => None,
+        })
+        .max();
+
+    let mut builder = ModuleBuilder {
+        cells: Vec::new(),
+        next_net: max_net.map(|net| net + 1).unwrap_or(0),
+        constants: HashMap::new(),
+    };
+
+    let inputs = module.port_bits("input");
+    let outputs = module.port_bits("output");
+
+    for (label, bit) in inputs.iter() {
+        let net = builder.net(bit);
+        builder.cells.push(Cell { component: InputPin::create(label), inputs: vec![], outputs: vec![(0, net)] });
+    }
+
+    for (label, bit) in outputs.iter() {
+        let net = builder.net(bit);
+        builder.cells.push(Cell { component: OutputPin::create(label), inputs: vec![(0, net)], outputs: vec![] });
+    }
+
+    for (cell_name, cell) in module.cells.iter() {
+        let connection = |port: &str| cell.connections.get(port).map(Vec::as_slice).unwrap_or(&[]);
+
+        if let Some(saved) = circuits.get(&cell.cell_type) {
+            let submodule = &netlist.modules[&cell.cell_type];
+            let component = Subcircuit::from_saved_circuit(saved, &cell.cell_type);
+
+            // Pins follow the port bits of the submodule, inputs first
+            let mut pins = Vec::new();
+            for direction in ["input", "output"] {
+                for (port, definition) in submodule.ports.iter().filter(|(_, port)| port.direction == direction) {
+                    let bits = connection(port);
+                    for idx in 0..definition.bits.len() {
+                        pins.push(bits.get(idx).cloned());
+                    }
+                }
+            }
+
+            let mut cell = Cell { component, inputs: vec![], outputs: vec![] };
+            for (pin, bit) in pins.iter().enumerate() {
+                let Some(bit) = bit else { continue; };
+                let net = builder.net(bit);
+
+                match saved.pins[pin].direction {
+                    Direction::Input => cell.inputs.push((pin, net)),
+                    _ => cell.outputs.push((pin, net)),
+                }
+            }
+
+            builder.cells.push(cell);
+            continue;
+        }
+
+        let Some((ports, _)) = bitwise_cell(&cell.cell_type) else {
+            return Err(NetlistError::UnsupportedCell {
+                module: name.to_string(),
+                cell: cell_name.clone(),
+                cell_type: cell.cell_type.clone(),
+            });
+        };
+
+        let width = connection("Y").len();
+
+        if ports.iter().any(|port| connection(port).len() != width) {
+            return Err(NetlistError::WidthMismatch { module: name.to_string(), cell: cell_name.clone() });
+        }
+
+        for idx in 0..width {
+            let (ports, component) = bitwise_cell(&cell.cell_type).unwrap();
+
+            let inputs = ports.iter().enumerate()
+                .map(|(pin, port)| (pin, builder.net(&connection(port)[idx])))
+                .collect();
+            let output = builder.net(&connection("Y")[idx]);
+
+            builder.cells.push(Cell { component, inputs, outputs: vec![(ports.len(), output)] });
+        }
+    }
+
+    let too_large = |_| NetlistError::LayoutTooLarge { module: name.to_string() };
+    let (components, wires) = layout(builder.cells).map_err(too_large)?;
+
+    let input_names: Vec<&str> = inputs.iter().map(|(label, _)| label.as_str()).collect();
+    let output_names: Vec<&str> = outputs.iter().map(|(label, _)| label.as_str()).collect();
+    let (pins, bounds) = port_pins(&input_names, &output_names).map_err(too_large)?;
+
+    circuits.insert(name.to_string(), SavedCircuit { components, wires, bounds, pins });
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+//! Fixture helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use cirquil::core::canvas::location::Location;
+use cirquil::core::simulation::component::Component;
+use cirquil::serde::project::{SavedComponent, SavedWire};
+
+pub fn component(location: (i16, i16), component: Component) -> SavedComponent {
+    SavedComponent { location: Location::new(location.0, location.1), component }
+}
+
+pub fn wire(start: (i16, i16), end: (i16, i16)) -> SavedWire {
+    SavedWire { start: Location::new(start.0, start.1), end: Location::new(end.0, end.1) }
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use cirquil::core::analysis::{analyze_circuit, AnalysisError};
+use cirquil::core::analysis::minimize::{minimize, to_sum_of_products};
+use cirquil::core::canvas::location::Location;
+use cirquil::core::compiler::circuit::compile_circuit;
+use cirquil::core::expression::{BinaryOp, Expr};
+use cirquil::core::simulation::component::ComponentModel;
+use cirquil::core::simulation::components::logic::and_gate::AndGate;
+use cirquil::core::simulation::components::subcircuit::input_pin::InputPin;
+use cirquil::core::simulation::components::subcircuit::output_pin::OutputPin;
+use cirquil::core::simulation::value::Value;
+use cirquil::serde::project::{SavedCircuit, SavedCircuitBounds};
+
+use common::component;
+
+#[test]
+pub fn test_analyze_circuit() {
+    let saved = SavedCircuit {
+        components: vec![
+            component((10, 10), InputPin::create("a")),
+            component((10, -10), InputPin::create("b")),
+            component((40, 0), AndGate::from_bit_width(1)),
+            component((40, 0), OutputPin::create("y")),
+        ],
+        wires: vec![],
+        bounds: SavedCircuitBounds { start: Location::new(0, 0), end: Location::new(0, 0) },
+        pins: vec![],
+    };
+
+    let (circuit, _) = compile_circuit("main".to_string(), saved).unwrap();
+    let table = analyze_circuit(&circuit, 4).unwrap();
+
+    let outputs: Vec<String> = table.rows.iter().map(|row| row[0].to_bit_string(1)).collect();
+    assert_eq!(outputs, vec!["0", "0", "0", "1"]);
+    assert_eq!(table.get_expressions(), vec![("y".to_string(), Expr::binary(
+        BinaryOp::And, Expr::variable("a"), Expr::variable("b"),
+    ))]);
+
+    assert_eq!(table.to_csv(), "a,b,y\n0,0,0\n0,1,0\n1,0,0\n1,1,1\n");
+    assert!(table.to_markdown().starts_with("| a | b | y |\n|---|---|---|\n| 0 | 0 | 0 |\n"));
+    assert!(table.to_markdown().ends_with("- `y = a & b`\n"));
+
+    // The analysed copy does not disturb the original circuit
+    let y = circuit.components.iter().find(|c| matches!(c.model, ComponentModel::OutputPin(_))).unwrap();
+    let ComponentModel::OutputPin(pin) = &y.model else { unreachable!() };
+    assert_eq!(pin.value.get(), Value::default());
+
+    assert_eq!(analyze_circuit(&circuit, 1).unwrap_err(), AnalysisError::TooManyInputs { bits: 2, limit: 1 });
+}
+
+#[test]
+pub fn test_minimize() {
+    let variables = [Expr::variable("a"), Expr::variable("b"), Expr::variable("c")];
+
+    // Cyclic function without essential prime implicants
+    let minterms = [0, 1, 2, 5, 6, 7];
+    let implicants = minimize(&minterms, &[]);
+    assert_eq!(implicants.len(), 3);
+
+    let expression = to_sum_of_products(&implicants, &variables);
+    for row in 0..8u64 {
+        let value = expression.evaluate(&|name| (row >> (2 - (name.as_bytes()[0] - b'a')) & 1, 1)).0;
+        assert_eq!(value == 1, minterms.contains(&row), "row {}: {}", row, expression);
+    }
+
+    // Don't-cares are used to grow terms but never have to be covered
+    assert_eq!(to_sum_of_products(&minimize(&[1, 3], &[5, 7]), &variables).to_string(), "c");
+    assert_eq!(to_sum_of_products(&minimize(&[], &[1]), &variables), Expr::Constant(0));
+    assert_eq!(to_sum_of_products(&minimize(&[0, 1, 2, 3], &[4, 5, 6, 7]), &variables), Expr::Constant(1));
+}
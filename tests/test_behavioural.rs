@@ -0,0 +1,93 @@
+use cirquil::core::expression::parser::parse_expression;
+use cirquil::core::simulation::component::{Component, ComponentModel};
+use cirquil::core::simulation::components::behavioural::Behavioural;
+use cirquil::core::simulation::components::behavioural::definition::{Definition, DefinitionError};
+use cirquil::core::simulation::pin::Direction;
+use cirquil::core::simulation::value::Value;
+
+fn run(component: &Component, inputs: &[Value]) -> Vec<Value> {
+    for (idx, value) in inputs.iter().enumerate() {
+        component.set_pin_value(idx, *value);
+    }
+    component.propagate();
+
+    (inputs.len()..component.get_pins().len()).map(|idx| component.get_pin_value(idx)).collect()
+}
+
+#[test]
+pub fn test_expression_definition() {
+    let adder = Behavioural::from_definition("
+        # full adder
+        sum = a ^ b ^ cin
+        cout = a & b | cin & (a ^ b)
+    ").unwrap();
+
+    let directions: Vec<Direction> = adder.get_pins().iter().map(|pin| pin.direction).collect();
+    assert_eq!(directions, vec![Direction::Input, Direction::Input, Direction::Input, Direction::Output, Direction::Output]);
+
+    for n in 0..8u32 {
+        let inputs: Vec<Value> = (0..3).map(|bit| Value::create((n >> bit) & 1, 1)).collect();
+        let total = n.count_ones();
+        assert_eq!(run(&adder, &inputs), vec![Value::create(total & 1, 1), Value::create(total >> 1, 1)]);
+    }
+
+    // Unknown inputs only spoil outputs which depend on them
+    let and = Behavioural::create();
+    let unknown = Value::new(0, u32::MAX);
+    assert_eq!(run(&and, &[unknown, Value::create(0, 1)]), vec![Value::create(0, 1)]);
+    assert_eq!(run(&and, &[unknown, Value::create(1, 1)])[0].get_undefined() & 1, 1);
+    assert_eq!(run(&and, &[Value::new(1, u32::MAX), Value::create(0, 1)])[0].get_error() & 1, 1);
+
+    let wide = Behavioural::from_definition("input a[8], b[8]\noutput s[9]\ns = a + b").unwrap();
+    assert_eq!(run(&wide, &[Value::create(200, 8), Value::create(100, 8)]), vec![Value::create(300, 9)]);
+
+    let expression = parse_expression("a & ~(b | c) ^ d[3:1]").unwrap();
+    assert_eq!(parse_expression(&expression.to_string()).unwrap(), expression);
+}
+
+#[test]
+pub fn test_truth_table_definition() {
+    let mux = Behavioural::from_definition("
+        s a b | y
+        0 0 - | 0
+        0 1 - | 1
+        1 - 0 | 0
+        1 - 1 | 1
+    ").unwrap();
+
+    let [f, t] = [Value::create(0, 1), Value::create(1, 1)];
+    assert_eq!(run(&mux, &[f, t, f]), vec![t]);
+    assert_eq!(run(&mux, &[t, t, f]), vec![f]);
+    // Both choices are equal, so the select line does not matter
+    assert_eq!(run(&mux, &[Value::new(0, u32::MAX), t, t]), vec![t]);
+
+    // Rows which are not listed leave outputs undefined
+    let partial = Behavioural::from_definition("a | y\n1 | 1").unwrap();
+    assert_eq!(run(&partial, &[f])[0].get_undefined() & 1, 1);
+
+    let saved = serde_json::to_string(&mux).unwrap();
+    let loaded: Component = serde_json::from_str(&saved).unwrap();
+    let ComponentModel::Extension(extension) = &loaded.model else { panic!() };
+    let behavioural = extension.0.as_any().downcast_ref::<Behavioural>().unwrap();
+    assert_eq!(behavioural.definition.get_inputs().len(), 3);
+    assert_eq!(run(&loaded, &[f, t, f]), vec![t]);
+
+    // The definition is only saved as a property, so editing it changes the model
+    let edited = serde_json::to_string(&Behavioural::create()).unwrap().replace("y = a & b", "y = a | b");
+    let or: Component = serde_json::from_str(&edited).unwrap();
+    assert_eq!(run(&or, &[f, t]), vec![t]);
+    assert!(serde_json::from_str::<Component>(&edited.replace("y = a | b", "y = a |")).is_err());
+
+    // Pins follow the loaded definition, not the saved pins
+    let extra_output = edited.replace("y = a | b", "y = a | b\\nz = a & b");
+    let or_and: Component = serde_json::from_str(&extra_output).unwrap();
+    assert_eq!(or_and.get_pins().len(), 4);
+    assert_eq!(run(&or_and, &[f, t]), vec![t, f]);
+
+    assert_eq!(Definition::parse("a b | y\n0 1 | 1 1").unwrap_err(), DefinitionError {
+        line: 2,
+        message: "expected 1 bits, found 2".to_string(),
+    });
+    assert!(Definition::parse("input a\ny = a & b").is_err());
+    assert!(Definition::parse("y = a &").is_err());
+}
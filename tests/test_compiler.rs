@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use cirquil::core::canvas::location::Location;
+use cirquil::core::compiler::error::CompileError;
+use cirquil::core::compiler::project::compile_project;
+use cirquil::core::simulation::component::ComponentProperties;
+use cirquil::core::simulation::components::logic::and_gate::AndGate;
+use cirquil::core::simulation::components::subcircuit::input_pin::InputPin;
+use cirquil::core::simulation::components::subcircuit::Subcircuit;
+use cirquil::serde::project::{ProjectFile, SavedCircuit, SavedCircuitBounds, SavedComponent};
+
+fn circuit(components: Vec<SavedComponent>) -> SavedCircuit {
+    SavedCircuit {
+        components,
+        wires: vec![],
+        bounds: SavedCircuitBounds { start: Location::new(0, 0), end: Location::new(0, 0) },
+        pins: vec![],
+    }
+}
+
+fn project(top_circuit: &str, main: SavedCircuit) -> ProjectFile {
+    ProjectFile {
+        top_circuit: top_circuit.to_string(),
+        circuits: HashMap::from([("main".to_string(), main)]),
+    }
+}
+
+#[test]
+pub fn test_compile_errors() {
+    let result = compile_project(project("top", circuit(vec![])));
+    assert_eq!(result.unwrap_err(), CompileError::MissingTopCircuit { name: "top".to_string() });
+
+    let subcircuit = Subcircuit::from_saved_circuit(&circuit(vec![]), "missing");
+    let uuid = subcircuit.uuid;
+    let result = compile_project(project("main", circuit(vec![
+        SavedComponent { location: Location::new(10, 20), component: subcircuit },
+    ])));
+    assert_eq!(result.unwrap_err(), CompileError::UnknownSubcircuit {
+        circuit: "main".to_string(),
+        component: uuid,
+        location: Location::new(10, 20),
+        name: "missing".to_string(),
+    });
+
+    let gate = AndGate::from_bit_width(1);
+    let mut clone = gate.clone();
+    clone.uuid = gate.uuid;
+    let result = compile_project(project("main", circuit(vec![
+        SavedComponent { location: Location::new(0, 0), component: gate.clone() },
+        SavedComponent { location: Location::new(50, 0), component: clone },
+    ])));
+    assert_eq!(result.unwrap_err(), CompileError::UuidClash {
+        circuit: "main".to_string(),
+        component: gate.uuid,
+        location: Location::new(50, 0),
+    });
+
+    let mut pin = InputPin::create("a");
+    pin.properties = ComponentProperties::new(vec![]);
+    let uuid = pin.uuid;
+    let result = compile_project(project("main", circuit(vec![
+        SavedComponent { location: Location::new(30, 10), component: pin },
+    ])));
+    assert_eq!(result.unwrap_err(), CompileError::MissingProperty {
+        circuit: "main".to_string(),
+        component: uuid,
+        location: Location::new(30, 10),
+        property: "label".to_string(),
+    });
+}
+
+#[test]
+pub fn test_recursive_subcircuit() {
+    let a_to_b = Subcircuit::from_saved_circuit(&circuit(vec![]), "b");
+    let b_to_a = Subcircuit::from_saved_circuit(&circuit(vec![]), "a");
+    let uuid = b_to_a.uuid;
+
+    let project = ProjectFile {
+        top_circuit: "main".to_string(),
+        circuits: HashMap::from([
+            ("main".to_string(), circuit(vec![
+                SavedComponent { location: Location::new(0, 0), component: Subcircuit::from_saved_circuit(&circuit(vec![]), "a") },
+            ])),
+            ("a".to_string(), circuit(vec![SavedComponent { location: Location::new(0, 0), component: a_to_b }])),
+            ("b".to_string(), circuit(vec![SavedComponent { location: Location::new(30, 40), component: b_to_a }])),
+        ]),
+    };
+
+    assert_eq!(compile_project(project).unwrap_err(), CompileError::RecursiveSubcircuit {
+        circuit: "b".to_string(),
+        component: uuid,
+        location: Location::new(30, 40),
+        chain: vec!["a".to_string(), "b".to_string(), "a".to_string()],
+    });
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use std::collections::HashMap;
+
+use cirquil::core::canvas::location::Location;
+use cirquil::core::compiler::drc::{check_project, DiagnosticKind, Severity};
+use cirquil::core::compiler::project::compile_project;
+use cirquil::core::simulation::components::logic::and_gate::AndGate;
+use cirquil::core::simulation::components::subcircuit::input_pin::InputPin;
+use cirquil::core::simulation::components::subcircuit::output_pin::OutputPin;
+use cirquil::core::simulation::components::tunnel::Tunnel;
+use cirquil::core::simulation::pin::Direction;
+use cirquil::serde::project::{ProjectFile, SavedCircuit, SavedCircuitBounds, SavedCircuitPin};
+
+use common::{component, wire};
+
+#[test]
+pub fn test_design_check() {
+    let main = SavedCircuit {
+        components: vec![
+            component((100, 100), AndGate::from_bit_width(1)),
+            component((40, 90), InputPin::create("a")),
+            component((40, 90), InputPin::create("b")),
+            component((140, 100), OutputPin::create("y")),
+            component((200, 200), Tunnel::from_name_width("lonely", 1)),
+        ],
+        wires: vec![
+            wire((40, 90), (70, 90)),
+            wire((100, 100), (140, 100)),
+            wire((140, 100), (140, 60)),
+        ],
+        bounds: SavedCircuitBounds { start: Location::new(0, 0), end: Location::new(0, 0) },
+        pins: vec![
+            SavedCircuitPin { location: Location::new(0, 0), label: "missing".to_string(), bit_width: 1, direction: Direction::Input },
+        ],
+    };
+
+    let project = ProjectFile {
+        top_circuit: "main".to_string(),
+        circuits: HashMap::from([("main".to_string(), main)]),
+    };
+
+    let (_, circuits) = compile_project(project.clone()).unwrap();
+    let diagnostics = check_project(&project, &circuits);
+
+    let has = |predicate: &dyn Fn(&DiagnosticKind) -> bool| diagnostics.iter().any(|d| predicate(&d.kind));
+
+    assert!(has(&|k| matches!(k, DiagnosticKind::UnconnectedInput { pin: 0, .. })));
+    assert!(has(&|k| matches!(k, DiagnosticKind::MultipleDrivers { drivers, .. } if drivers.len() == 2)));
+    assert!(has(&|k| matches!(k, DiagnosticKind::LonelyTunnel { label, .. } if label == "lonely")));
+    assert!(has(&|k| matches!(k, DiagnosticKind::UnmatchedPort { label, .. } if label == "missing")));
+    assert!(has(&|k| matches!(k, DiagnosticKind::DanglingWireEnd)));
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+
+    let dangling: Vec<Location> = diagnostics.iter()
+        .filter(|d| d.kind == DiagnosticKind::DanglingWireEnd)
+        .map(|d| d.location)
+        .collect();
+    assert_eq!(dangling, vec![Location::new(140, 60)]);
+}
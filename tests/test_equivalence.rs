@@ -0,0 +1,42 @@
+use std::rc::Rc;
+
+use cirquil::core::analysis::equivalence::{check_equivalence, Equivalence, EquivalenceError, EquivalenceOptions};
+use cirquil::core::compiler::project::compile_project;
+use cirquil::core::simulation::circuit::Circuit;
+use cirquil::core::synthesis::synthesize;
+
+fn synthesized(source: &str) -> Rc<Circuit> {
+    let (top, circuits) = compile_project(synthesize(source, "main").unwrap()).unwrap();
+    circuits.instantiated_circuits[top].0.clone()
+}
+
+#[test]
+pub fn test_equivalence() {
+    let reference = synthesized("y = (a ^ b) & c\nz = a");
+    let optimized = synthesized("z = a\ny = (a & ~b | ~a & b) & c");
+    let broken = synthesized("y = (a | b) & c\nz = a");
+
+    let options = EquivalenceOptions::default();
+    assert!(matches!(
+        check_equivalence(&reference, &optimized, &options).unwrap(),
+        Equivalence::Equivalent { vectors: 8, exhaustive: true }
+    ));
+
+    let Equivalence::Different(counterexample) = check_equivalence(&reference, &broken, &options).unwrap() else {
+        panic!("circuits should differ");
+    };
+    assert_eq!(counterexample.to_string(), "inputs a=1 b=1 c=1, y: 0 != 1");
+
+    // Random vectors find the difference as well
+    let random = EquivalenceOptions { max_exhaustive_bits: 0, random_vectors: 1000, ..options };
+    assert!(matches!(check_equivalence(&reference, &broken, &random).unwrap(), Equivalence::Different(_)));
+    assert!(matches!(
+        check_equivalence(&reference, &optimized, &random).unwrap(),
+        Equivalence::Equivalent { exhaustive: false, .. }
+    ));
+
+    assert_eq!(
+        check_equivalence(&reference, &synthesized("y = a & b & c"), &options).unwrap_err(),
+        EquivalenceError::MissingPort { name: "z".to_string() }
+    );
+}
@@ -0,0 +1,71 @@
+#![cfg(unix)]
+
+use std::env::temp_dir;
+use std::fs;
+use std::time::Instant;
+
+use cirquil::core::simulation::component::{Component, ComponentModel};
+use cirquil::core::simulation::components::external::{allow_external_commands, External, RESPONSE_TIMEOUT};
+use cirquil::core::simulation::value::Value;
+
+#[test]
+pub fn test_external() {
+    let model = temp_dir().join("cirquil_external_model.sh");
+    fs::write(&model, "while read a b; do echo $((a + b)) $((a & b)); done\n").unwrap();
+
+    let adder = External::from_command(&format!("sh {}", model.display()), "a[8], b[8]", "sum[8], and[8]").unwrap();
+    assert_eq!(adder.get_pins().len(), 4);
+
+    // Commands only run once allowed
+    adder.propagate();
+    assert_eq!(adder.get_pin_value(2).to_bit_string(8), "EEEEEEEE");
+    allow_external_commands(true);
+    adder.reset();
+
+    adder.set_pin_value(0, Value::create(5, 8));
+    adder.set_pin_value(1, Value::create(12, 8));
+    adder.propagate();
+    assert_eq!((adder.get_pin_value(2), adder.get_pin_value(3)), (Value::create(17, 8), Value::create(4, 8)));
+
+    // Loaded copies start their own process
+    let loaded: Component = serde_json::from_str(&serde_json::to_string(&adder).unwrap()).unwrap();
+    loaded.set_pin_value(0, Value::create(1, 8));
+    loaded.set_pin_value(1, Value::create(2, 8));
+    loaded.propagate();
+    assert_eq!(loaded.get_pin_value(2), Value::create(3, 8));
+
+    // The properties are the only saved configuration, edits change the pins
+    let saved = serde_json::to_string(&adder).unwrap();
+    assert!(saved.contains(r#""state":null"#));
+    let narrow: Component = serde_json::from_str(&saved.replace("sum[8], and[8]", "sum[4], and[8]")).unwrap();
+    assert_eq!(narrow.get_pins()[2].bit_width, 4);
+    narrow.set_pin_value(0, Value::create(9, 8));
+    narrow.set_pin_value(1, Value::create(8, 8));
+    narrow.propagate();
+    assert_eq!(narrow.get_pin_value(2), Value::create(1, 4));
+
+    let missing = External::from_command("cirquil_missing_model", "a", "y").unwrap();
+    missing.propagate();
+    assert_eq!(missing.get_pin_value(1).to_bit_string(1), "E");
+
+    let ComponentModel::Extension(model) = &missing.model else { panic!() };
+    assert!(model.0.as_any().downcast_ref::<External>().unwrap().has_failed());
+
+    assert!(External::from_command("", "a[40]", "y").is_err());
+
+    // A model which never answers fails after the timeout
+    let silent = External::from_command("sh -c cat>/dev/null", "a", "y").unwrap();
+    let start = Instant::now();
+    silent.propagate();
+    assert!(start.elapsed() >= RESPONSE_TIMEOUT);
+    assert_eq!(silent.get_pin_value(1).to_bit_string(1), "E");
+
+    // A model which answers without reading its input fails once the pipe fills
+    let deaf = External::from_command("yes 0", "a[32]", "y").unwrap();
+    deaf.set_pin_value(0, Value::create(u32::MAX, 32));
+    let failed = (0..100_000).any(|_| {
+        deaf.propagate();
+        deaf.get_pin_value(1).to_bit_string(1) == "E"
+    });
+    assert!(failed);
+}
@@ -0,0 +1,54 @@
+use cirquil::core::canvas::facing::Facing;
+use cirquil::core::canvas::location::Location;
+use cirquil::core::compiler::project::compile_project;
+use cirquil::core::simulation::circuit::Circuit;
+use cirquil::core::simulation::component::{Component, ComponentModel};
+use cirquil::logisim::converter::convert_logisim_project;
+use cirquil::logisim::parser::parse_logisim;
+
+fn find(circuit: &Circuit, predicate: impl Fn(&Component) -> bool) -> &Component {
+    circuit.components.iter().find(|c| predicate(c)).unwrap()
+}
+
+fn labeled<'a>(circuit: &'a Circuit, label: &str) -> &'a Component {
+    find(circuit, |c| c.get_properties().get_string("label").as_deref() == Some(label))
+}
+
+#[test]
+pub fn test_facing_rotation() {
+    let offset = Location::new(-30, 10);
+
+    assert_eq!(Facing::East.rotate(offset), offset);
+    assert_eq!(Facing::North.rotate(offset), Location::new(10, 30));
+    assert_eq!(Facing::South.rotate(offset), Location::new(-10, -30));
+    assert_eq!(Facing::West.rotate(offset), Location::new(30, -10));
+
+    assert_eq!(Facing::North.rotated_clockwise(), Facing::East);
+    assert_eq!(Facing::East.relative_to(Facing::West), Facing::West);
+}
+
+#[test]
+pub fn test_logisim_facing() {
+    let project = convert_logisim_project(parse_logisim("tests/data/rotated.circ").unwrap()).unwrap();
+    let (_, circuits) = compile_project(project).unwrap();
+    let (circuit, _) = &circuits.instantiated_circuits[0];
+
+    let and = find(circuit, |c| matches!(c.model, ComponentModel::AndGate(_)));
+    let not = find(circuit, |c| matches!(c.model, ComponentModel::NotGate(_)));
+
+    assert_eq!(and.get_facing(), Facing::North);
+    assert_eq!(not.get_facing(), Facing::West);
+
+    let wire = |component: &Component, pin: usize| component.get_pins()[pin].wire.get();
+
+    assert_eq!(wire(and, 0), wire(labeled(circuit, "b"), 0));
+    assert_eq!(wire(and, 1), wire(labeled(circuit, "a"), 0));
+    assert_eq!(wire(and, 2), wire(labeled(circuit, "y"), 0));
+    assert_eq!(wire(not, 0), wire(labeled(circuit, "c"), 0));
+    assert_eq!(wire(not, 1), wire(labeled(circuit, "d"), 0));
+    assert!(circuit.components.iter().all(|c| c.get_pins().iter().all(|pin| pin.wire.get().is_some())));
+
+    // Output pins are drawn facing west in Logisim terms
+    assert_eq!(labeled(circuit, "d").get_facing(), Facing::West);
+    assert_eq!(labeled(circuit, "y").get_facing(), Facing::North);
+}
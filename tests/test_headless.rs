@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::env::temp_dir;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use cirquil::core::canvas::location::Location;
+use cirquil::core::simulation::components::clock_generator::ClockGenerator;
+use cirquil::core::simulation::components::subcircuit::output_pin::OutputPin;
+use cirquil::headless::{HeadlessArgs, parse_timeout, run_player_headless};
+use cirquil::player::osc::TriggerType;
+use cirquil::serde::fs::serialize_to_file;
+use cirquil::serde::project::{ProjectFile, SavedCircuit, SavedCircuitBounds, SavedComponent};
+use cirquil::serde::summary::{RunSummary, StopReason};
+use cirquil::core::simulation::assertion::AssertionFailure;
+use cirquil::serde::workbench::{AssertionTrigger, OscilloscopeConfig, ProbePin, SavedAssertion, SavedProbe, WorkbenchFile};
+
+/// Saves a clock driving an output pin and a workbench probing the clock.
+fn save_clock_project(name: &str, assertions: Vec<SavedAssertion>) -> (PathBuf, PathBuf) {
+    let clock = ClockGenerator::create();
+    let uuid = clock.uuid;
+
+    let project = ProjectFile {
+        top_circuit: "main".to_string(),
+        circuits: HashMap::from([("main".to_string(), SavedCircuit {
+            components: vec![
+                SavedComponent { location: Location::new(0, 0), component: clock },
+                SavedComponent { location: Location::new(0, 0), component: OutputPin::create("q") },
+            ],
+            wires: vec![],
+            bounds: SavedCircuitBounds { start: Location::new(0, 0), end: Location::new(0, 0) },
+            pins: vec![],
+        })]),
+    };
+
+    let workbench = WorkbenchFile {
+        probes: vec![SavedProbe {
+            name: "clk".to_string(),
+            location: Location::new(0, 0),
+            subcircuit_path: vec![],
+            pins: vec![ProbePin { component: uuid, pin: 0 }],
+        }],
+        oscilloscope_config: OscilloscopeConfig {
+            rows: vec![],
+            last_row_id: 0,
+            trigger_type: TriggerType::Always,
+            trigger_source: String::new(),
+        },
+        last_probe_id: 1,
+        assertions,
+    };
+
+    let circuit_path = temp_dir().join(format!("cirquil_{}.cirq", name));
+    let workbench_path = temp_dir().join(format!("cirquil_{}.cirqw", name));
+    project.save(&circuit_path).unwrap();
+    serialize_to_file(&workbench, &workbench_path).unwrap();
+
+    (circuit_path, workbench_path)
+}
+
+fn args(name: &str, until: Option<&str>, assertions: Vec<SavedAssertion>) -> HeadlessArgs {
+    let (circuit_path, workbench_path) = save_clock_project(name, assertions);
+
+    HeadlessArgs {
+        circuit_path,
+        workbench_path,
+        cycles: 4,
+        replay_path: None,
+        trace_path: None,
+        vcd_path: None,
+        stimulus_path: None,
+        until: until.map(String::from),
+        timeout: None,
+        summary_path: None,
+    }
+}
+
+fn run(name: &str, until: Option<&str>, assertions: Vec<SavedAssertion>) -> RunSummary {
+    run_player_headless(args(name, until, assertions)).unwrap()
+}
+
+#[test]
+pub fn test_run_conditions() {
+    let summary = run("cycles", None, vec![]);
+    assert_eq!((summary.cycles, summary.stop_reason), (4, StopReason::Cycles));
+    assert_eq!(summary.probes["clk"], "0");
+
+    let summary = run("condition", Some("clk == 1"), vec![]);
+    assert_eq!((summary.cycles, summary.stop_reason), (1, StopReason::Condition));
+    assert_eq!(summary.probes["clk"], "1");
+
+    let summary = run("not_met", Some("clk > 1"), vec![]);
+    assert_eq!((summary.cycles, summary.stop_reason), (4, StopReason::ConditionNotMet));
+}
+
+#[test]
+pub fn test_run_assertions() {
+    let assertion = |name: &str, condition: &str, trigger: AssertionTrigger| SavedAssertion {
+        name: name.to_string(),
+        condition: condition.to_string(),
+        trigger,
+    };
+
+    let summary = run("assertions", None, vec![
+        assertion("tautology", "clk -> clk", AssertionTrigger::EveryCycle),
+        assertion("low_on_edge", "clk == 0", AssertionTrigger::RisingEdge("clk".to_string())),
+    ]);
+
+    // The first cycle has no previous clock value, so the first edge is in the third
+    assert_eq!((summary.cycles, summary.stop_reason), (3, StopReason::AssertionFailed));
+    assert_eq!(summary.failed_assertion, Some(AssertionFailure {
+        name: "low_on_edge".to_string(),
+        condition: "clk == 0".to_string(),
+        cycle: 2,
+    }));
+}
+
+#[test]
+pub fn test_run_timeout_summary() {
+    let summary_path = temp_dir().join("cirquil_timeout_summary.json");
+    let summary = run_player_headless(HeadlessArgs {
+        timeout: Some(Duration::ZERO),
+        summary_path: Some(summary_path.clone()),
+        ..args("timeout", None, vec![])
+    }).unwrap();
+    assert_eq!((summary.cycles, summary.stop_reason), (0, StopReason::Timeout));
+
+    let saved: RunSummary = serde_json::from_str(&fs::read_to_string(&summary_path).unwrap()).unwrap();
+    assert_eq!((saved.cycles, saved.stop_reason), (0, StopReason::Timeout));
+    assert_eq!(saved.probes, summary.probes);
+
+    assert_eq!(parse_timeout("1.5"), Ok(Duration::from_millis(1500)));
+    assert!(parse_timeout("-1").is_err());
+    assert!(parse_timeout("inf").is_err());
+    assert!(parse_timeout("NaN").is_err());
+    assert!(parse_timeout("soon").is_err());
+}
@@ -0,0 +1,119 @@
+use cirquil::core::analysis::equivalence::{check_equivalence, Equivalence, EquivalenceOptions};
+use cirquil::core::analysis::vectors::run_test_vectors;
+use cirquil::core::compiler::project::compile_project;
+use cirquil::core::synthesis::synthesize;
+use cirquil::serde::vectors::TestVectors;
+use cirquil::serde::yosys::{import_yosys, NetlistError};
+
+const ADDER: &str = r#"{
+  "creator": "Yosys",
+  "modules": {
+    "full_adder": {
+      "ports": {
+        "a": { "direction": "input", "bits": [ 2 ] },
+        "b": { "direction": "input", "bits": [ 3 ] },
+        "cin": { "direction": "input", "bits": [ 4 ] },
+        "sum": { "direction": "output", "bits": [ 5 ] },
+        "cout": { "direction": "output", "bits": [ 6 ] }
+      },
+      "cells": {
+        "$x1": { "type": "$_XOR_", "connections": { "A": [ 2 ], "B": [ 3 ], "Y": [ 7 ] } },
+        "$x2": { "type": "$_XOR_", "connections": { "A": [ 7 ], "B": [ 4 ], "Y": [ 5 ] } },
+        "$a1": { "type": "$_AND_", "connections": { "A": [ 2 ], "B": [ 3 ], "Y": [ 8 ] } },
+        "$a2": { "type": "$_AND_", "connections": { "A": [ 7 ], "B": [ 4 ], "Y": [ 9 ] } },
+        "$o1": { "type": "$_OR_", "connections": { "A": [ 8 ], "B": [ 9 ], "Y": [ 6 ] } }
+      }
+    },
+    "add2": {
+      "attributes": { "top": "00000000000000000000000000000001" },
+      "ports": {
+        "a": { "direction": "input", "bits": [ 2, 3 ] },
+        "b": { "direction": "input", "bits": [ 4, 5 ] },
+        "sum": { "direction": "output", "bits": [ 6, 7 ] },
+        "cout": { "direction": "output", "bits": [ 8 ] }
+      },
+      "cells": {
+        "fa0": { "type": "full_adder", "connections": { "a": [ 2 ], "b": [ 4 ], "cin": [ "0" ], "sum": [ 6 ], "cout": [ 9 ] } },
+        "fa1": { "type": "full_adder", "connections": { "a": [ 3 ], "b": [ 5 ], "cin": [ 9 ], "sum": [ 7 ], "cout": [ 8 ] } }
+      }
+    }
+  }
+}"#;
+
+#[test]
+pub fn test_import_yosys() {
+    let project = import_yosys(ADDER).unwrap();
+    assert_eq!(project.top_circuit, "add2");
+
+    let labels: Vec<&str> = project.circuits["add2"].pins.iter().map(|pin| pin.label.as_str()).collect();
+    assert_eq!(labels, vec!["a_0", "a_1", "b_0", "b_1", "cout", "sum_0", "sum_1"]);
+
+    // The full adder cell matches its definition
+    let mut full_adder = project.clone();
+    full_adder.top_circuit = "full_adder".to_string();
+    let (top, circuits) = compile_project(full_adder).unwrap();
+    let (reference_top, reference) = compile_project(synthesize("sum = a ^ b ^ cin\ncout = a & b | cin & (a ^ b)", "main").unwrap()).unwrap();
+
+    assert!(matches!(
+        check_equivalence(&reference.instantiated_circuits[reference_top].0, &circuits.instantiated_circuits[top].0, &EquivalenceOptions::default()).unwrap(),
+        Equivalence::Equivalent { exhaustive: true, .. }
+    ));
+
+    // Two bit adder with the first carry tied to zero
+    let mut source = "a_0 a_1 b_0 b_1 sum_0 sum_1 cout\n".to_string();
+    for a in 0..4 {
+        for b in 0..4 {
+            let sum = a + b;
+            source += &format!("{} {} {} {} {} {} {}\n", a & 1, a >> 1, b & 1, b >> 1, sum & 1, (sum >> 1) & 1, sum >> 2);
+        }
+    }
+
+    let (top, circuits) = compile_project(project).unwrap();
+    let results = run_test_vectors(&circuits.instantiated_circuits[top].0, &TestVectors::parse(&source).unwrap()).unwrap();
+    assert_eq!(results.len(), 16);
+    assert!(results.iter().all(|result| result.passed()));
+}
+
+#[test]
+pub fn test_import_errors() {
+    let flip_flop = r#"{"modules": {"top": {"cells": {"q": {"type": "$_DFF_P_", "connections": {"C": [2], "D": [3], "Q": [4]}}}}}}"#;
+    assert_eq!(import_yosys(flip_flop).unwrap_err(), NetlistError::UnsupportedCell {
+        module: "top".to_string(),
+        cell: "q".to_string(),
+        cell_type: "$_DFF_P_".to_string(),
+    });
+
+    let recursive = r#"{"modules": {"loop": {"cells": {"self": {"type": "loop", "connections": {}}}}}}"#;
+    assert_eq!(import_yosys(recursive).unwrap_err(), NetlistError::RecursiveModule {
+        chain: vec!["loop".to_string(), "loop".to_string()],
+    });
+
+    assert!(matches!(import_yosys("{").unwrap_err(), NetlistError::Json(_)));
+}
+
+/// Module `top` with `count` AND cells, each one combining two neighbouring
+/// bits of the input port.
+fn and_netlist(count: usize) -> String {
+    let inputs: Vec<String> = (0..=count).map(|idx| (2 + idx).to_string()).collect();
+    let outputs: Vec<String> = (0..count).map(|idx| (3 + count + idx).to_string()).collect();
+    let cells: Vec<String> = (0..count)
+        .map(|idx| format!(r#""and{}": {{"type": "$_AND_", "connections": {{"A": [{}], "B": [{}], "Y": [{}]}}}}"#,
+                           idx, inputs[idx], inputs[idx + 1], outputs[idx]))
+        .collect();
+
+    format!(r#"{{"modules": {{"top": {{
+        "ports": {{"a": {{"direction": "input", "bits": [{}]}}, "y": {{"direction": "output", "bits": [{}]}}}},
+        "cells": {{{}}}
+    }}}}}}"#, inputs.join(", "), outputs.join(", "), cells.join(", "))
+}
+
+#[test]
+pub fn test_import_large() {
+    let project = import_yosys(&and_netlist(100)).unwrap();
+    assert_eq!(project.circuits["top"].components.len(), 100 + 101 + 100);
+    compile_project(project).unwrap();
+
+    assert_eq!(import_yosys(&and_netlist(1000)).unwrap_err(), NetlistError::LayoutTooLarge {
+        module: "top".to_string(),
+    });
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use cirquil::core::canvas::location::Location;
+use cirquil::core::compiler::incremental::IncrementalCompiler;
+use cirquil::core::simulation::circuit::Circuit;
+use cirquil::core::simulation::component::{Component, ComponentModel};
+use cirquil::core::simulation::components::logic::and_gate::AndGate;
+use cirquil::core::simulation::components::logic::not_gate::NotGate;
+use cirquil::core::simulation::components::subcircuit::input_pin::InputPin;
+use cirquil::core::simulation::components::subcircuit::output_pin::OutputPin;
+use cirquil::core::simulation::components::subcircuit::Subcircuit;
+use cirquil::core::simulation::pin::Direction;
+use cirquil::core::simulation::value::Value;
+use cirquil::serde::project::{ProjectFile, SavedCircuit, SavedCircuitBounds, SavedCircuitPin};
+
+use common::{component, wire};
+
+fn find<'a>(circuit: &'a Circuit, label: &str) -> &'a Component {
+    circuit.components.iter()
+        .find(|c| {
+            c.get_properties().get("label")
+                .and_then(|p| p.as_string())
+                .is_some_and(|p| p.get() == label)
+        })
+        .unwrap()
+}
+
+fn output(circuit: &Circuit, label: &str) -> String {
+    match &find(circuit, label).model {
+        ComponentModel::OutputPin(pin) => pin.value.get().to_bit_string(1),
+        _ => panic!("{} is not an output pin", label),
+    }
+}
+
+#[test]
+pub fn test_incremental_update() {
+    let pin_a = component((0, 0), InputPin::create("a"));
+    let pin_y = component((40, 0), OutputPin::create("y"));
+
+    let inv = SavedCircuit {
+        components: vec![pin_a.clone(), component((40, 0), NotGate::from_bit_width(1)), pin_y.clone()],
+        wires: vec![wire((0, 0), (20, 0))],
+        bounds: SavedCircuitBounds { start: Location::new(-10, -10), end: Location::new(10, 10) },
+        pins: vec![
+            SavedCircuitPin { location: Location::new(-10, 0), label: "a".to_string(), bit_width: 1, direction: Direction::Input },
+            SavedCircuitPin { location: Location::new(10, 0), label: "y".to_string(), bit_width: 1, direction: Direction::Output },
+        ],
+    };
+
+    let mut main = SavedCircuit {
+        components: vec![
+            component((0, 0), InputPin::create("x")),
+            component((50, 0), Subcircuit::from_saved_circuit(&inv, "inv")),
+            component((60, 0), OutputPin::create("z")),
+        ],
+        wires: vec![wire((0, 0), (40, 0))],
+        bounds: SavedCircuitBounds { start: Location::new(0, 0), end: Location::new(0, 0) },
+        pins: vec![],
+    };
+
+    let project = ProjectFile {
+        top_circuit: "main".to_string(),
+        circuits: HashMap::from([("main".to_string(), main.clone()), ("inv".to_string(), inv.clone())]),
+    };
+
+    let mut compiler = IncrementalCompiler::new(project).unwrap();
+    let top = |compiler: &IncrementalCompiler| compiler.get_circuits().instantiated_circuits[compiler.get_top_circuit()].0.clone();
+
+    let circuit = top(&compiler);
+    let ComponentModel::InputPin(pin) = &find(&circuit, "x").model else { unreachable!() };
+    pin.value.set(Value::new(1, 0));
+    top(&compiler).propagate_all();
+    assert_eq!(output(&top(&compiler), "z"), "0");
+
+    // Editing the top circuit keeps the subcircuit instance and the input state
+    let child = compiler.get_circuits().instantiated_circuits[0].0.clone();
+    main.components.push(component((200, 200), AndGate::from_bit_width(1)));
+    compiler.update_circuit("main", main.clone()).unwrap();
+
+    assert!(Rc::ptr_eq(&child, &compiler.get_circuits().instantiated_circuits[0].0));
+    assert_eq!(top(&compiler).components.len(), 4);
+    assert_eq!(output(&top(&compiler), "z"), "0");
+
+    // Editing the subcircuit repoints its parent to the rebuilt instance
+    let buffer = SavedCircuit {
+        components: vec![pin_a, pin_y],
+        wires: vec![wire((0, 0), (40, 0))],
+        ..inv.clone()
+    };
+    compiler.update_circuit("inv", buffer).unwrap();
+    top(&compiler).propagate_all();
+
+    assert!(!Rc::ptr_eq(&child, &compiler.get_circuits().instantiated_circuits[0].0));
+    assert_eq!(output(&top(&compiler), "z"), "1");
+
+    // Adding a subcircuit instantiates the tree again and keeps the state
+    main.components.push(component((50, 100), Subcircuit::from_saved_circuit(&inv, "inv")));
+    compiler.update_circuit("main", main).unwrap();
+
+    assert_eq!(compiler.get_circuits().instantiated_circuits.len(), 3);
+    assert_eq!(output(&top(&compiler), "z"), "1");
+    let circuit = top(&compiler);
+    let ComponentModel::InputPin(pin) = &find(&circuit, "x").model else { unreachable!() };
+    assert_eq!(pin.value.get().get_raw_value(), 1);
+}
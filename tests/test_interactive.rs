@@ -0,0 +1,39 @@
+use std::env::temp_dir;
+
+use cirquil::core::synthesis::synthesize;
+use cirquil::headless::interactive::{InteractiveArgs, run_interactive};
+
+#[test]
+pub fn test_interactive() {
+    let circuit_path = temp_dir().join("cirquil_interactive.cirq");
+    synthesize("y = a & b", "main").unwrap().save(&circuit_path).unwrap();
+
+    let script = "\
+        poke a 1\n\
+        poke b 0b1\n\
+        peek y\n\
+        step 2\n\
+        poke c 1\n\
+        list\n\
+        reset\n\
+        peek y\n\
+        quit\n\
+        step\n";
+
+    let mut output = Vec::new();
+    run_interactive(InteractiveArgs { circuit_path, workbench_path: None }, script.as_bytes(), &mut output).unwrap();
+
+    let expected = "\
+        a = 1\n\
+        b = 1\n\
+        y = 1\n\
+        cycle 2\n\
+        ERROR: Top circuit has no input labelled c and no component has this UUID\n\
+        input a = 1\n\
+        input b = 1\n\
+        output y = 1\n\
+        cycle 0\n\
+        y = E\n";
+
+    assert_eq!(String::from_utf8(output).unwrap(), expected);
+}
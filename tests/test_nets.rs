@@ -0,0 +1,172 @@
+use std::env::temp_dir;
+use std::fs;
+
+use cirquil::core::canvas::location::Location;
+use cirquil::core::compiler::project::{compile_project, InstantiatedCircuits};
+use cirquil::core::simulation::circuit::Circuit;
+use cirquil::core::simulation::wire::WireIdx;
+use cirquil::logisim::converter::convert_logisim_project;
+use cirquil::logisim::converter::error::ConvertError;
+use cirquil::logisim::parser::parse_logisim;
+
+fn load(path: &str) -> InstantiatedCircuits {
+    let project = convert_logisim_project(parse_logisim(path).unwrap()).unwrap();
+    let (_, circuits) = compile_project(project).unwrap();
+
+    circuits
+}
+
+fn pin_wire(circuit: &Circuit, label: &str) -> Option<WireIdx> {
+    circuit.components.iter()
+        .find(|c| {
+            c.get_properties().get("label")
+                .and_then(|p| p.as_string())
+                .is_some_and(|p| p.get() == label)
+        })
+        .unwrap()
+        .get_pins()[0].wire.get()
+}
+
+#[test]
+pub fn test_junctions_and_crossings() {
+    let circuits = load("tests/data/junctions.circ");
+    let (circuit, _) = &circuits.instantiated_circuits[0];
+    let canvas = &circuits.canvas_circuits[0];
+
+    let a = pin_wire(circuit, "a").unwrap();
+    let d = pin_wire(circuit, "d").unwrap();
+    let f = pin_wire(circuit, "f").unwrap();
+
+    // T-junction joins the gate input, crossing does not join d to a
+    assert_eq!(pin_wire(circuit, "c"), Some(a));
+    assert_eq!(pin_wire(circuit, "e"), Some(d));
+    assert_ne!(a, d);
+    assert_eq!(circuit.get_wire(a).connected_components.len(), 3);
+
+    // Pin in the middle of a wire splits it
+    assert_eq!(pin_wire(circuit, "g"), Some(f));
+    assert_eq!(canvas.wires[f].segments.len(), 2);
+
+    // Gate output touching a pin directly forms a net without segments
+    let b = pin_wire(circuit, "b").unwrap();
+    assert!(canvas.wires[b].segments.is_empty());
+    assert_eq!(circuit.get_wire(b).connected_components.len(), 2);
+
+    assert_eq!(canvas.wires[a].nodes, vec![Location::new(150, 100)]);
+    assert!(canvas.wires[d].nodes.is_empty());
+    assert!(canvas.wires.iter().enumerate().all(|(i, w)| w.wire == i));
+    assert_eq!(canvas.wires.len(), circuit.wires.len());
+}
+
+#[test]
+pub fn test_tunnels() {
+    let circuits = load("tests/data/tunnels.circ");
+    let (circuit, _) = &circuits.instantiated_circuits[0];
+    let canvas = &circuits.canvas_circuits[0];
+
+    let a = pin_wire(circuit, "a").unwrap();
+
+    for label in ["b", "y", "z"] {
+        assert_eq!(pin_wire(circuit, label), Some(a));
+    }
+
+    assert_eq!(circuit.wires.len(), 1);
+    assert_eq!(canvas.wires[a].nodes, vec![Location::new(100, 80)]);
+}
+
+/// Hand-written in the Logisim 2.7.1 format, not saved by Logisim: inputs
+/// `a` and `b` feed an AND and an OR gate, and tunnels carry the gate outputs
+/// to the output pins `and` and `or_copy`.
+#[test]
+pub fn test_logisim_gate_tunnels() {
+    let circuits = load("tests/data/gate_tunnels.circ");
+    let (circuit, _) = &circuits.instantiated_circuits[0];
+    let canvas = &circuits.canvas_circuits[0];
+
+    let a = pin_wire(circuit, "a").unwrap();
+    let b = pin_wire(circuit, "b").unwrap();
+    let and = pin_wire(circuit, "and").unwrap();
+    let or = pin_wire(circuit, "or").unwrap();
+
+    // Wires split at T-junctions, the vertical of a only crosses b
+    assert_ne!(a, b);
+    assert_eq!(canvas.wires[a].nodes, vec![Location::new(120, 100)]);
+    assert_eq!(canvas.wires[b].nodes, vec![Location::new(100, 200)]);
+    assert_eq!(circuit.get_wire(a).connected_components.len(), 3);
+    assert_eq!(circuit.get_wire(b).connected_components.len(), 3);
+
+    // Tunnels join the gate outputs to the far pins
+    assert_eq!(pin_wire(circuit, "or_copy"), Some(or));
+    assert_ne!(and, or);
+    assert_eq!(circuit.get_wire(and).connected_components.len(), 4);
+    assert_eq!(circuit.get_wire(or).connected_components.len(), 5);
+    assert_eq!(canvas.wires[or].nodes, vec![Location::new(230, 210)]);
+
+    assert_eq!(circuit.wires.len(), 4);
+}
+
+#[test]
+pub fn test_unknown_component() {
+    let path = temp_dir().join("cirquil_unknown_component.circ");
+    fs::write(&path, r##"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<project source="2.7.1" version="1.0">
+  <lib desc="#Wiring" name="0"/>
+  <main name="main"/>
+  <circuit name="main">
+    <wire from="(60,60)" to="(100,60)"/>
+    <comp lib="0" loc="(100,60)" name="Splitter"/>
+  </circuit>
+</project>
+"##).unwrap();
+
+    assert_eq!(convert_logisim_project(parse_logisim(&path).unwrap()).unwrap_err(), ConvertError::UnknownComponent {
+        circuit: "main".to_string(),
+        name: "Splitter".to_string(),
+        library: 0,
+        location: Location::new(100, 60),
+    });
+}
+
+#[test]
+pub fn test_unlabeled_components() {
+    let path = temp_dir().join("cirquil_unlabeled_pin.circ");
+    fs::write(&path, r##"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<project source="2.7.1" version="1.0">
+  <lib desc="#Wiring" name="0"/>
+  <main name="main"/>
+  <circuit name="main">
+    <wire from="(60,60)" to="(100,60)"/>
+    <comp lib="0" loc="(60,60)" name="Pin"/>
+    <comp lib="0" loc="(100,60)" name="Pin">
+      <a name="output" val="true"/>
+    </comp>
+  </circuit>
+</project>
+"##).unwrap();
+
+    let project = convert_logisim_project(parse_logisim(&path).unwrap()).unwrap();
+    let labels: Vec<String> = project.circuits["main"].components.iter()
+        .map(|saved| saved.component.get_properties().get_string("label").unwrap())
+        .collect();
+    assert_eq!(labels, vec!["".to_string(), "".to_string()]);
+
+    // Tunnels are connected by their labels, so they need one
+    let path = temp_dir().join("cirquil_unlabeled_tunnel.circ");
+    fs::write(&path, r##"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<project source="2.7.1" version="1.0">
+  <lib desc="#Wiring" name="0"/>
+  <main name="main"/>
+  <circuit name="main">
+    <wire from="(60,60)" to="(100,60)"/>
+    <comp lib="0" loc="(60,60)" name="Tunnel"/>
+  </circuit>
+</project>
+"##).unwrap();
+
+    assert_eq!(convert_logisim_project(parse_logisim(&path).unwrap()).unwrap_err(), ConvertError::MissingParameter {
+        circuit: "main".to_string(),
+        name: "Tunnel".to_string(),
+        parameter: "label".to_string(),
+        location: Location::new(60, 60),
+    });
+}
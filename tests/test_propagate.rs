@@ -2,10 +2,12 @@ use std::cell::Cell;
 use std::time::Instant;
 
 use cirquil::core::simulation::circuit::Circuit;
+use cirquil::core::simulation::component::ComponentModel;
 use cirquil::core::simulation::components::clock_generator::ClockGenerator;
 use cirquil::core::simulation::components::logic::and_gate::AndGate;
 use cirquil::core::simulation::components::logic::not_gate::NotGate;
 use cirquil::core::simulation::components::logic::or_gate::OrGate;
+use cirquil::core::simulation::components::subcircuit::input_pin::InputPin;
 use cirquil::core::simulation::value::Value;
 use cirquil::core::simulation::wire::Wire;
 
@@ -37,6 +39,7 @@ pub fn test_propagate() {
         clock_generators: vec![2],
         input_pins: vec![],
         output_pins: vec![],
+        contentions: Default::default(),
     };
 
     println!("{:?} {:?}", circuit.components, circuit.wires);
@@ -82,6 +85,7 @@ pub fn test_or() {
         clock_generators: vec![1],
         input_pins: vec![],
         output_pins: vec![],
+        contentions: Default::default(),
     };
 
     println!("{:?} {:?}", circuit.components, circuit.wires);
@@ -123,6 +127,7 @@ pub fn test_not() {
         clock_generators: vec![0],
         input_pins: vec![],
         output_pins: vec![],
+        contentions: Default::default(),
     };
 
     println!("{:?} {:?}", circuit.components, circuit.wires);
@@ -146,4 +151,75 @@ pub fn test_not() {
     }
 
     println!("{:?} {:?} MHz", start.elapsed(), 1f64 / (start.elapsed().as_micros() as f64 / 1_000_000f64));
-}
\ No newline at end of file
+}
+
+#[test]
+pub fn test_reset() {
+    let clock = ClockGenerator::create();
+    let not = NotGate::from_bit_width(1);
+
+    let wire = Wire { value: Cell::new(Default::default()), connected_components: vec![(0, 0), (1, 0)] };
+
+    clock.set_pin_wire(0, Some(0));
+    not.set_pin_wire(0, Some(0));
+
+    let circuit = Circuit {
+        components: vec![clock, not],
+        wires: vec![wire],
+        clock_generators: vec![0],
+        input_pins: vec![],
+        output_pins: vec![],
+        contentions: Default::default(),
+    };
+
+    circuit.propagate_all();
+    circuit.tick();
+    circuit.propagate_ticked();
+
+    assert_eq!(circuit.wires[0].value.get().get_defined_value() & 1, 1);
+
+    circuit.reset();
+
+    assert_eq!(circuit.wires[0].value.get(), Value::default());
+    assert_eq!(circuit.components[1].get_pin_value(1), Value::default());
+
+    circuit.propagate_all();
+
+    assert_eq!(circuit.wires[0].value.get().get_defined_value() & 1, 0);
+}
+
+#[test]
+pub fn test_contention() {
+    let low = InputPin::create("low");
+    let high = InputPin::create("high");
+    let not = NotGate::from_bit_width(1);
+
+    if let ComponentModel::InputPin(p) = &low.model { p.value.set(Value::create(0, 1)) }
+    if let ComponentModel::InputPin(p) = &high.model { p.value.set(Value::create(1, 1)) }
+
+    let wire = Wire { value: Cell::new(Default::default()), connected_components: vec![(0, 0), (1, 0), (2, 0)] };
+
+    low.set_pin_wire(0, Some(0));
+    high.set_pin_wire(0, Some(0));
+    not.set_pin_wire(0, Some(0));
+
+    let circuit = Circuit {
+        components: vec![low, high, not],
+        wires: vec![wire],
+        clock_generators: vec![],
+        input_pins: vec![],
+        output_pins: vec![],
+        contentions: Default::default(),
+    };
+
+    circuit.propagate_all();
+
+    assert!(circuit.wires[0].value.get().is_error());
+
+    let log = circuit.contentions.borrow();
+    let contentions = log.get_contentions();
+    assert_eq!(contentions.len(), 1);
+    assert_eq!(contentions[0].wire, 0);
+    assert_eq!(contentions[0].drivers.len(), 2);
+    assert_eq!(contentions[0].conflicting_bits(), 1);
+}
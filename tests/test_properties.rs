@@ -0,0 +1,52 @@
+use cirquil::core::simulation::component::ComponentProperties;
+use cirquil::core::simulation::components::logic::and_gate::AndGate;
+use cirquil::core::simulation::components::tunnel::Tunnel;
+use cirquil::core::simulation::property::{BoolProperty, EnumProperty, IntegerProperty, Property, PropertyError, PropertySchema, StringProperty};
+
+#[test]
+pub fn test_schema_defaults() {
+    let gate = AndGate::from_properties(ComponentProperties::default());
+    assert!(gate.get_pins().iter().all(|pin| pin.bit_width == 1));
+    assert!(gate.get_property("bit_width").as_bounded_integer().is_some());
+
+    let gate = AndGate::from_bit_width(4);
+    assert!(gate.get_pins().iter().all(|pin| pin.bit_width == 4));
+
+    // Integers saved by older versions are read as bounded integers
+    let legacy = ComponentProperties::new(vec![
+        ("bit_width".to_string(), Property::Integer(IntegerProperty::new(8))),
+    ]);
+    assert!(legacy.validate(&AndGate::schema()).is_empty());
+    assert!(AndGate::from_properties(legacy).get_pins().iter().all(|pin| pin.bit_width == 8));
+
+    let tunnel = Tunnel::from_properties(ComponentProperties::default());
+    assert_eq!(tunnel.get_properties().get_string("name"), Some("Tunnel".to_string()));
+}
+
+#[test]
+pub fn test_schema_validation() {
+    let properties = ComponentProperties::new(vec![
+        ("bit_width".to_string(), Property::Integer(IntegerProperty::new(40))),
+    ]);
+    assert_eq!(properties.validate(&AndGate::schema()), vec![
+        PropertyError::OutOfBounds { name: "bit_width".to_string(), min: 1, max: 33, value: 40 },
+    ]);
+
+    let properties = ComponentProperties::new(vec![
+        ("name".to_string(), Property::Bool(BoolProperty::new(true))),
+    ]);
+    assert_eq!(properties.validate(&Tunnel::schema()), vec![
+        PropertyError::Missing { name: "bit_width".to_string() },
+        PropertyError::WrongType { name: "name".to_string(), expected: "string", found: "bool" },
+    ]);
+
+    let schema = PropertySchema::new("gate", "Gate Type", Property::Enum(EnumProperty::new(&["and", "or"], "and")));
+    let property = Property::Enum(EnumProperty::new(&["and", "or", "xor"], "xor"));
+    assert_eq!(schema.validate(&property), Err(PropertyError::UnknownVariant { name: "gate".to_string(), value: "xor".to_string() }));
+    assert!(schema.validate(&Property::String(StringProperty::new("and".to_string()))).is_err());
+
+    let property = EnumProperty::new(&["and", "or"], "and");
+    assert!(property.set("or").is_ok());
+    assert!(property.set("xor").is_err());
+    assert_eq!(property.get(), "or");
+}
@@ -0,0 +1,110 @@
+use std::any::Any;
+use std::cell::Cell;
+
+use egui::{Context, Rect, Shape};
+
+use cirquil::core::canvas::location::Location;
+use cirquil::core::registry::{ComponentDescriptor, find_descriptor, get_descriptors, register};
+use cirquil::core::simulation::component::{Behaviour, Component, ComponentModel, ComponentPins, ComponentProperties};
+use cirquil::core::simulation::components::extension::{Extension, ExtensionModel};
+use cirquil::core::simulation::pin::{Direction, Pin};
+use cirquil::core::simulation::value::Value;
+use cirquil::core::uuid::make_uuid;
+use cirquil::gui::component::{AsShapes, Bounds, Poke};
+
+#[derive(Debug, Clone, Default)]
+struct Counter {
+    count: Cell<u32>,
+}
+
+impl Behaviour for Counter {
+    fn propagate(&self, pins: &ComponentPins, _properties: &ComponentProperties) {
+        pins.set_value(0, Value::create(self.count.get(), 8))
+    }
+}
+
+impl AsShapes for Counter {
+    fn as_shapes(&self, _context: &Context) -> Vec<Shape> { vec![] }
+}
+
+impl Bounds for Counter {
+    fn get_bounds(&self) -> Rect { Rect::ZERO }
+}
+
+impl Poke for Counter {}
+
+impl Extension for Counter {
+    fn get_type(&self) -> &'static str { "test_counter" }
+    fn get_name(&self) -> &'static str { "Counter" }
+
+    fn ticks(&self) -> bool { true }
+    fn tick(&self) { self.count.set(self.count.get() + 1) }
+    fn reset(&self) { self.count.set(0) }
+
+    fn save(&self) -> Result<serde_json::Value, String> {
+        Ok(self.count.get().into())
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Extension> { Box::new(self.clone()) }
+    fn as_any(&self) -> &dyn Any { self }
+}
+
+fn create_counter() -> Component {
+    let pins = vec![
+        Pin {
+            value: Cell::new(Default::default()),
+            bit_width: 8,
+            direction: Direction::Output,
+            wire: Cell::new(None),
+            location: Location::new(0, 0),
+        },
+    ];
+
+    Component {
+        pins: ComponentPins::new(pins),
+        properties: ComponentProperties::default(),
+        model: ComponentModel::Extension(ExtensionModel::new(Counter::default())),
+        uuid: make_uuid(),
+    }
+}
+
+fn load_counter(state: serde_json::Value) -> Result<Box<dyn Extension>, String> {
+    let count = state.as_u64().ok_or("Counter state is not a number")?;
+    Ok(Box::new(Counter { count: Cell::new(count as u32) }))
+}
+
+#[test]
+pub fn test_register_extension() {
+    register(ComponentDescriptor {
+        component_type: "test_counter",
+        name: "Counter",
+        category: Some("Testing"),
+        create: create_counter,
+        load: Some(load_counter),
+        logisim: None,
+    });
+
+    assert!(find_descriptor("and_gate").is_some());
+    assert_eq!(get_descriptors().iter().filter(|d| d.component_type == "test_counter").count(), 1);
+
+    let counter = (find_descriptor("test_counter").unwrap().create)();
+    assert_eq!(counter.model.get_name(), "Counter");
+
+    let ComponentModel::Extension(model) = &counter.model else { panic!() };
+    model.0.tick();
+    model.0.tick();
+    counter.propagate();
+    assert_eq!(counter.get_pin_value(0), Value::create(2, 8));
+
+    let saved = serde_json::to_string(&counter).unwrap();
+    let loaded: Component = serde_json::from_str(&saved).unwrap();
+    loaded.propagate();
+    assert_eq!(loaded.get_pin_value(0), Value::create(2, 8));
+
+    loaded.reset();
+    loaded.propagate();
+    assert_eq!(loaded.get_pin_value(0), Value::create(0, 8));
+
+    let unknown = saved.replace("test_counter", "missing_counter");
+    assert!(serde_json::from_str::<Component>(&unknown).is_err());
+}
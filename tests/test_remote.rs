@@ -0,0 +1,68 @@
+use std::env::temp_dir;
+use std::io::{ErrorKind, Read};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use cirquil::core::synthesis::synthesize;
+use cirquil::headless::session::Session;
+use cirquil::remote::client::{RemoteClient, RemoteError};
+use cirquil::remote::RemoteHandler;
+use cirquil::remote::server::RemoteServer;
+
+#[test]
+pub fn test_remote() {
+    let circuit_path = temp_dir().join("cirquil_remote.cirq");
+    synthesize("y = a & b", "main").unwrap().save(&circuit_path).unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    // Circuits are not Send, the session is loaded on the serving thread
+    let server = thread::spawn(move || {
+        let mut session = Session::load(circuit_path, None).unwrap();
+        let server = RemoteServer::from_listener(listener).unwrap();
+
+        RemoteHandler::default().serve(&mut session, &server);
+    });
+
+    let mut client = RemoteClient::connect(address).unwrap();
+
+    client.write("a", 1).unwrap();
+    client.write("b", 1).unwrap();
+    assert_eq!(client.read("y").unwrap(), "1");
+
+    assert!(matches!(client.write("c", 1), Err(RemoteError::Server(_))));
+    assert!(matches!(client.subscribe(&["z"]), Err(RemoteError::Server(_))));
+
+    client.subscribe(&["y"]).unwrap();
+    assert_eq!(client.tick(3).unwrap(), 3);
+
+    let samples = client.take_samples();
+    assert_eq!(samples.iter().map(|sample| sample.cycle).collect::<Vec<usize>>(), vec![1, 2, 3]);
+    assert_eq!(samples[2].values["y"], "1");
+
+    client.reset().unwrap();
+    client.propagate().unwrap();
+    assert_eq!(client.read("y").unwrap(), "E");
+    assert_eq!(client.list().unwrap()[0], ("input a".to_string(), "x".to_string()));
+
+    client.shutdown().unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+pub fn test_remote_server_drop() {
+    let server = RemoteServer::bind("127.0.0.1:0").unwrap();
+    let address = server.address;
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    thread::sleep(Duration::from_millis(200));
+    drop(server);
+
+    // The port is free again and the client sees the connection closed
+    drop(TcpListener::bind(address).unwrap());
+    assert_eq!(stream.read(&mut [0; 16]).unwrap_or(0), 0);
+
+    assert_eq!(RemoteServer::bind("0.0.0.0:0").err().unwrap().kind(), ErrorKind::InvalidInput);
+}
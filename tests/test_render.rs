@@ -0,0 +1,41 @@
+use cirquil::core::canvas::location::Location;
+use cirquil::core::compiler::project::compile_project;
+use cirquil::core::simulation::probe::{CanvasProbe, Probe};
+use cirquil::core::synthesis::synthesize;
+use cirquil::gui::svg::{render_svg, SvgOptions};
+use cirquil::logisim::converter::convert_logisim_project;
+use cirquil::logisim::parser::parse_logisim;
+
+#[test]
+pub fn test_render_svg() {
+    let project = convert_logisim_project(parse_logisim("tests/data/tunnels.circ").unwrap()).unwrap();
+    let (top, circuits) = compile_project(project).unwrap();
+
+    let svg = render_svg(&circuits, top, &[], &SvgOptions::default());
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.ends_with("</svg>\n"));
+
+    // Every wire segment is drawn, tunnel labels are text
+    let segments: usize = circuits.canvas_circuits[circuits.instantiated_circuits[top].1].wires.iter()
+        .map(|wire| wire.segments.len())
+        .sum();
+    assert_eq!(svg.matches("<line ").count(), segments);
+    assert_eq!(svg.matches(">net</text>").count(), 3);
+    assert!(!svg.contains("stroke=\"#006400\""));
+
+    // Values and probe markers
+    let (top, circuits) = compile_project(synthesize("y = a & b", "main").unwrap()).unwrap();
+    circuits.reset();
+
+    let probe = CanvasProbe {
+        location: Location::new(0, 0),
+        probe: Probe { name: "and_out".to_string(), circuit: top, wire: 0 },
+    };
+
+    let plain = render_svg(&circuits, top, &[], &SvgOptions::default());
+    let svg = render_svg(&circuits, top, &[probe], &SvgOptions { values: true });
+    assert!(svg.contains(">and_out</text>"));
+    assert!(!plain.contains("and_out"));
+    assert!(svg.matches("<circle ").count() > plain.matches("<circle ").count());
+    assert!(svg.contains("stroke=\"#00008b\""));
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use std::collections::HashMap;
+
+use cirquil::core::canvas::location::Location;
+use cirquil::core::compiler::project::{compile_project, InstantiatedCircuits};
+use cirquil::core::simulation::circuit::CircuitIdx;
+use cirquil::core::simulation::component::ComponentModel;
+use cirquil::core::simulation::components::input::button::InputButton;
+use cirquil::core::simulation::components::subcircuit::input_pin::InputPin;
+use cirquil::core::simulation::components::subcircuit::output_pin::OutputPin;
+use cirquil::core::simulation::components::subcircuit::Subcircuit;
+use cirquil::core::simulation::pin::Direction;
+use cirquil::core::simulation::stimulus::Stimulus;
+use cirquil::core::synthesis::synthesize;
+use cirquil::serde::project::{ProjectFile, SavedCircuit, SavedCircuitBounds, SavedCircuitPin};
+use cirquil::serde::stimulus::StimulusFile;
+
+use common::component;
+
+#[test]
+pub fn test_stimulus() {
+    let (top, circuits) = compile_project(synthesize("y = a & b", "main").unwrap()).unwrap();
+    circuits.reset();
+
+    let output = || {
+        let circuit = &circuits.instantiated_circuits[top].0;
+        circuit.components.iter()
+            .find_map(|component| match &component.model {
+                ComponentModel::OutputPin(pin) => Some(pin.value.get().to_bit_string(1)),
+                _ => None,
+            })
+            .unwrap()
+    };
+
+    let file: StimulusFile = serde_json::from_str(r#"{"0": {"a": 1, "b": 0}, "2": {"b": 1}}"#).unwrap();
+    assert_eq!(file, StimulusFile::parse_csv("cycle,a,b\n0,1,0\n2,,0b1\n").unwrap());
+    assert_eq!(file, StimulusFile::parse_csv("cycle,a,b\n0,0X1,0x0\n2,,0B1\n").unwrap());
+
+    let stimulus = Stimulus::from_file(file, top, &circuits).unwrap();
+
+    stimulus.apply(0, &circuits);
+    assert_eq!(output(), "0");
+    stimulus.apply(1, &circuits);
+    assert_eq!(output(), "0");
+    stimulus.apply(2, &circuits);
+    assert_eq!(output(), "1");
+
+    let unknown: StimulusFile = serde_json::from_str(r#"{"0": {"c": 1}}"#).unwrap();
+    assert!(Stimulus::from_file(unknown, top, &circuits).unwrap_err().contains("no input labelled c"));
+}
+
+fn output(circuits: &InstantiatedCircuits, top: CircuitIdx, label: &str) -> u32 {
+    circuits.instantiated_circuits[top].0.components.iter()
+        .find_map(|component| match &component.model {
+            ComponentModel::OutputPin(pin) if component.get_properties().get_string("label").as_deref() == Some(label) => {
+                Some(pin.value.get().get_raw_value() & 1)
+            }
+            _ => None,
+        })
+        .unwrap()
+}
+
+#[test]
+pub fn test_stimulus_uuids() {
+    let button = InputButton::create();
+    let inner_pin = InputPin::create("a");
+    let top_pin = InputPin::create("x");
+    let (button_uuid, inner_uuid, top_uuid) = (button.uuid, inner_pin.uuid, top_pin.uuid);
+
+    // Button driving `y` and a buffer from `a` to `b`
+    let inner = SavedCircuit {
+        components: vec![
+            component((0, 0), button),
+            component((0, 0), OutputPin::create("y")),
+            component((0, 40), inner_pin),
+            component((0, 40), OutputPin::create("b")),
+        ],
+        wires: vec![],
+        bounds: SavedCircuitBounds { start: Location::new(-10, -10), end: Location::new(10, 20) },
+        pins: vec![
+            SavedCircuitPin { location: Location::new(-10, 0), label: "a".to_string(), bit_width: 1, direction: Direction::Input },
+            SavedCircuitPin { location: Location::new(10, 0), label: "y".to_string(), bit_width: 1, direction: Direction::Output },
+            SavedCircuitPin { location: Location::new(10, 10), label: "b".to_string(), bit_width: 1, direction: Direction::Output },
+        ],
+    };
+
+    let main = SavedCircuit {
+        components: vec![
+            component((40, 0), top_pin),
+            component((50, 0), Subcircuit::from_saved_circuit(&inner, "inner")),
+            component((60, 0), OutputPin::create("z")),
+            component((60, 10), OutputPin::create("w")),
+        ],
+        wires: vec![],
+        bounds: SavedCircuitBounds { start: Location::new(0, 0), end: Location::new(0, 0) },
+        pins: vec![],
+    };
+
+    let (top, circuits) = compile_project(ProjectFile {
+        top_circuit: "main".to_string(),
+        circuits: HashMap::from([("main".to_string(), main), ("inner".to_string(), inner)]),
+    }).unwrap();
+    circuits.reset();
+
+    let stimulus = |source: String| {
+        Stimulus::from_file(serde_json::from_str(&source).unwrap(), top, &circuits)
+    };
+
+    stimulus(format!(r#"{{"0": {{"{}": 1, "{}": 1}}}}"#, button_uuid, top_uuid)).unwrap().apply(0, &circuits);
+    assert_eq!((output(&circuits, top, "z"), output(&circuits, top, "w")), (1, 1));
+
+    let error = stimulus(format!(r#"{{"0": {{"{}": 1}}}}"#, inner_uuid)).unwrap_err();
+    assert!(error.contains("inside a subcircuit"));
+}
@@ -0,0 +1,57 @@
+use cirquil::core::analysis::{analyze_circuit, tabulate_definition};
+use cirquil::core::compiler::drc::check_project;
+use cirquil::core::compiler::project::compile_project;
+use cirquil::core::simulation::components::behavioural::definition::Definition;
+use cirquil::core::synthesis::{synthesize, SynthesisError};
+use cirquil::core::synthesis::layout::LayoutError;
+
+const FULL_ADDER: &str = "
+a b c | s carry
+0 0 0 | 0 0
+0 0 1 | 1 0
+0 1 0 | 1 0
+0 1 1 | 0 1
+1 0 0 | 1 0
+1 0 1 | 0 1
+1 1 0 | 0 1
+1 1 1 | 1 1
+";
+
+fn assert_synthesized(source: &str) {
+    let project_file = synthesize(source, "main").unwrap();
+    let (top, circuits) = compile_project(project_file.clone()).unwrap();
+    assert_eq!(check_project(&project_file, &circuits).len(), 0);
+
+    let expected = tabulate_definition(&Definition::parse(source).unwrap(), 8).unwrap();
+    let actual = analyze_circuit(&circuits.instantiated_circuits[top].0, 8).unwrap();
+
+    assert_eq!(actual.inputs, expected.inputs);
+    assert_eq!(actual.outputs, expected.outputs);
+    for (row, (actual, expected)) in actual.rows.iter().zip(expected.rows.iter()).enumerate() {
+        let actual: Vec<String> = actual.iter().map(|value| value.to_bit_string(1)).collect();
+        let expected: Vec<String> = expected.iter().map(|value| value.to_bit_string(1)).collect();
+        assert_eq!(actual, expected, "row {}", row);
+    }
+}
+
+#[test]
+pub fn test_synthesize() {
+    assert_synthesized(FULL_ADDER);
+    assert_synthesized("y = (a ^ b) & ~c\nz = a | ~a\nw = a");
+}
+
+#[test]
+pub fn test_synthesize_errors() {
+    assert_eq!(synthesize("y[2] = a", "main").unwrap_err(), SynthesisError::WidePort { name: "y".to_string() });
+    assert_eq!(synthesize("y = 1", "main").unwrap_err(), SynthesisError::NoInputs);
+
+    // A random table over ten inputs minimizes to more gates than the canvas holds
+    let mut seed: u32 = 1;
+    let mut table = "a b c d e f g h i j | y\n".to_string();
+    for row in 0..1024 {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        let inputs: Vec<String> = (0..10).rev().map(|bit| ((row >> bit) & 1).to_string()).collect();
+        table += &format!("{} | {}\n", inputs.join(" "), (seed >> 16) & 1);
+    }
+    assert_eq!(synthesize(&table, "main").unwrap_err(), SynthesisError::Layout(LayoutError));
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use std::collections::HashMap;
+
+use cirquil::core::canvas::location::Location;
+use cirquil::core::compiler::project::compile_project;
+use cirquil::core::simulation::circuit::CircuitIdx;
+use cirquil::core::simulation::component::ComponentModel;
+use cirquil::core::simulation::components::subcircuit::input_pin::InputPin;
+use cirquil::core::simulation::components::subcircuit::output_pin::OutputPin;
+use cirquil::core::simulation::components::subcircuit::Subcircuit;
+use cirquil::core::simulation::pin::Direction;
+use cirquil::core::simulation::probe::Probe;
+use cirquil::core::simulation::property::{Property, StringProperty};
+use cirquil::core::simulation::trace::Trace;
+use cirquil::core::simulation::value::Value;
+use cirquil::headless::session::read_signal;
+use cirquil::serde::project::{ProjectFile, SavedCircuit, SavedCircuitBounds, SavedCircuitPin};
+use cirquil::serde::vcd::{VcdSignal, write_vcd};
+
+use common::component;
+
+#[test]
+pub fn test_write_vcd() {
+    let mut trace = Trace::default();
+    let clock = trace.add_row();
+    let bus = trace.add_row();
+
+    trace.add_sample(vec![(clock, Value::create(0, 1)), (bus, Value::default())]);
+    trace.add_sample(vec![(clock, Value::create(1, 1)), (bus, Value::create(5, 4))]);
+    trace.add_sample(vec![(clock, Value::create(1, 1)), (bus, Value::new(0b1000, 0b1000))]);
+
+    let signals = vec![
+        VcdSignal { name: "clk".to_string(), scope: vec!["main".to_string()], bit_width: 1, trace_idx: clock },
+        VcdSignal {
+            name: "data bus".to_string(),
+            scope: vec!["main".to_string(), "alu".to_string()],
+            bit_width: 4,
+            trace_idx: bus,
+        },
+    ];
+
+    let mut output = Vec::new();
+    write_vcd(&mut output, &signals, &trace).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    let body = output.split_once("$timescale 1 ns $end\n").unwrap().1;
+    assert_eq!(body, "\
+$scope module main $end
+$var wire 1 ! clk $end
+$scope module alu $end
+$var wire 4 \" data_bus $end
+$upscope $end
+$upscope $end
+$enddefinitions $end
+#0
+$dumpvars
+0!
+bzzzz \"
+$end
+#1
+1!
+b0101 \"
+#2
+bx000 \"
+#3
+");
+}
+
+#[test]
+pub fn test_instance_scopes() {
+    // Buffer from `a` to `y`
+    let buffer = SavedCircuit {
+        components: vec![component((0, 0), InputPin::create("a")), component((0, 0), OutputPin::create("y"))],
+        wires: vec![],
+        bounds: SavedCircuitBounds { start: Location::new(-10, -10), end: Location::new(10, 10) },
+        pins: vec![
+            SavedCircuitPin { location: Location::new(-10, 0), label: "a".to_string(), bit_width: 1, direction: Direction::Input },
+            SavedCircuitPin { location: Location::new(10, 0), label: "y".to_string(), bit_width: 1, direction: Direction::Output },
+        ],
+    };
+
+    let mut left = Subcircuit::from_saved_circuit(&buffer, "buffer");
+    left.properties.set("label", Property::String(StringProperty::new("left".to_string())));
+    let right = Subcircuit::from_saved_circuit(&buffer, "buffer");
+    let right_uuid = right.uuid.to_string();
+
+    let main = SavedCircuit {
+        components: vec![
+            component((40, 0), InputPin::create("x")),
+            component((50, 0), left),
+            component((50, 40), right),
+        ],
+        wires: vec![],
+        bounds: SavedCircuitBounds { start: Location::new(0, 0), end: Location::new(0, 0) },
+        pins: vec![],
+    };
+
+    let (top, circuits) = compile_project(ProjectFile {
+        top_circuit: "main".to_string(),
+        circuits: HashMap::from([("main".to_string(), main), ("buffer".to_string(), buffer)]),
+    }).unwrap();
+    circuits.reset();
+
+    let top_circuit = &circuits.instantiated_circuits[top].0;
+    let ComponentModel::InputPin(x) = &top_circuit.components[0].model else { unreachable!() };
+    x.value.set(Value::create(1, 1));
+    top_circuit.propagate_all();
+
+    let left_idx = circuits.find_instance("main/left").unwrap();
+    let right_idx = circuits.find_instance(&format!("main/{}", right_uuid)).unwrap();
+    assert_ne!(left_idx, right_idx);
+
+    let probe = |circuit: CircuitIdx| Probe {
+        name: "y".to_string(),
+        circuit,
+        wire: circuits.instantiated_circuits[circuit].0.components[1].get_pins()[0].wire.get().unwrap(),
+    };
+    assert_eq!(probe(left_idx).to_vcd_signal("y", 0, &circuits).scope, vec!["main".to_string(), "left".to_string()]);
+    assert_eq!(probe(right_idx).to_vcd_signal("y", 1, &circuits).scope, vec!["main".to_string(), right_uuid.clone()]);
+
+    // Only the labelled instance is driven by `x`
+    assert_eq!(read_signal("left/y", [], &circuits).unwrap(), (Value::create(1, 1), 1));
+    assert_ne!(read_signal(&format!("{}/y", right_uuid), [], &circuits).unwrap().0, Value::create(1, 1));
+}
@@ -0,0 +1,33 @@
+use cirquil::core::analysis::vectors::{run_test_vectors, VectorFailure};
+use cirquil::core::compiler::project::compile_project;
+use cirquil::core::synthesis::synthesize;
+use cirquil::serde::vectors::TestVectors;
+
+#[test]
+pub fn test_run_vectors() {
+    let (top, circuits) = compile_project(synthesize("s = a ^ b\nc = a & b", "main").unwrap()).unwrap();
+    let circuit = &circuits.instantiated_circuits[top].0;
+
+    let vectors = TestVectors::parse("
+        # half adder
+        a b  s c[1]
+        0 0  0 0
+        0 1  1 x
+        1 1  1 1   # wrong sum
+    ").unwrap();
+
+    let results = run_test_vectors(circuit, &vectors).unwrap();
+    assert_eq!(results.iter().map(|result| result.line).collect::<Vec<_>>(), vec![4, 5, 6]);
+    assert!(results[0].passed() && results[1].passed());
+    assert_eq!(results[2].failures, vec![VectorFailure {
+        name: "s".to_string(),
+        expected: "1".to_string(),
+        actual: "0".to_string(),
+    }]);
+
+    let csv = TestVectors::parse_csv("a,b,s,c\n1,0,1,0\n").unwrap();
+    assert!(run_test_vectors(circuit, &csv).unwrap()[0].passed());
+
+    let unknown = TestVectors::parse("a q\n0 0").unwrap();
+    assert_eq!(run_test_vectors(circuit, &unknown).unwrap_err().message, "circuit has no pin labelled q");
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use cirquil::core::analysis::vectors::run_test_vectors;
+use cirquil::core::canvas::location::Location;
+use cirquil::core::compiler::project::compile_project;
+use cirquil::core::simulation::components::clock_generator::ClockGenerator;
+use cirquil::core::simulation::components::subcircuit::input_pin::InputPin;
+use cirquil::core::simulation::components::subcircuit::output_pin::OutputPin;
+use cirquil::core::simulation::components::subcircuit::Subcircuit;
+use cirquil::core::simulation::pin::Direction;
+use cirquil::core::synthesis::synthesize;
+use cirquil::serde::project::{SavedCircuit, SavedCircuitBounds, SavedComponent};
+use cirquil::serde::vectors::{TestVectors, VectorColumn, VectorRow, VectorValue};
+use cirquil::serde::verilog::{export_verilog, VerilogError};
+
+enum Statement {
+    Assign(String, Vec<String>),
+    Instance(String, Vec<(String, String)>),
+}
+
+struct Module {
+    ports: Vec<String>,
+    statements: Vec<Statement>,
+}
+
+/// Reads back the subset of Verilog the exporter writes for one-bit nets.
+fn parse_modules(verilog: &str) -> HashMap<String, Module> {
+    let mut modules = HashMap::new();
+    let mut current: Option<(String, Module)> = None;
+
+    for line in verilog.lines().map(|line| line.trim().trim_end_matches(';')) {
+        let tokens: Vec<&str> = line.split(|c: char| c.is_whitespace() || "(),".contains(c))
+            .filter(|token| !token.is_empty())
+            .collect();
+
+        match tokens.first().copied() {
+            Some("module") => {
+                let ports = tokens[2..].iter().map(|port| port.to_string()).collect();
+                current = Some((tokens[1].to_string(), Module { ports, statements: vec![] }));
+            }
+            Some("endmodule") => {
+                let (name, module) = current.take().unwrap();
+                modules.insert(name, module);
+            }
+            Some("assign") => {
+                let statement = Statement::Assign(tokens[1].to_string(), tokens[3..].iter().map(|token| token.to_string()).collect());
+                current.as_mut().unwrap().1.statements.push(statement);
+            }
+            Some("input" | "output" | "wire") | None => {}
+            Some(module) => {
+                let connections = tokens[2..].iter()
+                    .map(|connection| connection.trim_start_matches('.'))
+                    .collect::<Vec<&str>>()
+                    .chunks(2)
+                    .map(|pair| (pair[0].to_string(), pair[1].to_string()))
+                    .collect();
+
+                current.as_mut().unwrap().1.statements.push(Statement::Instance(module.to_string(), connections));
+            }
+        }
+    }
+
+    modules
+}
+
+fn evaluate(modules: &HashMap<String, Module>, name: &str, inputs: HashMap<String, bool>) -> HashMap<String, bool> {
+    let module = &modules[name];
+    let mut nets = inputs;
+
+    // Assignments are not ordered, repeat until every net settles
+    for _ in 0..module.statements.len() {
+        for statement in module.statements.iter() {
+            match statement {
+                Statement::Assign(lhs, rhs) => {
+                    let value = |operand: &str| match operand.strip_prefix('~') {
+                        Some(operand) => nets.get(operand).map(|value| !value),
+                        None => nets.get(operand).copied(),
+                    };
+
+                    let result = match rhs.as_slice() {
+                        [operand] => value(operand),
+                        [a, op, b] => value(a).zip(value(b)).map(|(a, b)| if op == "&" { a & b } else { a | b }),
+                        _ => panic!("unexpected assignment"),
+                    };
+
+                    if let Some(result) = result {
+                        nets.insert(lhs.clone(), result);
+                    }
+                }
+                Statement::Instance(module, connections) => {
+                    let inputs = connections.iter()
+                        .filter_map(|(port, net)| nets.get(net).map(|value| (port.clone(), *value)))
+                        .collect();
+
+                    let outputs = evaluate(modules, module, inputs);
+                    for (port, net) in connections.iter() {
+                        if let Some(value) = outputs.get(port) {
+                            nets.insert(net.clone(), *value);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    nets.retain(|net, _| module.ports.contains(net));
+    nets
+}
+
+#[test]
+pub fn test_verilog_round_trip() {
+    let mut project = synthesize("s = a ^ b ^ cin\ncout = a & b | cin & (a ^ b)", "adder").unwrap();
+
+    // Top circuit with pins placed right on the ports of an adder instance
+    let adder = &project.circuits["adder"];
+    let origin = Location::new(200, 200);
+
+    let mut components = vec![SavedComponent { location: origin, component: Subcircuit::from_saved_circuit(adder, "adder") }];
+    for pin in adder.pins.iter() {
+        let component = match pin.direction {
+            Direction::Input => InputPin::create(&pin.label),
+            _ => OutputPin::create(&pin.label),
+        };
+
+        components.push(SavedComponent { location: origin + pin.location, component });
+    }
+
+    project.circuits.insert("main".to_string(), SavedCircuit {
+        components,
+        wires: vec![],
+        bounds: SavedCircuitBounds { start: Location::new(0, 0), end: Location::new(0, 0) },
+        pins: vec![],
+    });
+    project.top_circuit = "main".to_string();
+
+    let verilog = export_verilog(&project).unwrap();
+    assert!(verilog.starts_with("module main(a, b, cin, s, cout);"));
+    assert!(verilog.contains("adder i0(.a(n"));
+
+    let modules = parse_modules(&verilog);
+    let names = ["a", "b", "cin", "s", "cout"];
+
+    // Expected outputs come from the exported Verilog, the simulator has to agree
+    let rows = (0..8u32)
+        .map(|vector| {
+            let inputs = names[..3].iter().enumerate()
+                .map(|(idx, name)| (name.to_string(), vector >> (2 - idx) & 1 == 1))
+                .collect();
+
+            let outputs = evaluate(&modules, "main", inputs);
+            let values = names.iter()
+                .map(|name| VectorValue { bits: outputs[*name] as u32, care: u32::MAX })
+                .collect();
+
+            VectorRow { line: vector as usize + 2, values }
+        })
+        .collect();
+
+    let vectors = TestVectors {
+        columns: names.iter().map(|name| VectorColumn { name: name.to_string(), bit_width: None }).collect(),
+        rows,
+    };
+
+    let (top, circuits) = compile_project(project.clone()).unwrap();
+    circuits.reset();
+
+    let results = run_test_vectors(&circuits.instantiated_circuits[top].0, &vectors).unwrap();
+    assert!(results.iter().all(|result| result.passed()));
+
+    project.circuits.get_mut("adder").unwrap().components.push(SavedComponent {
+        location: Location::new(0, 0),
+        component: ClockGenerator::create(),
+    });
+    assert!(matches!(export_verilog(&project), Err(VerilogError::Unsupported { .. })));
+}
+
+#[test]
+pub fn test_verilog_names() {
+    // Generated net names move aside for ports with the same name
+    let mut project = synthesize("n0 = a & b\nu0_0 = a", "main").unwrap();
+    let verilog = export_verilog(&project).unwrap();
+    let declarations: Vec<&str> = verilog.lines()
+        .filter(|line| ["input", "output", "wire"].iter().any(|keyword| line.trim().starts_with(keyword)))
+        .map(|line| line.trim().trim_end_matches(';').rsplit(' ').next().unwrap())
+        .collect();
+    for (idx, name) in declarations.iter().enumerate() {
+        assert!(!declarations[idx + 1..].contains(name), "{} is declared twice", name);
+    }
+    assert!(!verilog.contains("assign n0 = n0;"));
+
+    let component = |component| SavedComponent { location: Location::new(500, 500), component };
+
+    let main = project.circuits.get_mut("main").unwrap();
+    main.components.push(component(OutputPin::create("n0")));
+    assert_eq!(export_verilog(&project), Err(VerilogError::DuplicatePort {
+        circuit: "main".to_string(),
+        name: "n0".to_string(),
+    }));
+
+    let main = project.circuits.get_mut("main").unwrap();
+    main.components.last_mut().unwrap().component = InputPin::create("");
+    assert_eq!(export_verilog(&project), Err(VerilogError::UnlabeledPort { circuit: "main".to_string() }));
+}